@@ -1,10 +1,11 @@
 use proc_macro::TokenStream;
-use quote::quote;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
 use syn::{Data, Ident, parse_macro_input};
 
-/// Derives the `AsReactive` and `ReactiveCapture` traits for a struct, while also
-/// providing a new reactive struct with the same field names, but they are wrapped
-/// in the Leptos `RwSignal`.
+/// Derives the `AsReactive`, `ReactiveCapture` and `DirtyTracking` traits for a struct, while
+/// also providing a new reactive struct with the same field names, but they are wrapped
+/// in the Leptos `RwSignal`. The reactive struct only contains signals, so it is `Copy`.
 ///
 /// Fields that are custom types are not automatically converted into reactive types. They
 /// will be wrapped in an `RwSignal` struct, but that is where this process stops. Making
@@ -49,93 +50,434 @@ use syn::{Data, Ident, parse_macro_input};
 /// let captured_user = user_reactive.capture()
 /// ```
 ///
-/// # Important Considerations
+/// # Nested Reactive Types
 ///
-/// A reminder that custom types, in this case `CustomType`, are just wrapped in an `RwSignal`.
-/// They are not automatically converted to their own `Reactive` type in the case that they
-/// derive this macro. As a result, custom reactive types *must* be updated manually by utilizing
-/// the `RwSignal::update` and `RwSignal::set` methods.
+/// Custom types, in this case `CustomType`, are just wrapped in an `RwSignal` by default. Fields
+/// whose types also derive `Reactive` can opt in to being converted using `#[reactive(nested)]`:
+/// ```ignore
+/// #[derive(Reactive)]
+/// struct User {
+///     name: String,
+///     #[reactive(nested)]
+///     address: Address,
+///     #[reactive(nested)]
+///     contacts: Vec<Contact>,
+/// }
 ///
-/// For example, the `UserReactive` struct from earlier does not convert the `CustomType` into a
-/// `CustomTypeReactive`. We have to do that ourselves:
+/// // This struct will be created:
+/// struct UserReactive {
+///     name: RwSignal<String>,
+///     address: AddressReactive,
+///     contacts: RwSignal<Vec<ContactReactive>>,
+/// }
 /// ```
-/// let user: User = User::new();
-/// let user_reactive: UserReactive = user.as_reactive();
-/// let custom_reactive: CustomTypeReactive = user_reactive.custom.get().as_reactive();
-/// // And then convert back...
-/// user_reactive.custom.set(custom_reactive.capture());
+/// Nested fields are converted with `as_reactive` and `capture` along with the rest of the struct.
+/// A nested `Vec<T>` is still wrapped in an `RwSignal`, so that entries can be added and removed.
+///
+/// # Supported Shapes
+///
+/// Structs with named fields, tuple structs and unit structs are supported. A tuple struct creates
+/// a reactive tuple struct, so `struct Pair(i32, String)` creates
+/// `struct PairReactive(RwSignal<i32>, RwSignal<String>)`. Generic structs are supported as long
+/// as every field can be stored in an `RwSignal` and compared, which adds
+/// `Clone + PartialEq + Send + Sync + 'static` bounds to the reactive struct, or `AsReactive`
+/// bounds for nested fields.
+///
+/// Enums and unions are not supported. Wrap them in an `RwSignal` field instead.
+///
+/// # Dirty Tracking
+///
+/// The reactive struct keeps a snapshot of the values it was created with, and implements
+/// `DirtyTracking` by comparing every field against it. Because of this, every field must also
+/// implement `PartialEq`. A `UserPatch` struct is also created, which has the same fields wrapped
+/// in an `Option`, and is returned by `capture_patch`:
+/// ```ignore
+/// let user_reactive = user.as_reactive();
+/// user_reactive.name.set("Jane".to_string());
+///
+/// if user_reactive.is_dirty() {
+///     let patch: UserPatch = user_reactive.capture_patch();
+///     // Only `patch.name` is `Some`.
+///     save(patch);
+///     user_reactive.mark_saved();
+/// }
 /// ```
-/// If a shorthand for the `capture` and `as_reactive` functions are absolutely *required*, then this
-/// macro is probably not the way to go - as of now, this functionality should be implemented manually.
-#[proc_macro_derive(Reactive)]
+/// Nested fields are tracked by their own reactive struct, and nested lists are dirty when any
+/// entry changes or when entries are added, removed or reordered.
+#[proc_macro_derive(Reactive, attributes(reactive))]
 pub fn make_reactive(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as syn::DeriveInput);
 
+    match expand_reactive(input) {
+        Ok(expanded) => expanded,
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// How a single field is converted into its reactive version.
+enum ReactiveField {
+    /// The field is wrapped in an `RwSignal`.
+    Signal,
+    /// The field is converted into its own reactive type.
+    Nested,
+    /// The field is a `Vec` whose items are converted into their reactive type. Contains the item
+    /// type.
+    NestedList(syn::Type),
+}
+
+/// Gets the item type of a `Vec<T>`.
+fn vec_item_type(ty: &syn::Type) -> Option<syn::Type> {
+    let syn::Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != "Vec" {
+        return None;
+    }
+
+    match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) if args.args.len() == 1 => match &args.args[0] {
+            syn::GenericArgument::Type(item) => Some(item.clone()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn parse_reactive_field(field: &syn::Field) -> syn::Result<ReactiveField> {
+    let mut nested = false;
+
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("reactive"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("nested") {
+                nested = true;
+                Ok(())
+            } else {
+                Err(meta.error("expected `nested`"))
+            }
+        })?;
+    }
+
+    Ok(match (nested, vec_item_type(&field.ty)) {
+        (false, _) => ReactiveField::Signal,
+        (true, Some(item)) => ReactiveField::NestedList(item),
+        (true, None) => ReactiveField::Nested,
+    })
+}
+
+fn expand_reactive(input: syn::DeriveInput) -> syn::Result<TokenStream> {
     let name = input.ident;
+    let vis = input.vis;
     let reactive_name = Ident::new(&format!("{}Reactive", name), name.span());
+    let patch_name = Ident::new(&format!("{}Patch", name), name.span());
 
-    let fields = if let Data::Struct(data) = input.data {
-        data.fields
-    } else {
-        panic!("Only structs can be reactive")
+    let fields = match input.data {
+        Data::Struct(data) => data.fields,
+        Data::Enum(data) => {
+            return Err(syn::Error::new(
+                data.enum_token.span,
+                "Reactive can't be derived for enums. Wrap the enum in an `RwSignal` field instead.",
+            ));
+        }
+        Data::Union(data) => {
+            return Err(syn::Error::new(
+                data.union_token.span,
+                "Reactive can't be derived for unions.",
+            ));
+        }
     };
 
     // Build the new fields.
     let mut new_fields = Vec::new();
+    let mut patch_fields = Vec::new();
+    let mut new_fn_lets = Vec::new();
     let mut new_fn_args = Vec::new();
+    let mut snapshot_args = Vec::new();
     let mut from_fn_args = Vec::new();
+    let mut changed_checks = Vec::new();
+    let mut patch_args = Vec::new();
+    let mut patch_defaults = Vec::new();
+    let mut reset_steps = Vec::new();
+    let mut save_steps = Vec::new();
+    let mut bounds: Vec<syn::WherePredicate> = Vec::new();
 
-    for field in fields {
-        let field_name = field.ident.unwrap();
-        let field_type = field.ty;
-
-        let new_field = quote! {
-            pub #field_name: _RwSignal<#field_type>,
+    for (i, field) in fields.iter().enumerate() {
+        let kind = parse_reactive_field(field)?;
+        let field_type = &field.ty;
+        // Tuple struct fields are named by their index, which also works in struct expressions.
+        let (member, field_label) = match &field.ident {
+            Some(ident) => (syn::Member::Named(ident.clone()), ident.to_string()),
+            None => (syn::Member::Unnamed(syn::Index::from(i)), i.to_string()),
         };
+        let local = format_ident!("field_{}", i);
 
-        new_fields.push(new_field);
-
-        // Create new function arguments/inits
-        let new_fn_arg = quote! {
-            #field_name: _RwSignal::new(self.#field_name),
+        // Each field is described by its reactive type, how it's created, how it's captured from
+        // a reference to the reactive field, how it's compared against the snapshot, and how it's
+        // reset and saved.
+        let capture = |access: TokenStream2| match &kind {
+            ReactiveField::Signal => {
+                quote! { ::leptos::prelude::GetUntracked::get_untracked(#access) }
+            }
+            ReactiveField::Nested => quote! { ::traits::ReactiveCapture::capture(#access) },
+            ReactiveField::NestedList(_) => quote! {
+                ::leptos::prelude::WithUntracked::with_untracked(#access, |items| {
+                    items.iter().map(::traits::ReactiveCapture::capture).collect()
+                })
+            },
         };
 
-        let from_fn_arg = quote! {
-            #field_name: self.#field_name.get_untracked(),
+        let (new_type, new_fn_arg, changed_check) = match &kind {
+            ReactiveField::Signal => {
+                bounds.push(syn::parse_quote! {
+                    #field_type: ::core::clone::Clone + ::core::cmp::PartialEq
+                        + ::core::marker::Send + ::core::marker::Sync + 'static
+                });
+                let changed_check = quote! {
+                    ::leptos::prelude::With::with(&self.#member, |value| value != &snapshot.#member)
+                };
+                reset_steps.push(quote! {
+                    ::leptos::prelude::Set::set(
+                        &self.#member,
+                        ::core::clone::Clone::clone(&snapshot.#member),
+                    );
+                });
+                (
+                    quote! { ::leptos::prelude::RwSignal<#field_type> },
+                    quote! { ::leptos::prelude::RwSignal::new(self.#member) },
+                    changed_check,
+                )
+            }
+            ReactiveField::Nested => {
+                bounds.push(syn::parse_quote! { #field_type: ::traits::AsReactive });
+                bounds.push(syn::parse_quote! {
+                    <#field_type as ::traits::AsReactive>::ReactiveType:
+                        ::traits::ReactiveCapture<CaptureType = #field_type> + ::traits::DirtyTracking
+                        + ::core::marker::Copy
+                });
+                reset_steps.push(quote! { ::traits::DirtyTracking::reset(&self.#member); });
+                save_steps.push(quote! { ::traits::DirtyTracking::mark_saved(&self.#member); });
+                (
+                    quote! { <#field_type as ::traits::AsReactive>::ReactiveType },
+                    quote! { ::traits::AsReactive::as_reactive(self.#member) },
+                    quote! { ::traits::DirtyTracking::is_dirty(&self.#member) },
+                )
+            }
+            ReactiveField::NestedList(item) => {
+                bounds.push(syn::parse_quote! {
+                    #item: ::traits::AsReactive + ::core::clone::Clone + ::core::cmp::PartialEq
+                });
+                bounds.push(syn::parse_quote! {
+                    <#item as ::traits::AsReactive>::ReactiveType:
+                        ::traits::ReactiveCapture<CaptureType = #item> + ::traits::DirtyTracking
+                        + ::core::marker::Send + ::core::marker::Sync + 'static
+                });
+                // Every item is checked so that changes to any item are tracked, and then the
+                // items are compared in case they were added, removed or reordered.
+                let changed_check = quote! {
+                    ::leptos::prelude::With::with(&self.#member, |items| {
+                        items.iter().any(::traits::DirtyTracking::is_dirty)
+                            || items
+                                .iter()
+                                .map(::traits::ReactiveCapture::capture)
+                                .collect::<::std::vec::Vec<_>>()
+                                != snapshot.#member
+                    })
+                };
+                reset_steps.push(quote! {
+                    ::leptos::prelude::Set::set(
+                        &self.#member,
+                        snapshot
+                            .#member
+                            .iter()
+                            .cloned()
+                            .map(::traits::AsReactive::as_reactive)
+                            .collect(),
+                    );
+                });
+                save_steps.push(quote! {
+                    ::leptos::prelude::WithUntracked::with_untracked(&self.#member, |items| {
+                        items.iter().for_each(::traits::DirtyTracking::mark_saved)
+                    });
+                });
+                (
+                    quote! { ::leptos::prelude::RwSignal<::std::vec::Vec<<#item as ::traits::AsReactive>::ReactiveType>> },
+                    quote! {
+                        ::leptos::prelude::RwSignal::new(
+                            self.#member.into_iter().map(::traits::AsReactive::as_reactive).collect()
+                        )
+                    },
+                    changed_check,
+                )
+            }
         };
 
-        new_fn_args.push(new_fn_arg);
-        from_fn_args.push(from_fn_arg);
+        new_fields.push(match &field.ident {
+            Some(field_name) => quote! { pub #field_name: #new_type, },
+            None => quote! { pub #new_type, },
+        });
+        patch_fields.push(match &field.ident {
+            Some(field_name) => quote! { pub #field_name: ::core::option::Option<#field_type>, },
+            None => quote! { pub ::core::option::Option<#field_type>, },
+        });
+        new_fn_lets.push(quote! { let #local: #new_type = #new_fn_arg; });
+        new_fn_args.push(quote! { #member: #local, });
+        let snapshot_capture = capture(quote! { &#local });
+        snapshot_args.push(quote! { #member: #snapshot_capture, });
+        let self_capture = capture(quote! { &self.#member });
+        from_fn_args.push(quote! { #member: #self_capture, });
+        patch_args
+            .push(quote! { #member: changed.contains(&#field_label).then_some(current.#member), });
+        patch_defaults.push(quote! { #member: ::core::option::Option::None, });
+        changed_checks.push(quote! {
+            if #changed_check {
+                changed.push(#field_label);
+            }
+        });
     }
 
+    // Generic fields must be able to be stored in a signal. Concrete types are checked when the
+    // reactive struct is created, so they don't need any extra bounds.
+    let mut generics = input.generics.clone();
+    if !generics.params.is_empty() {
+        let (_, ty_generics, _) = input.generics.split_for_impl();
+        bounds.push(syn::parse_quote! {
+            #name #ty_generics: ::core::marker::Send + ::core::marker::Sync + 'static
+        });
+        generics.make_where_clause().predicates.extend(bounds);
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
     let reactive_docs = format!(
         "Reactive version of the [`{name}`] struct. Automatically created by the `Reactive` macro."
     );
+    let patch_docs = format!(
+        "The changed fields of a [`{reactive_name}`] struct. Automatically created by the `Reactive` macro."
+    );
+
+    // Structs without fields have nothing to track, so they don't keep a snapshot.
+    let snapshot_type = quote! { ::leptos::prelude::StoredValue<#name #ty_generics> };
+    let (reactive_struct, patch_struct) = match &fields {
+        syn::Fields::Named(_) if !fields.is_empty() => (
+            quote! {
+                #vis struct #reactive_name #impl_generics #where_clause {
+                    #(#new_fields)*
+                    __snapshot: #snapshot_type,
+                }
+            },
+            quote! {
+                #vis struct #patch_name #impl_generics #where_clause {
+                    #(#patch_fields)*
+                }
+            },
+        ),
+        syn::Fields::Unnamed(_) if !fields.is_empty() => (
+            quote! {
+                #vis struct #reactive_name #impl_generics (#(#new_fields)* #snapshot_type) #where_clause;
+            },
+            quote! {
+                #vis struct #patch_name #impl_generics (#(#patch_fields)*) #where_clause;
+            },
+        ),
+        _ => (
+            quote! { #vis struct #reactive_name; },
+            quote! { #vis struct #patch_name; },
+        ),
+    };
+    let snapshot_index = syn::Member::Unnamed(syn::Index::from(fields.len()));
+    let snapshot_member = match &fields {
+        syn::Fields::Unnamed(_) => quote! { #snapshot_index },
+        _ => quote! { __snapshot },
+    };
+
+    let (new_fn_body, changed_fields_body, patch_body, reset_body, mark_saved_body) = if fields
+        .is_empty()
+    {
+        (
+            quote! { #reactive_name {} },
+            quote! { ::std::vec::Vec::new() },
+            quote! { #patch_name {} },
+            quote! {},
+            quote! {},
+        )
+    } else {
+        (
+            quote! {
+                #(#new_fn_lets)*
+                let snapshot = #name {
+                    #(#snapshot_args)*
+                };
+                #reactive_name {
+                    #(#new_fn_args)*
+                    #snapshot_member: ::leptos::prelude::StoredValue::new(snapshot),
+                }
+            },
+            quote! {
+                ::leptos::prelude::WithValue::with_value(&self.#snapshot_member, |snapshot| {
+                    let mut changed = ::std::vec::Vec::new();
+                    #(#changed_checks)*
+                    changed
+                })
+            },
+            quote! {
+                let changed = ::leptos::prelude::untrack(|| ::traits::DirtyTracking::changed_fields(self));
+                let current = ::traits::ReactiveCapture::capture(self);
+                #patch_name {
+                    #(#patch_args)*
+                }
+            },
+            quote! {
+                ::leptos::prelude::WithValue::with_value(&self.#snapshot_member, |snapshot| {
+                    #(#reset_steps)*
+                });
+            },
+            quote! {
+                #(#save_steps)*
+                let current = ::traits::ReactiveCapture::capture(self);
+                ::leptos::prelude::SetValue::set_value(&self.#snapshot_member, current);
+            },
+        )
+    };
 
     // Expand the macro. The output is a struct with new reactive fields and a new function,
-    // which initializes the reactive fields based on the original struct's fields.
+    // which initializes the reactive fields based on the original struct's fields. The reactive
+    // struct also keeps a snapshot of the original struct, which is used for dirty tracking.
     let expanded = quote! {
-        use leptos::prelude::RwSignal as _RwSignal;  // in case the user imports it themselves
-        use leptos::prelude::GetUntracked as _;
-        use traits::{AsReactive, ReactiveCapture};
-
         #[doc = #reactive_docs]
-        pub struct #reactive_name {
-            #(#new_fields)*
+        #reactive_struct
+
+        #[doc = #patch_docs]
+        #patch_struct
+
+        impl #impl_generics ::core::default::Default for #patch_name #ty_generics #where_clause {
+            fn default() -> Self {
+                #patch_name {
+                    #(#patch_defaults)*
+                }
+            }
         }
 
-        impl AsReactive for #name {
-            type ReactiveType = #reactive_name;
+        impl #impl_generics ::core::clone::Clone for #reactive_name #ty_generics #where_clause {
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+
+        impl #impl_generics ::core::marker::Copy for #reactive_name #ty_generics #where_clause {}
+
+        impl #impl_generics ::traits::AsReactive for #name #ty_generics #where_clause {
+            type ReactiveType = #reactive_name #ty_generics;
 
             fn as_reactive(self) -> Self::ReactiveType {
-                #reactive_name {
-                    #(#new_fn_args)*
-                }
+                #new_fn_body
             }
         }
 
-        impl ReactiveCapture for #reactive_name {
-            type CaptureType = #name;
+        impl #impl_generics ::traits::ReactiveCapture for #reactive_name #ty_generics #where_clause {
+            type CaptureType = #name #ty_generics;
 
             fn capture(&self) -> Self::CaptureType {
                 #name {
@@ -143,6 +485,189 @@ pub fn make_reactive(input: TokenStream) -> TokenStream {
                 }
             }
         }
+
+        impl #impl_generics ::traits::DirtyTracking for #reactive_name #ty_generics #where_clause {
+            type Patch = #patch_name #ty_generics;
+
+            fn changed_fields(&self) -> ::std::vec::Vec<&'static str> {
+                #changed_fields_body
+            }
+
+            fn capture_patch(&self) -> Self::Patch {
+                #patch_body
+            }
+
+            fn reset(&self) {
+                #reset_body
+            }
+
+            fn mark_saved(&self) {
+                #mark_saved_body
+            }
+        }
+    };
+
+    Ok(TokenStream::from(expanded))
+}
+
+/// Settings from a field's `#[value(...)]` attribute.
+struct ValueFieldOptions {
+    /// The key of the field in the map. Defaults to the field's name.
+    key: String,
+    /// Whether a missing value should use the type's `Default` instead of being an error.
+    default: bool,
+}
+
+fn parse_value_options(field: &syn::Field) -> syn::Result<ValueFieldOptions> {
+    let mut options = ValueFieldOptions {
+        key: field.ident.as_ref().unwrap().to_string(),
+        default: false,
+    };
+
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("value"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                options.key = meta.value()?.parse::<syn::LitStr>()?.value();
+                Ok(())
+            } else if meta.path.is_ident("default") {
+                options.default = true;
+                Ok(())
+            } else {
+                Err(meta.error("expected `rename = \"...\"` or `default`"))
+            }
+        })?;
+    }
+
+    Ok(options)
+}
+
+/// Derives the `ValueMap` and `ValueField` traits for a struct, which convert it to and from the
+/// `HashMap<String, ValueType>` that forms and tables store, as well as `ExpandableInfo`.
+///
+/// Every field's type must implement `ValueField`. This includes `String`, numbers, `bool`,
+/// `Option<T>`, `Vec<T>`, `HashMap<String, ValueType>`, `ValueType` and other structs that derive
+/// `ValueMap`, which are stored as nested maps.
+///
+/// Fields can be configured using the `value` attribute:
+/// - `#[value(rename = "key")]` reads and writes the field using a different key.
+/// - `#[value(default)]` uses the type's `Default` value when the field is missing, instead of
+///   returning an error.
+///
+/// `Option<T>` fields are always allowed to be missing, and are left out of the map when they
+/// are `None`.
+///
+/// ```ignore
+/// #[derive(ValueMap)]
+/// struct LoanerEntry {
+///     subject: String,
+///     first_name: String,
+///     #[value(rename = "date_taken")]
+///     date: String,
+///     notes: Option<String>,
+/// }
+///
+/// let entry = LoanerEntry::from_map(&map)?;
+/// let map = entry.to_map();
+/// ```
+///
+/// # Important Considerations
+///
+/// The generated code refers to `crate::common`, so this macro can only be used inside the main
+/// crate.
+#[proc_macro_derive(ValueMap, attributes(value))]
+pub fn make_value_map(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as syn::DeriveInput);
+
+    let name = input.ident;
+
+    let fields = match input.data {
+        Data::Struct(syn::DataStruct {
+            fields: syn::Fields::Named(fields),
+            ..
+        }) => fields.named,
+        _ => {
+            return syn::Error::new(
+                name.span(),
+                "ValueMap can only be derived for structs with named fields",
+            )
+            .to_compile_error()
+            .into();
+        }
+    };
+
+    let mut to_map_fields = Vec::new();
+    let mut from_map_fields = Vec::new();
+
+    for field in fields {
+        let options = match parse_value_options(&field) {
+            Ok(options) => options,
+            Err(err) => return err.to_compile_error().into(),
+        };
+        let field_name = field.ident.unwrap();
+        let field_type = field.ty;
+        let key = options.key;
+
+        to_map_fields.push(quote! {
+            let value = _ValueField::to_value(&self.#field_name);
+            if !value.is_null() {
+                map.insert(#key.to_string(), value);
+            }
+        });
+
+        let read = if options.default {
+            quote! {
+                match map.get(#key).filter(|value| !value.is_empty()) {
+                    Some(value) => <#field_type as _ValueField>::from_value(value),
+                    None => Ok(::core::default::Default::default()),
+                }
+            }
+        } else {
+            quote! { <#field_type as _ValueField>::from_field(map.get(#key)) }
+        };
+
+        from_map_fields.push(quote! {
+            #field_name: (#read).map_err(|err| format!("{}: {}", #key, err))?,
+        });
+    }
+
+    // Expand the macro. The struct is converted field by field, and can also be stored inside
+    // other structs as a nested map.
+    let expanded = quote! {
+        const _: () = {
+            use crate::common::{ValueField as _ValueField, ValueMap as _ValueMap, ValueType as _ValueType};
+            use std::collections::HashMap as _HashMap;
+
+            impl _ValueMap for #name {
+                fn to_map(&self) -> _HashMap<String, _ValueType> {
+                    let mut map = _HashMap::new();
+                    #(#to_map_fields)*
+                    map
+                }
+
+                fn from_map(map: &_HashMap<String, _ValueType>) -> Result<Self, String> {
+                    Ok(#name {
+                        #(#from_map_fields)*
+                    })
+                }
+            }
+
+            impl _ValueField for #name {
+                fn to_value(&self) -> _ValueType {
+                    _ValueType::Map(Some(_ValueMap::to_map(self)))
+                }
+
+                fn from_value(value: &_ValueType) -> Result<Self, String> {
+                    match value {
+                        _ValueType::Map(Some(map)) => _ValueMap::from_map(map),
+                        _ => Err(format!("expected a map, found {:?}", value)),
+                    }
+                }
+            }
+        };
     };
 
     TokenStream::from(expanded)
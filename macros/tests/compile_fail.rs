@@ -0,0 +1,6 @@
+/// Shapes that the derives don't support should fail with a clear error instead of a panic.
+#[test]
+fn unsupported_shapes() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+}
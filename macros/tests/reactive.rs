@@ -0,0 +1,197 @@
+use leptos::prelude::{Get, GetUntracked, Memo, Owner, Set, Update, WithUntracked};
+use macros::Reactive;
+use traits::{AsReactive, DirtyTracking, ReactiveCapture};
+
+#[derive(Reactive, Debug, Clone, PartialEq)]
+struct Address {
+    street: String,
+    town: String,
+}
+
+#[derive(Reactive, Debug, Clone, PartialEq)]
+struct Contact {
+    name: String,
+    #[reactive(nested)]
+    address: Address,
+    #[reactive(nested)]
+    previous_addresses: Vec<Address>,
+    tags: Vec<String>,
+}
+
+#[derive(Reactive, Debug, Clone, PartialEq)]
+struct Pair(i32, String);
+
+#[derive(Reactive, Debug, Clone, PartialEq)]
+struct Unit;
+
+#[derive(Reactive, Debug, Clone, PartialEq)]
+struct Labeled<T> {
+    label: String,
+    value: T,
+}
+
+#[derive(Reactive, Debug, Clone, PartialEq)]
+struct Wrapper<T>(#[reactive(nested)] T);
+
+fn address(street: &str) -> Address {
+    Address {
+        street: street.to_string(),
+        town: "Southbury".to_string(),
+    }
+}
+
+#[test]
+fn nested_fields() {
+    let contact = Contact {
+        name: "Jane".to_string(),
+        address: address("234 Judd Rd"),
+        previous_addresses: vec![address("1 Main St"), address("2 Main St")],
+        tags: vec!["alumni".to_string()],
+    };
+
+    let reactive = contact.clone().as_reactive();
+    assert_eq!(reactive.address.street.get_untracked(), "234 Judd Rd");
+    assert_eq!(reactive.previous_addresses.with_untracked(Vec::len), 2);
+    assert_eq!(reactive.capture(), contact);
+
+    reactive.address.town.set("Middlebury".to_string());
+    reactive
+        .previous_addresses
+        .with_untracked(|addresses| addresses[1].street.set("3 Main St".to_string()));
+    reactive
+        .previous_addresses
+        .update(|addresses| addresses.push(address("4 Main St").as_reactive()));
+
+    let captured = reactive.capture();
+    assert_eq!(captured.address.town, "Middlebury");
+    assert_eq!(
+        captured
+            .previous_addresses
+            .iter()
+            .map(|address| address.street.as_str())
+            .collect::<Vec<_>>(),
+        vec!["1 Main St", "3 Main St", "4 Main St"]
+    );
+}
+
+#[test]
+fn tuple_and_unit_structs() {
+    let reactive = Pair(1, "one".to_string()).as_reactive();
+    reactive.0.set(2);
+    assert_eq!(reactive.capture(), Pair(2, "one".to_string()));
+
+    assert_eq!(Unit.as_reactive().capture(), Unit);
+}
+
+#[test]
+fn generic_structs() {
+    let reactive = Labeled {
+        label: "GPA".to_string(),
+        value: 3.8,
+    }
+    .as_reactive();
+    reactive.value.set(4.0);
+    assert_eq!(reactive.capture().value, 4.0);
+
+    let reactive = Wrapper(address("234 Judd Rd")).as_reactive();
+    reactive.0.street.set("1 Main St".to_string());
+    assert_eq!(reactive.capture(), Wrapper(address("1 Main St")));
+}
+
+fn create_contact() -> Contact {
+    Contact {
+        name: "Jane".to_string(),
+        address: address("234 Judd Rd"),
+        previous_addresses: vec![address("1 Main St")],
+        tags: vec![],
+    }
+}
+
+#[test]
+fn changed_fields() {
+    let reactive = create_contact().as_reactive();
+    assert!(!reactive.is_dirty());
+
+    reactive.name.set("John".to_string());
+    reactive.address.town.set("Middlebury".to_string());
+    assert_eq!(reactive.changed_fields(), vec!["name", "address"]);
+    assert_eq!(reactive.address.changed_fields(), vec!["town"]);
+
+    // Changing a value back means it's no longer dirty.
+    reactive.name.set("Jane".to_string());
+    assert_eq!(reactive.changed_fields(), vec!["address"]);
+
+    // Lists are dirty when an item changes, or when items are added or removed.
+    reactive.address.town.set("Southbury".to_string());
+    reactive
+        .previous_addresses
+        .with_untracked(|addresses| addresses[0].town.set("Oxford".to_string()));
+    assert_eq!(reactive.changed_fields(), vec!["previous_addresses"]);
+    reactive
+        .previous_addresses
+        .with_untracked(|addresses| addresses[0].town.set("Southbury".to_string()));
+    reactive
+        .previous_addresses
+        .update(|addresses| addresses.push(address("2 Main St").as_reactive()));
+    assert_eq!(reactive.changed_fields(), vec!["previous_addresses"]);
+
+    let reactive = Pair(1, "one".to_string()).as_reactive();
+    reactive.1.set("two".to_string());
+    assert_eq!(reactive.changed_fields(), vec!["1"]);
+}
+
+#[test]
+fn capture_patch() {
+    let reactive = create_contact().as_reactive();
+    assert!(reactive.capture_patch().name.is_none());
+
+    reactive.tags.set(vec!["alumni".to_string()]);
+    reactive.address.street.set("1 Main St".to_string());
+
+    let patch = reactive.capture_patch();
+    assert_eq!(patch.name, None);
+    assert_eq!(patch.tags, Some(vec!["alumni".to_string()]));
+    assert_eq!(
+        patch.address.map(|address| address.street),
+        Some("1 Main St".to_string())
+    );
+    assert_eq!(patch.previous_addresses, None);
+}
+
+#[test]
+fn reset_and_mark_saved() {
+    let reactive = create_contact().as_reactive();
+
+    reactive.name.set("John".to_string());
+    reactive.address.town.set("Middlebury".to_string());
+    reactive
+        .previous_addresses
+        .update(|addresses| addresses.clear());
+    reactive.reset();
+    assert!(!reactive.is_dirty());
+    assert_eq!(reactive.capture(), create_contact());
+
+    reactive.name.set("John".to_string());
+    reactive.address.town.set("Middlebury".to_string());
+    reactive.mark_saved();
+    assert!(!reactive.is_dirty());
+    assert!(!reactive.address.is_dirty());
+
+    reactive.name.set("Jane".to_string());
+    reactive.reset();
+    assert_eq!(reactive.name.get_untracked(), "John");
+    assert_eq!(reactive.address.town.get_untracked(), "Middlebury");
+}
+
+#[test]
+fn changes_are_tracked() {
+    let owner = Owner::new();
+    owner.set();
+
+    let reactive = create_contact().as_reactive();
+    let dirty = Memo::new(move |_| reactive.is_dirty());
+    assert!(!dirty.get_untracked());
+
+    reactive.address.town.set("Middlebury".to_string());
+    assert!(dirty.get_untracked());
+}
@@ -0,0 +1,9 @@
+use macros::Reactive;
+
+#[derive(Reactive)]
+enum Status {
+    Open,
+    Closed,
+}
+
+fn main() {}
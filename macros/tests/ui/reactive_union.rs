@@ -0,0 +1,9 @@
+use macros::Reactive;
+
+#[derive(Reactive)]
+union Bits {
+    int: u32,
+    float: f32,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use macros::Reactive;
+
+#[derive(Reactive)]
+struct User {
+    #[reactive(flatten)]
+    name: String,
+}
+
+fn main() {}
@@ -1,6 +1,7 @@
-use crate::components::ToastList;
+use crate::common::DatePurpose;
+use crate::components::{FormWindowGate, ToastList};
 use crate::components::login::ProviderLoginContext;
-use crate::pages::{AdminApplicantsPageFallback, AdminApplicantsPageShell, AdminApplicantsStudentList, AdminHomePage, AdminProviderPage, AdminScholarshipPage, AdminShell, AdminUtilsPage, ApplicantsPageFallback, ApplicantsStudentList, AuthCallbackPage, LoanerBorrowForm, LoanerFallback, LoanerReturnForm, LoanerShell, ProviderContactPage, ProviderPortal, ScholarshipInfoPage};
+use crate::pages::{AdminApplicantsPageFallback, AdminApplicantsPageShell, AdminApplicantsStudentList, AdminDatesPage, AdminFormsPage, AdminHomePage, AdminProgressPage, AdminProviderPage, AdminScholarshipPage, AdminShell, AdminUtilsPage, ApplicantsPageFallback, ApplicantsStudentList, AuthCallbackPage, LoanerBorrowForm, LoanerFallback, LoanerReturnForm, LoanerShell, ProviderContactPage, ProviderPortal, ScholarshipInfoPage};
 use crate::pages::{ApplicantsPageShell, student};
 use leptos::prelude::*;
 use leptos_meta::{MetaTags, Stylesheet, Title, provide_meta_context};
@@ -82,14 +83,31 @@ fn ProviderRoutes() -> impl MatchNestedRoutes + Clone {
     view! {
         <ParentRoute path=path!("/providers") view=ProviderLoginContext>
             <Route path=path!("") view=ProviderPortal />
-            <ParentRoute path=path!("/applicants") view=ApplicantsPageShell>
+            <ParentRoute
+                path=path!("/applicants")
+                view=|| {
+                    view! {
+                        <FormWindowGate purpose=DatePurpose::DecisionWindow with_banner=true>
+                            <ApplicantsPageShell />
+                        </FormWindowGate>
+                    }
+                }
+            >
                 <Route path=path!("") view=ApplicantsPageFallback />
                 <Route path=path!("/:scholarship_id") view=ApplicantsStudentList />
             </ParentRoute>
             <Route path=path!("/callback") view=AuthCallbackPage />
             <Route path=path!("/profile") view=ProviderContactPage />
-            // <Route path=path!("/scholarships") view=FormClosedPage />
-            <ParentRoute path=path!("/scholarships") view=ScholarshipInfoPage>
+            <ParentRoute
+                path=path!("/scholarships")
+                view=|| {
+                    view! {
+                        <FormWindowGate purpose=DatePurpose::ProviderForms with_banner=true>
+                            <ScholarshipInfoPage />
+                        </FormWindowGate>
+                    }
+                }
+            >
                 <Route path=path!(":id") view=ScholarshipInfoPage />
                 <Route path=path!("") view=ScholarshipInfoPage />
             </ParentRoute>
@@ -120,7 +138,10 @@ fn AdminRoutes() -> impl MatchNestedRoutes + Clone {
             <Route path=path!("home") view=AdminHomePage />
             <Route path=path!("providers") view=AdminProviderPage />
             <Route path=path!("scholarships") view=AdminScholarshipPage />
+            <Route path=path!("forms") view=AdminFormsPage />
             <Route path=path!("utilities") view=AdminUtilsPage />
+            <Route path=path!("progress") view=AdminProgressPage />
+            <Route path=path!("dates") view=AdminDatesPage />
             <ParentRoute path=path!("applicants") view=AdminApplicantsPageShell>
                 <Route path=path!("") view=AdminApplicantsPageFallback />
                 <Route path=path!("/:scholarship_id") view=AdminApplicantsStudentList />
@@ -137,17 +158,14 @@ fn StudentRoutes() -> impl MatchNestedRoutes + Clone {
         <ParentRoute path=path!("students") view=student::StudentShell>
             <Route path=path!("callback") view=AuthCallbackPage />
             <Route path=path!("home") view=student::StudentHomePage />
-            <Route path=path!("demographics") view=student::ClosedForm />
-            <Route path=path!("eligibility") view=student::ClosedForm />
-            <Route path=path!("additional") view=student::ClosedForm />
-            <Route path=path!("additional/academics") view=student::ClosedForm />
-            <Route path=path!("additional/athletics") view=student::ClosedForm />
-            <Route path=path!("additional/extracurriculars") view=student::ClosedForm />
-            <Route path=path!("additional/work-experience") view=student::ClosedForm />
-            <Route path=path!("additional/university") view=student::ClosedForm />
-            <Route path=path!("additional/family-info") view=student::ClosedForm />
-            <Route path=path!("additional/financials") view=student::ClosedForm />
-            <Route path=path!("additional/specifics") view=student::ClosedForm />
+            <Route path=path!("eligibility") view=student::StudentEligibilityPage />
+            <Route path=path!("additional") view=student::AdditionalPage />
+            <Route path=path!("additional/financials") view=student::StudentFinancialPage />
+            // Every other page is a form, found using the path in its definition. The form pages
+            // check whether the student forms are open themselves, since a student may have an
+            // extension.
+            <Route path=path!(":form") view=student::StudentFormRoute />
+            <Route path=path!("additional/:form") view=student::StudentFormRoute />
         </ParentRoute>
     }
     .into_inner()
@@ -2,11 +2,11 @@ use super::{
     Comparison, MapListComparison, NestedListComparison, NumberComparison, NumberListComparison,
     TextComparison, TextListComparison,
 };
-use crate::common::ValueType;
+use crate::common::{ValueType, parent_path, rename_path};
 use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct ComparisonData {
     /// Each comparison will have a unique ID.
     pub id: String,
@@ -43,6 +43,12 @@ impl ComparisonData {
         }
     }
 
+    /// Renames a stored field that this comparison reads, such as after a form question is
+    /// renamed. Returns whether anything changed.
+    pub fn rename_field(&mut self, from: &str, to: &str) -> bool {
+        self.comparison.rename_field(&mut self.member, from, to)
+    }
+
     pub fn compare(&self, student_data: &HashMap<String, ValueType>) -> Result<bool, String> {
         student_data.get(&self.member)
             .ok_or_else(||
@@ -53,7 +59,7 @@ impl ComparisonData {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub enum ComparisonType {
     Number(NumberComparison),
     Text(TextComparison),
@@ -63,6 +69,35 @@ pub enum ComparisonType {
     NestedList(NestedListComparison),
 }
 
+impl ComparisonType {
+    /// Renames a stored field that a comparison of `member` reads. The field is either the member
+    /// itself, or the key that a `MapList` comparison reads from each entry, such as
+    /// `sports_participation.sport_name`. Returns whether anything changed.
+    pub fn rename_field(&mut self, member: &mut String, from: &str, to: &str) -> bool {
+        if let Some(renamed) = rename_path(member, from, to) {
+            *member = renamed;
+            return true;
+        }
+
+        let ComparisonType::MapList(
+            MapListComparison::FlattenToTextList(key, _)
+            | MapListComparison::FlattenToNumberList(key, _)
+            | MapListComparison::FlattenToNestedList(key, _),
+        ) = self
+        else {
+            return false;
+        };
+
+        match rename_path(&format!("{member}.{key}"), from, to) {
+            Some(renamed) if parent_path(&renamed) == member.as_str() => {
+                *key = renamed[member.len() + 1..].to_string();
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
 impl Comparison for ComparisonType {
     fn evaluate(&self, comp_value: &ValueType, target_value: &ValueType) -> Result<bool, String> {
         match self {
@@ -112,4 +147,32 @@ mod tests {
         result = text_comp_fail.compare(&student_data.data);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn renames_fields() {
+        let mut comparison = ComparisonData::new(
+            "sports_golf",
+            "sports_participation",
+            ComparisonType::MapList(MapListComparison::FlattenToTextList(
+                "sport_name".to_string(),
+                Box::new(TextListComparison::Contains),
+            )),
+            ValueType::String(Some("Golf".to_string())),
+            "Sports",
+            "Plays golf",
+        );
+
+        assert!(!comparison.rename_field("sport_name", "sport"));
+        assert!(comparison.rename_field("sports_participation.sport_name", "sports_participation.sport"));
+        assert_eq!(
+            comparison.comparison,
+            ComparisonType::MapList(MapListComparison::FlattenToTextList(
+                "sport".to_string(),
+                Box::new(TextListComparison::Contains),
+            ))
+        );
+
+        assert!(comparison.rename_field("sports_participation", "athletics"));
+        assert_eq!(comparison.member, "athletics");
+    }
 }
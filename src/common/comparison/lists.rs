@@ -2,7 +2,7 @@ use super::{Comparison, NumberComparison};
 use crate::common::ValueType;
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub enum NumberListComparison {
     /// Sums the values in the list and checks them using the given [`NumberComparison`].
     /// Requires the list to contain only numbers.
@@ -13,8 +13,27 @@ pub enum NumberListComparison {
     NotContains,
 }
 
+/// Converts string and number sets into lists, so that they can be compared the same way.
+/// Any other value is returned unchanged.
+fn set_as_list(value: &ValueType) -> ValueType {
+    match value {
+        ValueType::StringSet(set) => ValueType::List(set.as_ref().map(|set| {
+            set.iter()
+                .map(|item| ValueType::String(Some(item.clone())))
+                .collect()
+        })),
+        ValueType::NumberSet(set) => ValueType::List(set.as_ref().map(|set| {
+            set.iter()
+                .map(|item| ValueType::Number(Some(item.clone())))
+                .collect()
+        })),
+        other => other.clone(),
+    }
+}
+
 impl Comparison for NumberListComparison {
     fn evaluate(&self, comp_value: &ValueType, target_value: &ValueType) -> Result<bool, String> {
+        let comp_value = &set_as_list(comp_value);
         match self {
             NumberListComparison::Sum(num_comp) => {
                 // Requires the list to contain only numbers. Get this list or throw an error.
@@ -65,7 +84,7 @@ impl Comparison for NumberListComparison {
 ///
 /// For example, using `TextListComparison::Contains` checks if a specific value is contained
 /// in the list.
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub enum TextListComparison {
     /// Checks if the list contains the target value.
     Contains,
@@ -79,6 +98,7 @@ pub enum TextListComparison {
 
 impl Comparison for TextListComparison {
     fn evaluate(&self, comp_value: &ValueType, target_value: &ValueType) -> Result<bool, String> {
+        let comp_value = &set_as_list(comp_value);
         match self {
             TextListComparison::Contains => {
                 let list_opt = match comp_value {
@@ -156,7 +176,7 @@ impl Comparison for TextListComparison {
 ///
 /// This will flatten a nested list into a single list of `ValueType::String` enums and then
 /// will check if the list contains the indicated target value.
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub enum NestedListComparison {
     /// Flattens a nested list into a single text list, then performs the given comparison.
     FlattenToTextList(Box<TextListComparison>),
@@ -210,7 +230,7 @@ impl Comparison for NestedListComparison {
 /// ```
 /// MapListComparison::FlattenToTextList("name".to_owned(), Box::new(TextListComparison::Equal));
 /// ```
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub enum MapListComparison {
     /// Flattens the map list to a text list and then performs the given comparison.
     FlattenToTextList(String, Box<TextListComparison>),
@@ -295,6 +315,20 @@ mod tests {
         create_empty_lists, create_number_list, create_text_list,
     };
 
+    #[test]
+    fn sets_compare_as_lists() {
+        let text_set = ValueType::StringSet(Some(vec!["Soccer".to_string(), "Golf".to_string()]));
+        let number_set = ValueType::NumberSet(Some(vec!["10".to_string(), "15".to_string()]));
+
+        let contains = TextListComparison::Contains
+            .evaluate(&text_set, &ValueType::String(Some("Golf".to_string())));
+        assert_eq!(contains, Ok(true));
+
+        let sum = NumberListComparison::Sum(Box::new(NumberComparison::GreaterThanOrEqual))
+            .evaluate(&number_set, &ValueType::Number(Some("25".to_string())));
+        assert_eq!(sum, Ok(true));
+    }
+
     #[test]
     fn map_list_flatten_to_text_list() {
         let map_list = create_map_list_helper();
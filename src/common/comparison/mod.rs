@@ -1,16 +1,25 @@
 mod base;
 mod lists;
 mod number;
+mod rules;
 mod text;
 mod traits;
 
-pub use self::{base::*, lists::*, number::*, text::*, traits::*};
+pub use self::{base::*, lists::*, number::*, rules::*, text::*, traits::*};
 
 #[cfg(test)]
-mod test_utils {
+pub(crate) mod test_utils {
     use crate::common::{ExpandableInfo, ValueType};
     use std::collections::HashMap;
 
+    pub fn text(value: &str) -> ValueType {
+        ValueType::String(Some(value.to_string()))
+    }
+
+    pub fn number(value: &str) -> ValueType {
+        ValueType::Number(Some(value.to_string()))
+    }
+
     pub fn create_student_data() -> ExpandableInfo {
         let map = create_map_helper(
             vec![
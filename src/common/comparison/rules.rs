@@ -0,0 +1,1313 @@
+use super::{
+    Comparison, ComparisonData, ComparisonType, MapListComparison, NestedListComparison, NumberComparison,
+    NumberListComparison, TextComparison, TextListComparison,
+};
+use crate::common::ValueType;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::ops::Range;
+use std::str::FromStr;
+
+/// An error found while reading a rule. The `span` is a byte range into the original rule text,
+/// and points at the part of the rule that couldn't be understood.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleError {
+    /// A human-readable description of the problem.
+    pub message: String,
+    /// The byte range of the rule text that caused the error.
+    pub span: Range<usize>,
+}
+
+impl RuleError {
+    fn new(message: impl Into<String>, span: Range<usize>) -> Self {
+        Self {
+            message: message.into(),
+            span,
+        }
+    }
+
+    /// Renders the error beneath the rule text, with carets underlining the offending span.
+    ///
+    /// For example, the rule `gpa >= "high"` renders as:
+    /// ```text
+    /// gpa >= "high"
+    ///     ^^^^^^^^^
+    /// `>=` can only be used with numbers.
+    /// ```
+    pub fn render(&self, source: &str) -> String {
+        let start = self.span.start.min(source.len());
+        let end = self.span.end.clamp(start, source.len());
+        let padding = source[..start].chars().count();
+        let width = source[start..end].chars().count().max(1);
+
+        format!(
+            "{}\n{}{}\n{}",
+            source,
+            " ".repeat(padding),
+            "^".repeat(width),
+            self.message
+        )
+    }
+}
+
+impl Display for RuleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} (at {}..{})",
+            self.message, self.span.start, self.span.end
+        )
+    }
+}
+
+impl std::error::Error for RuleError {}
+
+/// A single condition read from a rule, such as `gpa >= 3.0`. This contains everything a
+/// [`ComparisonData`] needs except for its ID and category.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RuleCondition {
+    /// The member from the student's data to match against.
+    pub member: String,
+    /// The comparison to perform.
+    pub comparison: ComparisonType,
+    /// The value to match against.
+    pub target_value: ValueType,
+}
+
+impl RuleCondition {
+    pub fn new(
+        member: impl Into<String>,
+        comparison: ComparisonType,
+        target_value: ValueType,
+    ) -> Self {
+        Self {
+            member: member.into(),
+            comparison,
+            target_value,
+        }
+    }
+
+    /// Describes this condition in plain language, for display to students and providers.
+    /// For example, `gpa >= 3.0` is described as "gpa is at least 3.0".
+    pub fn describe(&self) -> String {
+        let label = humanize(&self.member);
+        let value = describe_value(&self.target_value);
+
+        match &self.comparison {
+            ComparisonType::Number(op) => format!("{label} {} {value}", number_phrase(op)),
+            ComparisonType::Text(text) => match text {
+                TextComparison::Matches => format!("{label} is {value}"),
+                TextComparison::NotMatches => format!("{label} is not {value}"),
+                TextComparison::Contains => format!("{label} contains {value}"),
+                TextComparison::NotContains => format!("{label} does not contain {value}"),
+            },
+            ComparisonType::TextList(check) => describe_text_list(&label, check, &value),
+            ComparisonType::NumberList(check) => describe_number_list(&label, check, &value),
+            ComparisonType::MapList(map_list) => match map_list {
+                MapListComparison::FlattenToTextList(key, check) => {
+                    let entry_label = format!("any {label} entry's {}", humanize(key));
+                    describe_text_list(&entry_label, check, &value)
+                }
+                MapListComparison::FlattenToNumberList(key, check) => match check.as_ref() {
+                    NumberListComparison::Sum(op) => format!(
+                        "the total {} across all {label} entries {} {value}",
+                        humanize(key),
+                        number_phrase(op)
+                    ),
+                    other => {
+                        let entry_label = format!("any {label} entry's {}", humanize(key));
+                        describe_number_list(&entry_label, other, &value)
+                    }
+                },
+                MapListComparison::FlattenToNestedList(key, nested) => {
+                    let NestedListComparison::FlattenToTextList(check) = nested.as_ref();
+                    let entry_label = format!("any {label} entry's {}", humanize(key));
+                    describe_text_list(&entry_label, check, &value)
+                }
+            },
+            ComparisonType::NestedList(NestedListComparison::FlattenToTextList(check)) => {
+                describe_text_list(&label, check, &value)
+            }
+        }
+    }
+
+    /// Checks this condition against a student's data. Fails if the student doesn't have the
+    /// member, or if the comparison can't be performed.
+    pub fn evaluate(&self, student_data: &HashMap<String, ValueType>) -> Result<bool, String> {
+        student_data
+            .get(&self.member)
+            .ok_or_else(|| format!("Couldn't find member {:?}", self.member))
+            .and_then(|value| self.comparison.evaluate(value, &self.target_value))
+    }
+
+    /// Converts this condition into a [`ComparisonData`] under the given category. The ID is
+    /// generated from the rule text, and the display text is the plain-language description.
+    pub fn into_comparison(self, category: impl Into<String>) -> ComparisonData {
+        let id = condition_id(&self.to_string());
+        let display_text = self.describe();
+
+        ComparisonData::new(
+            id,
+            self.member,
+            self.comparison,
+            self.target_value,
+            category,
+            display_text,
+        )
+    }
+}
+
+impl From<&ComparisonData> for RuleCondition {
+    fn from(data: &ComparisonData) -> Self {
+        Self::new(
+            data.member.clone(),
+            data.comparison.clone(),
+            data.target_value.clone(),
+        )
+    }
+}
+
+impl Display for RuleCondition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let member = &self.member;
+        let value = value_text(&self.target_value);
+
+        let text = match &self.comparison {
+            ComparisonType::Number(op) => format!("{member} {} {value}", op_symbol(op)),
+            ComparisonType::Text(text) => match text {
+                TextComparison::Matches => format!("{member} == {value}"),
+                TextComparison::NotMatches => format!("{member} != {value}"),
+                TextComparison::Contains => format!("{member} contains {value}"),
+                TextComparison::NotContains => format!("{member} not contains {value}"),
+            },
+            ComparisonType::TextList(check) => text_list_text(member, check, &value),
+            ComparisonType::NumberList(check) => number_list_text(member, check, &value),
+            ComparisonType::MapList(MapListComparison::FlattenToTextList(key, check)) => {
+                text_list_text(&format!("{member}.{key}"), check, &value)
+            }
+            ComparisonType::MapList(MapListComparison::FlattenToNumberList(key, check)) => {
+                number_list_text(&format!("{member}.{key}"), check, &value)
+            }
+            ComparisonType::MapList(MapListComparison::FlattenToNestedList(key, nested)) => {
+                let NestedListComparison::FlattenToTextList(check) = nested.as_ref();
+                text_list_text(&format!("{member}.{key}[*]"), check, &value)
+            }
+            ComparisonType::NestedList(NestedListComparison::FlattenToTextList(check)) => {
+                text_list_text(&format!("{member}[*]"), check, &value)
+            }
+        };
+
+        write!(f, "{}", text)
+    }
+}
+
+/// # Rule
+///
+/// A requirement written in a small text language, so that requirements can be created without
+/// writing Rust code. For example:
+/// ```text
+/// gpa >= 3.0 and town in ["Middlebury", "Southbury"]
+/// ```
+///
+/// A rule is a list of groups joined by `and`. Each group is one or more conditions joined by
+/// `or`, and passes when any of its conditions pass. This is the same shape as the `requirements`
+/// map on a scholarship, where every category must pass and any requirement within a category
+/// may pass. When `or` and `and` are mixed, the `or` conditions must be wrapped in parentheses.
+///
+/// The available conditions are:
+///  - `member >= 3.0`, using any of `>=`, `<=`, `>`, `<`, `==` and `!=` with a number.
+///  - `member == "text"` and `member != "text"`.
+///  - `member contains "text"` and `member not contains "text"`.
+///  - `member in ["a", "b"]`, which passes when the member matches any of the values.
+///  - `member includes "a"` and `member not includes "a"` for lists.
+///  - `member is empty` and `member is not empty` for lists.
+///  - `sum(member) >= 20` for lists of numbers.
+///
+/// Lists of maps are checked using a key: `sports_participation.sport_name includes "Golf"`
+/// checks the `sport_name` of every entry. Nested lists are flattened with `[*]`, so
+/// `extracurricular.grades[*] includes "9th"` checks every grade of every entry.
+///
+/// Rules can be printed back into text using `to_string`, and into plain language using
+/// [`Rule::describe`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Rule {
+    /// The groups in this rule. Every group must pass, and a group passes when any of its
+    /// conditions pass.
+    pub groups: Vec<Vec<RuleCondition>>,
+}
+
+impl Rule {
+    /// Parses a rule from its text.
+    pub fn parse(source: &str) -> Result<Self, RuleError> {
+        Parser::new(tokenize(source)?).parse_rule()
+    }
+
+    /// Describes this rule in plain language, for display to students and providers.
+    pub fn describe(&self) -> String {
+        let multiple = self.groups.len() > 1;
+
+        self.groups
+            .iter()
+            .map(|group| {
+                if let Some((member, values)) = as_in_list(group) {
+                    let values = values
+                        .iter()
+                        .map(|v| describe_value(v))
+                        .collect::<Vec<String>>()
+                        .join(" or ");
+                    return format!("{} is {values}", humanize(member));
+                }
+
+                let joined = group
+                    .iter()
+                    .map(RuleCondition::describe)
+                    .collect::<Vec<String>>()
+                    .join(" or ");
+
+                if multiple && group.len() > 1 {
+                    format!("either {joined}")
+                } else {
+                    joined
+                }
+            })
+            .collect::<Vec<String>>()
+            .join(", and ")
+    }
+
+    /// Checks this rule against a student's data. Every group must have at least one passing
+    /// condition. Conditions that can't be evaluated are treated as failing.
+    pub fn evaluate(&self, student_data: &HashMap<String, ValueType>) -> bool {
+        self.groups.iter().all(|group| {
+            group
+                .iter()
+                .any(|condition| condition.evaluate(student_data).unwrap_or(false))
+        })
+    }
+
+    /// Renames a stored field in every condition of this rule. Returns whether anything changed.
+    pub fn rename_field(&mut self, from: &str, to: &str) -> bool {
+        let mut renamed = false;
+        for condition in self.groups.iter_mut().flatten() {
+            renamed |= condition.comparison.rename_field(&mut condition.member, from, to);
+        }
+        renamed
+    }
+
+    /// Converts every condition in this rule into a [`ComparisonData`] under the given category.
+    ///
+    /// Requirements within a category are combined with `or`, so a rule with more than one group
+    /// puts each group in its own numbered category, such as `Residency 1` and `Residency 2`.
+    /// Scholarships must pass every category, which keeps the `and` between the groups.
+    pub fn into_comparisons(self, category: impl Into<String>) -> Vec<ComparisonData> {
+        let category = category.into();
+        let multiple = self.groups.len() > 1;
+
+        self.groups
+            .into_iter()
+            .enumerate()
+            .flat_map(|(index, group)| {
+                let category = if multiple {
+                    format!("{category} {}", index + 1)
+                } else {
+                    category.clone()
+                };
+                group
+                    .into_iter()
+                    .map(move |condition| condition.into_comparison(category.clone()))
+            })
+            .collect()
+    }
+}
+
+impl FromStr for Rule {
+    type Err = RuleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Rule::parse(s)
+    }
+}
+
+impl Display for Rule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let multiple = self.groups.len() > 1;
+
+        let text = self
+            .groups
+            .iter()
+            .map(|group| {
+                if let Some((member, values)) = as_in_list(group) {
+                    let values = values
+                        .iter()
+                        .map(|v| value_text(v))
+                        .collect::<Vec<String>>()
+                        .join(", ");
+                    return format!("{member} in [{values}]");
+                }
+
+                let joined = group
+                    .iter()
+                    .map(RuleCondition::to_string)
+                    .collect::<Vec<String>>()
+                    .join(" or ");
+
+                if multiple && group.len() > 1 {
+                    format!("({joined})")
+                } else {
+                    joined
+                }
+            })
+            .collect::<Vec<String>>()
+            .join(" and ");
+
+        write!(f, "{}", text)
+    }
+}
+
+impl ComparisonData {
+    /// Prints this comparison as a condition in the rule language. Refer to [`Rule`] for the
+    /// syntax.
+    pub fn to_rule_text(&self) -> String {
+        RuleCondition::from(self).to_string()
+    }
+}
+
+// region Printing Helpers
+
+fn op_symbol(op: &NumberComparison) -> &'static str {
+    match op {
+        NumberComparison::GreaterThan => ">",
+        NumberComparison::LessThan => "<",
+        NumberComparison::Equal => "==",
+        NumberComparison::NotEqual => "!=",
+        NumberComparison::GreaterThanOrEqual => ">=",
+        NumberComparison::LessThanOrEqual => "<=",
+    }
+}
+
+fn number_phrase(op: &NumberComparison) -> &'static str {
+    match op {
+        NumberComparison::GreaterThan => "is greater than",
+        NumberComparison::LessThan => "is less than",
+        NumberComparison::Equal => "is",
+        NumberComparison::NotEqual => "is not",
+        NumberComparison::GreaterThanOrEqual => "is at least",
+        NumberComparison::LessThanOrEqual => "is at most",
+    }
+}
+
+/// Prints a value as it would be written in a rule. Strings are quoted and escaped.
+fn value_text(value: &ValueType) -> String {
+    match value {
+        ValueType::Number(Some(n)) => n.clone(),
+        ValueType::String(Some(s)) => {
+            format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+        }
+        _ => "\"\"".to_string(),
+    }
+}
+
+fn describe_value(value: &ValueType) -> String {
+    match value {
+        ValueType::Number(Some(n)) => n.clone(),
+        ValueType::String(Some(s)) => s.clone(),
+        _ => String::new(),
+    }
+}
+
+/// Turns a data member like `weighted_gpa` into `weighted gpa`.
+fn humanize(member: &str) -> String {
+    member.replace(['_', '.'], " ")
+}
+
+fn text_list_text(path: &str, check: &TextListComparison, value: &str) -> String {
+    match check {
+        TextListComparison::Contains => format!("{path} includes {value}"),
+        TextListComparison::NotContains => format!("{path} not includes {value}"),
+        TextListComparison::IsEmpty => format!("{path} is empty"),
+        TextListComparison::IsNotEmpty => format!("{path} is not empty"),
+    }
+}
+
+fn number_list_text(path: &str, check: &NumberListComparison, value: &str) -> String {
+    match check {
+        NumberListComparison::Sum(op) => format!("sum({path}) {} {value}", op_symbol(op)),
+        NumberListComparison::Contains => format!("{path} includes {value}"),
+        NumberListComparison::NotContains => format!("{path} not includes {value}"),
+    }
+}
+
+fn describe_text_list(label: &str, check: &TextListComparison, value: &str) -> String {
+    match check {
+        TextListComparison::Contains => format!("{label} includes {value}"),
+        TextListComparison::NotContains => format!("{label} does not include {value}"),
+        TextListComparison::IsEmpty => format!("{label} has no entries"),
+        TextListComparison::IsNotEmpty => format!("{label} has at least one entry"),
+    }
+}
+
+fn describe_number_list(label: &str, check: &NumberListComparison, value: &str) -> String {
+    match check {
+        NumberListComparison::Sum(op) => format!("the total {label} {} {value}", number_phrase(op)),
+        NumberListComparison::Contains => format!("{label} includes {value}"),
+        NumberListComparison::NotContains => format!("{label} does not include {value}"),
+    }
+}
+
+/// Checks whether a group can be written as `member in [...]`. This is the case when there is
+/// more than one condition, and every condition checks the same member for an exact match.
+fn as_in_list(group: &[RuleCondition]) -> Option<(&str, Vec<&ValueType>)> {
+    let first = group.first()?;
+    if group.len() < 2 {
+        return None;
+    }
+
+    group
+        .iter()
+        .map(|condition| {
+            let exact = matches!(
+                (&condition.comparison, &condition.target_value),
+                (ComparisonType::Text(TextComparison::Matches), ValueType::String(Some(_)))
+                    | (ComparisonType::Number(NumberComparison::Equal), ValueType::Number(Some(_)))
+            );
+
+            (exact && condition.member == first.member).then_some(&condition.target_value)
+        })
+        .collect::<Option<Vec<&ValueType>>>()
+        .map(|values| (first.member.as_str(), values))
+}
+
+/// Creates a comparison ID from a condition's rule text, like `gpa_at_least_3_0`.
+fn condition_id(rule_text: &str) -> String {
+    let worded = rule_text
+        .replace(">=", " at least ")
+        .replace("<=", " at most ")
+        .replace("!=", " is not ")
+        .replace("==", " is ")
+        .replace('>', " over ")
+        .replace('<', " under ");
+
+    worded
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<&str>>()
+        .join("_")
+}
+
+// endregion
+// region Tokenizer
+
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    Word(String),
+    Number(String),
+    Text(String),
+    Op(NumberComparison),
+    LeftBracket,
+    RightBracket,
+    LeftParen,
+    RightParen,
+    Comma,
+    Dot,
+    Star,
+    End,
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: TokenKind,
+    span: Range<usize>,
+}
+
+impl Token {
+    /// Checks if this token is the given keyword. Keywords are case-insensitive.
+    fn is_word(&self, word: &str) -> bool {
+        matches!(&self.kind, TokenKind::Word(w) if w.eq_ignore_ascii_case(word))
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, RuleError> {
+    let mut tokens = Vec::new();
+    let mut chars = source.char_indices().peekable();
+
+    while let Some(&(start, c)) = chars.peek() {
+        let single = match c {
+            '[' => Some(TokenKind::LeftBracket),
+            ']' => Some(TokenKind::RightBracket),
+            '(' => Some(TokenKind::LeftParen),
+            ')' => Some(TokenKind::RightParen),
+            ',' => Some(TokenKind::Comma),
+            '.' => Some(TokenKind::Dot),
+            '*' => Some(TokenKind::Star),
+            _ => None,
+        };
+
+        if let Some(kind) = single {
+            chars.next();
+            tokens.push(Token {
+                kind,
+                span: start..start + 1,
+            });
+            continue;
+        }
+
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '>' | '<' | '=' | '!' => {
+                chars.next();
+                let has_equals = matches!(chars.peek(), Some((_, '=')));
+                if has_equals {
+                    chars.next();
+                }
+                let span = start..start + if has_equals { 2 } else { 1 };
+
+                let op = match (c, has_equals) {
+                    ('>', true) => NumberComparison::GreaterThanOrEqual,
+                    ('>', false) => NumberComparison::GreaterThan,
+                    ('<', true) => NumberComparison::LessThanOrEqual,
+                    ('<', false) => NumberComparison::LessThan,
+                    ('=', true) => NumberComparison::Equal,
+                    ('!', true) => NumberComparison::NotEqual,
+                    _ => {
+                        return Err(RuleError::new(
+                            format!(
+                                "Unknown operator `{c}`. Expected one of >=, <=, >, <, == or !=."
+                            ),
+                            span,
+                        ));
+                    }
+                };
+
+                tokens.push(Token {
+                    kind: TokenKind::Op(op),
+                    span,
+                });
+            }
+            '"' => {
+                chars.next();
+                let mut text = String::new();
+                let mut end = None;
+
+                while let Some((index, c)) = chars.next() {
+                    match c {
+                        '"' => {
+                            end = Some(index + 1);
+                            break;
+                        }
+                        '\\' => match chars.next() {
+                            Some((_, escaped)) => text.push(escaped),
+                            None => break,
+                        },
+                        c => text.push(c),
+                    }
+                }
+
+                let Some(end) = end else {
+                    return Err(RuleError::new(
+                        "This text is missing its closing quote.",
+                        start..source.len(),
+                    ));
+                };
+
+                tokens.push(Token {
+                    kind: TokenKind::Text(text),
+                    span: start..end,
+                });
+            }
+            c if c.is_ascii_digit() || c == '-' => {
+                let mut end = start;
+                while let Some(&(index, c)) = chars.peek() {
+                    if c.is_ascii_digit() || c == '.' || (c == '-' && index == start) {
+                        end = index + c.len_utf8();
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+
+                let number = &source[start..end];
+                if number.parse::<f64>().is_err() {
+                    return Err(RuleError::new(
+                        format!("`{number}` is not a valid number."),
+                        start..end,
+                    ));
+                }
+
+                tokens.push(Token {
+                    kind: TokenKind::Number(number.to_string()),
+                    span: start..end,
+                });
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut end = start;
+                while let Some(&(index, c)) = chars.peek() {
+                    if c.is_alphanumeric() || c == '_' {
+                        end = index + c.len_utf8();
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+
+                tokens.push(Token {
+                    kind: TokenKind::Word(source[start..end].to_string()),
+                    span: start..end,
+                });
+            }
+            c => {
+                return Err(RuleError::new(
+                    format!("Unexpected character `{c}`."),
+                    start..start + c.len_utf8(),
+                ));
+            }
+        }
+    }
+
+    tokens.push(Token {
+        kind: TokenKind::End,
+        span: source.len()..source.len(),
+    });
+
+    Ok(tokens)
+}
+
+// endregion
+// region Parser
+
+/// A reference to a student's data, like `town`, `sports_participation.sport_name`, or
+/// `extracurricular.grades[*]`.
+struct MemberPath {
+    member: String,
+    key: Option<String>,
+    nested: bool,
+    span: Range<usize>,
+}
+
+impl MemberPath {
+    fn is_plain(&self) -> bool {
+        self.key.is_none() && !self.nested
+    }
+}
+
+/// The check performed on a list, before it's wrapped according to its [`MemberPath`].
+enum ListCheck {
+    Text(TextListComparison),
+    Number(NumberListComparison),
+}
+
+/// A group of conditions, along with whether the group was wrapped in parentheses and how many
+/// `or` terms it contained.
+struct ParsedGroup {
+    conditions: Vec<RuleCondition>,
+    parenthesized: bool,
+    terms: usize,
+    span: Range<usize>,
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn new(tokens: Vec<Token>) -> Self {
+        Self {
+            tokens,
+            position: 0,
+        }
+    }
+
+    fn peek(&self) -> &Token {
+        &self.tokens[self.position]
+    }
+
+    fn peek_ahead(&self, offset: usize) -> &Token {
+        let index = (self.position + offset).min(self.tokens.len() - 1);
+        &self.tokens[index]
+    }
+
+    fn next(&mut self) -> Token {
+        let token = self.tokens[self.position].clone();
+        if token.kind != TokenKind::End {
+            self.position += 1;
+        }
+        token
+    }
+
+    fn previous_end(&self) -> usize {
+        self.position
+            .checked_sub(1)
+            .map(|index| self.tokens[index].span.end)
+            .unwrap_or(0)
+    }
+
+    fn expect(&mut self, kind: TokenKind, expected: &str) -> Result<Token, RuleError> {
+        let token = self.next();
+        if token.kind == kind {
+            Ok(token)
+        } else {
+            Err(RuleError::new(format!("Expected {expected}."), token.span))
+        }
+    }
+
+    fn expect_word(&mut self, word: &str) -> Result<Token, RuleError> {
+        let token = self.next();
+        if token.is_word(word) {
+            Ok(token)
+        } else {
+            Err(RuleError::new(format!("Expected `{word}`."), token.span))
+        }
+    }
+
+    fn parse_rule(mut self) -> Result<Rule, RuleError> {
+        let mut groups = Vec::new();
+
+        loop {
+            groups.push(self.parse_group()?);
+
+            if self.peek().is_word("and") {
+                self.next();
+            } else {
+                break;
+            }
+        }
+
+        let token = self.peek();
+        if token.kind != TokenKind::End {
+            return Err(RuleError::new(
+                "Expected `and`, `or`, or the end of the rule.",
+                token.span.clone(),
+            ));
+        }
+
+        if groups.len() > 1
+            && let Some(group) = groups.iter().find(|g| g.terms > 1 && !g.parenthesized)
+        {
+            return Err(RuleError::new(
+                "Wrap `or` conditions in parentheses when they are combined with `and`.",
+                group.span.clone(),
+            ));
+        }
+
+        Ok(Rule {
+            groups: groups.into_iter().map(|group| group.conditions).collect(),
+        })
+    }
+
+    fn parse_group(&mut self) -> Result<ParsedGroup, RuleError> {
+        let start = self.peek().span.start;
+        let parenthesized = self.peek().kind == TokenKind::LeftParen;
+
+        if parenthesized {
+            self.next();
+        }
+
+        let mut conditions = Vec::new();
+        let mut terms = 0;
+
+        loop {
+            conditions.extend(self.parse_term()?);
+            terms += 1;
+
+            if self.peek().is_word("or") {
+                self.next();
+            } else {
+                break;
+            }
+        }
+
+        if parenthesized {
+            self.expect(TokenKind::RightParen, "`)` to close the group")?;
+        }
+
+        Ok(ParsedGroup {
+            conditions,
+            parenthesized,
+            terms,
+            span: start..self.previous_end(),
+        })
+    }
+
+    fn parse_path(&mut self) -> Result<MemberPath, RuleError> {
+        let token = self.next();
+        let TokenKind::Word(member) = token.kind else {
+            return Err(RuleError::new(
+                "Expected the name of a student field.",
+                token.span,
+            ));
+        };
+
+        let mut key = None;
+        let mut nested = false;
+
+        if self.peek().kind == TokenKind::Dot {
+            self.next();
+            let token = self.next();
+            let TokenKind::Word(name) = token.kind else {
+                return Err(RuleError::new(
+                    "Expected a field name after `.`.",
+                    token.span,
+                ));
+            };
+            key = Some(name);
+        }
+
+        if self.peek().kind == TokenKind::LeftBracket {
+            self.next();
+            self.expect(TokenKind::Star, "`*` to flatten a nested list")?;
+            self.expect(TokenKind::RightBracket, "`]`")?;
+            nested = true;
+        }
+
+        Ok(MemberPath {
+            member,
+            key,
+            nested,
+            span: token.span.start..self.previous_end(),
+        })
+    }
+
+    /// Reads a single number or quoted text value.
+    fn parse_value(&mut self) -> Result<(ValueType, Range<usize>), RuleError> {
+        let token = self.next();
+        match token.kind {
+            TokenKind::Number(n) => Ok((ValueType::Number(Some(n)), token.span)),
+            TokenKind::Text(s) => Ok((ValueType::String(Some(s)), token.span)),
+            _ => Err(RuleError::new(
+                "Expected a number or quoted text.",
+                token.span,
+            )),
+        }
+    }
+
+    fn parse_term(&mut self) -> Result<Vec<RuleCondition>, RuleError> {
+        if self.peek().is_word("sum") && self.peek_ahead(1).kind == TokenKind::LeftParen {
+            return self.parse_sum().map(|condition| vec![condition]);
+        }
+
+        let path = self.parse_path()?;
+        let token = self.next();
+
+        match token.kind {
+            TokenKind::Op(op) => {
+                require_plain(&path, "Comparisons like `>=` and `==`")?;
+                let (value, value_span) = self.parse_value()?;
+
+                let comparison = match (&value, op) {
+                    (ValueType::Number(_), op) => ComparisonType::Number(op),
+                    (ValueType::String(_), NumberComparison::Equal) => {
+                        ComparisonType::Text(TextComparison::Matches)
+                    }
+                    (ValueType::String(_), NumberComparison::NotEqual) => {
+                        ComparisonType::Text(TextComparison::NotMatches)
+                    }
+                    (_, op) => {
+                        return Err(RuleError::new(
+                            format!("`{}` can only be used with numbers.", op_symbol(&op)),
+                            token.span.start..value_span.end,
+                        ));
+                    }
+                };
+
+                Ok(vec![RuleCondition::new(path.member, comparison, value)])
+            }
+            TokenKind::Word(ref word) if word.eq_ignore_ascii_case("in") => {
+                require_plain(&path, "`in`")?;
+                self.expect(TokenKind::LeftBracket, "`[` to start the list of values")?;
+
+                let mut conditions = Vec::new();
+                loop {
+                    let (value, _) = self.parse_value()?;
+                    let comparison = match value {
+                        ValueType::Number(_) => ComparisonType::Number(NumberComparison::Equal),
+                        _ => ComparisonType::Text(TextComparison::Matches),
+                    };
+                    conditions.push(RuleCondition::new(path.member.clone(), comparison, value));
+
+                    let separator = self.next();
+                    match separator.kind {
+                        TokenKind::Comma => continue,
+                        TokenKind::RightBracket => break,
+                        _ => {
+                            return Err(RuleError::new("Expected `,` or `]`.", separator.span));
+                        }
+                    }
+                }
+
+                Ok(conditions)
+            }
+            TokenKind::Word(ref word) if word.eq_ignore_ascii_case("contains") => {
+                self.parse_contains(path, false)
+            }
+            TokenKind::Word(ref word) if word.eq_ignore_ascii_case("includes") => {
+                self.parse_includes(path, false)
+            }
+            TokenKind::Word(ref word) if word.eq_ignore_ascii_case("not") => {
+                let token = self.next();
+                if token.is_word("contains") {
+                    self.parse_contains(path, true)
+                } else if token.is_word("includes") {
+                    self.parse_includes(path, true)
+                } else {
+                    Err(RuleError::new(
+                        "Expected `contains` or `includes` after `not`.",
+                        token.span,
+                    ))
+                }
+            }
+            TokenKind::Word(ref word) if word.eq_ignore_ascii_case("is") => {
+                let negated = self.peek().is_word("not");
+                if negated {
+                    self.next();
+                }
+                self.expect_word("empty")?;
+
+                let check = if negated {
+                    TextListComparison::IsNotEmpty
+                } else {
+                    TextListComparison::IsEmpty
+                };
+                let comparison = wrap_list_check(&path, ListCheck::Text(check))?;
+
+                Ok(vec![RuleCondition::new(
+                    path.member,
+                    comparison,
+                    ValueType::List(None),
+                )])
+            }
+            _ => Err(RuleError::new(
+                "Expected a comparison such as `>=`, `==`, `in`, `contains`, `includes`, or `is empty`.",
+                token.span,
+            )),
+        }
+    }
+
+    fn parse_sum(&mut self) -> Result<RuleCondition, RuleError> {
+        self.next();
+        self.expect(TokenKind::LeftParen, "`(`")?;
+        let path = self.parse_path()?;
+        self.expect(TokenKind::RightParen, "`)` to close `sum`")?;
+
+        if path.nested {
+            return Err(RuleError::new("Nested lists can't be summed.", path.span));
+        }
+
+        let token = self.next();
+        let TokenKind::Op(op) = token.kind else {
+            return Err(RuleError::new(
+                "Expected a comparison such as `>=` after `sum(...)`.",
+                token.span,
+            ));
+        };
+
+        let (value, value_span) = self.parse_value()?;
+        if !value.is_number() {
+            return Err(RuleError::new(
+                "Sums can only be compared to numbers.",
+                value_span,
+            ));
+        }
+
+        let comparison = wrap_list_check(
+            &path,
+            ListCheck::Number(NumberListComparison::Sum(Box::new(op))),
+        )?;
+
+        Ok(RuleCondition::new(path.member, comparison, value))
+    }
+
+    fn parse_contains(
+        &mut self,
+        path: MemberPath,
+        negated: bool,
+    ) -> Result<Vec<RuleCondition>, RuleError> {
+        require_plain(&path, "`contains`")?;
+        let (value, value_span) = self.parse_value()?;
+
+        if !value.is_string() {
+            return Err(RuleError::new(
+                "`contains` can only be used with quoted text. Use `includes` for lists.",
+                value_span,
+            ));
+        }
+
+        let comparison = if negated {
+            TextComparison::NotContains
+        } else {
+            TextComparison::Contains
+        };
+
+        Ok(vec![RuleCondition::new(
+            path.member,
+            ComparisonType::Text(comparison),
+            value,
+        )])
+    }
+
+    fn parse_includes(
+        &mut self,
+        path: MemberPath,
+        negated: bool,
+    ) -> Result<Vec<RuleCondition>, RuleError> {
+        let (value, _) = self.parse_value()?;
+
+        let check = match (&value, negated) {
+            (ValueType::Number(_), false) => ListCheck::Number(NumberListComparison::Contains),
+            (ValueType::Number(_), true) => ListCheck::Number(NumberListComparison::NotContains),
+            (_, false) => ListCheck::Text(TextListComparison::Contains),
+            (_, true) => ListCheck::Text(TextListComparison::NotContains),
+        };
+
+        let comparison = wrap_list_check(&path, check)?;
+        Ok(vec![RuleCondition::new(path.member, comparison, value)])
+    }
+}
+
+fn require_plain(path: &MemberPath, what: &str) -> Result<(), RuleError> {
+    if path.is_plain() {
+        Ok(())
+    } else {
+        Err(RuleError::new(
+            format!("{what} can only be used on a single value, not a list of entries."),
+            path.span.clone(),
+        ))
+    }
+}
+
+/// Wraps a list check into the comparison that reaches the list described by the path.
+fn wrap_list_check(path: &MemberPath, check: ListCheck) -> Result<ComparisonType, RuleError> {
+    let comparison = match (check, path.key.clone(), path.nested) {
+        (ListCheck::Text(check), None, false) => ComparisonType::TextList(check),
+        (ListCheck::Number(check), None, false) => ComparisonType::NumberList(check),
+        (ListCheck::Text(check), Some(key), false) => {
+            ComparisonType::MapList(MapListComparison::FlattenToTextList(key, Box::new(check)))
+        }
+        (ListCheck::Number(check), Some(key), false) => {
+            ComparisonType::MapList(MapListComparison::FlattenToNumberList(key, Box::new(check)))
+        }
+        (ListCheck::Text(check), None, true) => {
+            ComparisonType::NestedList(NestedListComparison::FlattenToTextList(Box::new(check)))
+        }
+        (ListCheck::Text(check), Some(key), true) => {
+            ComparisonType::MapList(MapListComparison::FlattenToNestedList(
+                key,
+                Box::new(NestedListComparison::FlattenToTextList(Box::new(check))),
+            ))
+        }
+        (ListCheck::Number(_), _, true) => {
+            return Err(RuleError::new(
+                "Nested lists can only be checked against quoted text.",
+                path.span.clone(),
+            ));
+        }
+    };
+
+    Ok(comparison)
+}
+
+// endregion
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_number_and_in_list() {
+        let rule = Rule::parse(r#"gpa >= 3.0 and town in ["Middlebury", "Southbury"]"#).unwrap();
+
+        assert_eq!(rule.groups.len(), 2);
+        assert_eq!(
+            rule.groups[0],
+            vec![RuleCondition::new(
+                "gpa",
+                ComparisonType::Number(NumberComparison::GreaterThanOrEqual),
+                ValueType::Number(Some("3.0".to_string())),
+            )]
+        );
+        assert_eq!(
+            rule.groups[1],
+            vec![
+                RuleCondition::new(
+                    "town",
+                    ComparisonType::Text(TextComparison::Matches),
+                    ValueType::String(Some("Middlebury".to_string())),
+                ),
+                RuleCondition::new(
+                    "town",
+                    ComparisonType::Text(TextComparison::Matches),
+                    ValueType::String(Some("Southbury".to_string())),
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_list_paths() {
+        let rule = Rule::parse(
+            r#"sports_participation.sport_name includes "Golf" and sum(service_hours) > 20 and extracurricular.grades[*] not includes "9th""#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            rule.groups[0][0].comparison,
+            ComparisonType::MapList(MapListComparison::FlattenToTextList(
+                "sport_name".to_string(),
+                Box::new(TextListComparison::Contains),
+            ))
+        );
+        assert_eq!(
+            rule.groups[1][0].comparison,
+            ComparisonType::NumberList(NumberListComparison::Sum(Box::new(
+                NumberComparison::GreaterThan
+            )))
+        );
+        assert_eq!(
+            rule.groups[2][0].comparison,
+            ComparisonType::MapList(MapListComparison::FlattenToNestedList(
+                "grades".to_string(),
+                Box::new(NestedListComparison::FlattenToTextList(Box::new(
+                    TextListComparison::NotContains
+                ))),
+            ))
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let rules = [
+            r#"gpa >= 3.0 and town in ["Middlebury", "Southbury"]"#,
+            r#"(major contains "Nursing" or major == "Allied Health") and attend_bas != "No""#,
+            r#"sum(extracurricular.num_hours) <= 100"#,
+            r#"awards is not empty or honors[*] includes "Honor \"Roll\"""#,
+            r#"act_score in [30, 31] and sat_score < 1600"#,
+        ];
+
+        for text in rules {
+            let rule = Rule::parse(text).unwrap();
+            assert_eq!(rule.to_string(), text);
+            assert_eq!(Rule::parse(&rule.to_string()).unwrap(), rule);
+        }
+    }
+
+    #[test]
+    fn print_existing_comparison() {
+        let comparison = ComparisonData::new(
+            "sports_golf",
+            "sports_participation",
+            ComparisonType::MapList(MapListComparison::FlattenToTextList(
+                "sport_name".to_string(),
+                Box::new(TextListComparison::Contains),
+            )),
+            ValueType::String(Some("Golf".to_string())),
+            "Sports Participation",
+            "Golf",
+        );
+
+        assert_eq!(
+            comparison.to_rule_text(),
+            r#"sports_participation.sport_name includes "Golf""#
+        );
+        assert_eq!(
+            RuleCondition::from(&comparison).describe(),
+            "any sports participation entry's sport name includes Golf"
+        );
+    }
+
+    #[test]
+    fn describe_rule() {
+        let rule = Rule::parse(
+            r#"weighted_gpa >= 3.0 and town in ["Middlebury", "Southbury"] and (attend_bas == "Yes" or sum(service_hours) > 20)"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            rule.describe(),
+            "weighted gpa is at least 3.0, and town is Middlebury or Southbury, and either attend bas is Yes or the total service hours is greater than 20"
+        );
+    }
+
+    #[test]
+    fn into_comparisons() {
+        let comparisons = Rule::parse(r#"gpa >= 3.0 or town == "Southbury""#)
+            .unwrap()
+            .into_comparisons("Residency");
+
+        assert_eq!(comparisons.len(), 2);
+        assert_eq!(comparisons[0].id, "gpa_at_least_3_0");
+        assert_eq!(comparisons[0].display_text, "gpa is at least 3.0");
+        assert_eq!(comparisons[1].id, "town_is_southbury");
+        assert_eq!(comparisons[1].category, "Residency");
+    }
+
+    #[test]
+    fn into_comparisons_keeps_and() {
+        let comparisons =
+            Rule::parse(r#"gpa >= 3.0 and (town == "Southbury" or town == "Oxford")"#)
+                .unwrap()
+                .into_comparisons("Residency");
+
+        let categories = comparisons
+            .iter()
+            .map(|comp| (comp.id.as_str(), comp.category.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            categories,
+            vec![
+                ("gpa_at_least_3_0", "Residency 1"),
+                ("town_is_southbury", "Residency 2"),
+                ("town_is_oxford", "Residency 2"),
+            ]
+        );
+    }
+
+    #[test]
+    fn evaluate_rule() {
+        let rule = Rule::parse(r#"gpa >= 3.0 and town in ["Middlebury", "Southbury"]"#).unwrap();
+        let student = |gpa: &str, town: &str| {
+            HashMap::from([
+                ("gpa".to_string(), ValueType::Number(Some(gpa.to_string()))),
+                ("town".to_string(), ValueType::String(Some(town.to_string()))),
+            ])
+        };
+
+        assert!(rule.evaluate(&student("3.2", "Southbury")));
+        assert!(!rule.evaluate(&student("3.2", "Oxford")));
+        assert!(!rule.evaluate(&student("2.9", "Middlebury")));
+        assert!(!rule.evaluate(&HashMap::new()));
+    }
+
+    #[test]
+    fn error_spans() {
+        let cases = [
+            ("gpa >= ", 7..7),
+            (r#"gpa >= "high""#, 4..13),
+            ("gpa => 3", 4..5),
+            (r#"town in ["A", ]"#, 14..15),
+            (r#"town == "Middlebury"#, 8..19),
+            ("a == 1 or b == 2 and c == 3", 0..16),
+            ("(a == 1 or b == 2", 17..17),
+            ("sports.sport_name >= 2", 0..17),
+            ("gpa 3", 4..5),
+            ("gpa >= 3 gpa", 9..12),
+        ];
+
+        for (text, span) in cases {
+            let error = Rule::parse(text).unwrap_err();
+            assert_eq!(error.span, span, "unexpected span for {text:?}: {error}");
+        }
+    }
+
+    #[test]
+    fn render_error() {
+        let text = r#"gpa >= "high""#;
+        let error = Rule::parse(text).unwrap_err();
+
+        assert_eq!(
+            error.render(text),
+            "gpa >= \"high\"\n    ^^^^^^^^^\n`>=` can only be used with numbers."
+        );
+    }
+}
@@ -0,0 +1,299 @@
+use crate::common::{FAFSA_FORM, FAFSA_INPUT, FormDefinition, ValueType, has_file, has_value};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// # Application Upload
+///
+/// A file upload that is part of the student application, along with the sidebar path of the
+/// page where it's uploaded. Uploads that aren't required are still listed, but never count
+/// against the student's completion.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ApplicationUpload {
+    pub form_id: &'static str,
+    pub input_name: &'static str,
+    pub title: &'static str,
+    pub path: &'static str,
+    pub required: bool,
+}
+
+/// Gets the file uploads that are part of the student application, in the order that they're
+/// shown after the forms.
+pub fn application_uploads() -> [ApplicationUpload; 1] {
+    [ApplicationUpload {
+        form_id: FAFSA_FORM,
+        input_name: FAFSA_INPUT,
+        title: "Financial Information",
+        path: "additional/financials",
+        required: false,
+    }]
+}
+
+/// How far along a student is with one part of their application.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum CompletionStatus {
+    /// Every required field has been answered.
+    Complete,
+    /// Some of the required fields have been answered.
+    InProgress,
+    /// None of the required fields have been answered.
+    NotStarted,
+    /// Nothing is required and nothing has been answered.
+    Optional,
+}
+
+/// # Form Completion
+///
+/// How much of a single form or upload the student has finished. Only required fields are
+/// counted, and fields that are hidden by their conditions aren't required.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FormCompletion {
+    /// The ID of the form, or the form ID of the upload.
+    pub id: String,
+    pub title: String,
+    /// The path of the form's page, relative to the student pages, such as `additional/athletics`.
+    pub path: String,
+    /// The number of required fields.
+    pub required: usize,
+    /// The number of required fields that have been answered.
+    pub answered: usize,
+    /// The labels of the required fields that haven't been answered.
+    pub missing: Vec<String>,
+    /// Whether the student has answered anything, including optional fields.
+    pub started: bool,
+}
+
+impl FormCompletion {
+    /// Checks a form's stored data against the required fields of its definition.
+    pub fn from_definition(definition: &FormDefinition, data: &HashMap<String, ValueType>) -> Self {
+        let missing = definition
+            .fields()
+            .filter(|field| field.is_required(data) && !has_value(data, field.key()))
+            .map(|field| {
+                let label = field.label().trim().trim_end_matches(':');
+                if label.is_empty() {
+                    field.key().to_string()
+                } else {
+                    label.to_string()
+                }
+            })
+            .collect::<Vec<String>>();
+        let required = definition
+            .fields()
+            .filter(|field| field.is_required(data))
+            .count();
+
+        Self {
+            id: definition.id.clone(),
+            title: definition.title.clone(),
+            path: definition.path.clone(),
+            required,
+            answered: required - missing.len(),
+            missing,
+            started: definition
+                .fields()
+                .any(|field| has_value(data, field.key())),
+        }
+    }
+
+    /// Checks an upload against the sort keys of the student's file entries.
+    pub fn from_upload(upload: &ApplicationUpload, files: &[String]) -> Self {
+        let uploaded = has_file(files, upload.form_id, upload.input_name);
+        let required = usize::from(upload.required);
+        let missing = (upload.required && !uploaded)
+            .then(|| upload.title.to_string())
+            .into_iter()
+            .collect::<Vec<String>>();
+
+        Self {
+            id: upload.form_id.to_string(),
+            title: upload.title.to_string(),
+            path: upload.path.to_string(),
+            required,
+            answered: required - missing.len(),
+            missing,
+            started: uploaded,
+        }
+    }
+
+    pub fn status(&self) -> CompletionStatus {
+        if self.required == 0 {
+            if self.started {
+                CompletionStatus::Complete
+            } else {
+                CompletionStatus::Optional
+            }
+        } else if self.answered == self.required {
+            CompletionStatus::Complete
+        } else if self.answered > 0 {
+            CompletionStatus::InProgress
+        } else {
+            CompletionStatus::NotStarted
+        }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.answered == self.required
+    }
+
+    /// The short text of the form's sidebar badge, such as `✓` or `3/9`.
+    pub fn badge(&self) -> String {
+        match self.status() {
+            CompletionStatus::Complete => "✓".to_string(),
+            CompletionStatus::Optional => "Optional".to_string(),
+            _ => format!("{}/{}", self.answered, self.required),
+        }
+    }
+}
+
+/// # Application Completion
+///
+/// How much of the whole application a student has finished, with one entry for each form and
+/// then one for each upload. Created using [`get_application_completion`].
+///
+/// [`get_application_completion`]: crate::pages::api::get_application_completion
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct ApplicationCompletion {
+    pub forms: Vec<FormCompletion>,
+}
+
+impl ApplicationCompletion {
+    /// Checks a student's data against every form definition and application upload. The data is
+    /// keyed by sort key, which is the form's ID in uppercase, and `files` are the sort keys of
+    /// the student's file entries.
+    pub fn new(
+        definitions: &[FormDefinition],
+        data: &HashMap<String, HashMap<String, ValueType>>,
+        files: &[String],
+    ) -> Self {
+        let empty = HashMap::new();
+        let forms = definitions
+            .iter()
+            .map(|definition| {
+                let form_data = data.get(&definition.id.to_uppercase()).unwrap_or(&empty);
+                FormCompletion::from_definition(definition, form_data)
+            })
+            .chain(
+                application_uploads()
+                    .iter()
+                    .map(|upload| FormCompletion::from_upload(upload, files)),
+            )
+            .collect();
+
+        Self { forms }
+    }
+
+    /// Gets the entry of the form with the given path.
+    pub fn form(&self, path: &str) -> Option<&FormCompletion> {
+        self.forms.iter().find(|form| form.path == path)
+    }
+
+    /// The percentage of required fields and uploads that are finished, rounded down. An
+    /// application without anything required is 100% complete.
+    pub fn percent(&self) -> usize {
+        let required = self.forms.iter().map(|form| form.required).sum::<usize>();
+        let answered = self.forms.iter().map(|form| form.answered).sum::<usize>();
+        (answered * 100).checked_div(required).unwrap_or(100)
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.forms.iter().all(FormCompletion::is_complete)
+    }
+
+    /// Gets the forms that still have required fields or uploads left.
+    pub fn incomplete(&self) -> impl Iterator<Item = &FormCompletion> {
+        self.forms.iter().filter(|form| !form.is_complete())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::comparison::test_utils::text;
+    use crate::common::{FormSection, InputType};
+
+    fn text_input(key: &str, label: &str, required: bool) -> InputType {
+        InputType::Text(key.to_string(), label.to_string(), String::new(), required)
+    }
+
+    fn definitions() -> Vec<FormDefinition> {
+        vec![
+            FormDefinition::new("demographics", "Demographics", "").section(FormSection::new(
+                "",
+                "",
+                vec![
+                    text_input("first_name", "First Name:", true),
+                    text_input("last_name", "Last Name:", true),
+                    text_input("nickname", "Nickname:", false),
+                    text_input("parent_two_name", "Parent 2 Name:", true)
+                        .shown_when(r#"has_second_parent == "Yes""#),
+                ],
+            )),
+            FormDefinition::new("athletics", "Athletics", "")
+                .tab("Athletics", "additional/athletics")
+                .section(FormSection::new(
+                    "",
+                    "",
+                    vec![text_input("sport", "Sport:", false)],
+                )),
+        ]
+    }
+
+    #[test]
+    fn tracks_required_fields() {
+        let data = HashMap::from([(
+            "DEMOGRAPHICS".to_string(),
+            HashMap::from([("first_name".to_string(), text("Ada"))]),
+        )]);
+        let completion = ApplicationCompletion::new(&definitions(), &data, &[]);
+
+        let demographics = completion.form("demographics").unwrap();
+        assert_eq!((demographics.answered, demographics.required), (1, 2));
+        assert_eq!(demographics.missing, vec!["Last Name".to_string()]);
+        assert_eq!(demographics.status(), CompletionStatus::InProgress);
+        assert_eq!(demographics.badge(), "1/2");
+
+        // Forms without required fields are optional until they're started.
+        let athletics = completion.form("additional/athletics").unwrap();
+        assert_eq!(athletics.status(), CompletionStatus::Optional);
+        assert_eq!(
+            completion.form("additional/financials").unwrap().status(),
+            CompletionStatus::Optional
+        );
+
+        assert_eq!(completion.percent(), 50);
+        assert!(!completion.is_complete());
+        assert_eq!(completion.incomplete().count(), 1);
+    }
+
+    #[test]
+    fn tracks_conditional_fields_and_uploads() {
+        let data = HashMap::from([(
+            "DEMOGRAPHICS".to_string(),
+            HashMap::from([
+                ("first_name".to_string(), text("Ada")),
+                ("last_name".to_string(), text("Lovelace")),
+                ("has_second_parent".to_string(), text("Yes")),
+            ]),
+        )]);
+        let files = vec!["FILE#financial_info#fafsa#fafsa.pdf".to_string()];
+        let completion = ApplicationCompletion::new(&definitions(), &data, &files);
+
+        let demographics = completion.form("demographics").unwrap();
+        assert_eq!(demographics.missing, vec!["Parent 2 Name".to_string()]);
+        assert_eq!(completion.percent(), 66);
+        assert_eq!(
+            completion.form("additional/financials").unwrap().status(),
+            CompletionStatus::Complete
+        );
+
+        let upload = ApplicationUpload {
+            required: true,
+            ..application_uploads()[0]
+        };
+        assert_eq!(
+            FormCompletion::from_upload(&upload, &[]).missing,
+            vec!["Financial Information".to_string()]
+        );
+        assert!(FormCompletion::from_upload(&upload, &files).is_complete());
+    }
+}
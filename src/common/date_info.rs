@@ -1,12 +1,54 @@
-use chrono::{DateTime, FixedOffset, Local};
+use chrono::{DateTime, FixedOffset, Local, NaiveDateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+/// What an important date controls. Dates with a purpose open and close parts of the site, both
+/// in routing and on the server, while dates without one are only shown in the date list.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum DatePurpose {
+    /// When students can fill out their forms and upload files.
+    StudentForms,
+    /// When providers can create and edit scholarships.
+    ProviderForms,
+    /// When providers can view applicants and choose recipients.
+    DecisionWindow,
+}
+
+impl DatePurpose {
+    pub const ALL: [DatePurpose; 3] = [
+        DatePurpose::StudentForms,
+        DatePurpose::ProviderForms,
+        DatePurpose::DecisionWindow,
+    ];
+
+    /// The name of the purpose, as shown to admins.
+    pub fn name(&self) -> &'static str {
+        match self {
+            DatePurpose::StudentForms => "Student Forms",
+            DatePurpose::ProviderForms => "Provider Forms",
+            DatePurpose::DecisionWindow => "Decision Window",
+        }
+    }
+
+    /// What the purpose's window is called in messages, such as `This form` in
+    /// `This form opens on ...`.
+    pub fn window_subject(&self) -> &'static str {
+        match self {
+            DatePurpose::StudentForms => "This form",
+            DatePurpose::ProviderForms => "Scholarship editing",
+            DatePurpose::DecisionWindow => "Applicant review",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DateInfo {
     pub id: String,
     pub title: String,
     pub date: DateRange,
     pub description: String,
+    /// What the date controls. Dates that were saved before purposes existed have none.
+    #[serde(default)]
+    pub purpose: Option<DatePurpose>,
 }
 
 impl DateInfo {
@@ -15,6 +57,30 @@ impl DateInfo {
             DateRange::Single(date) | DateRange::Range(date, _) => date,
         }
     }
+
+    /// Checks that the date can be saved. Every date needs an ID and a title, ranges must end
+    /// after they start, and dates with a purpose must be ranges, since single dates can't open
+    /// or close anything.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.id.trim().is_empty() {
+            return Err("Dates must have an ID.".to_string());
+        }
+        if self.title.trim().is_empty() {
+            return Err("Dates must have a title.".to_string());
+        }
+
+        match (&self.date, self.purpose) {
+            (DateRange::Range(start, end), _) if end <= start => {
+                Err(format!("{:?} must end after it starts.", self.title))
+            }
+            (DateRange::Single(_), Some(purpose)) => Err(format!(
+                "{:?} controls {} and so must have an end date.",
+                self.title,
+                purpose.name()
+            )),
+            _ => Ok(()),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -53,6 +119,119 @@ impl DateRange {
     }
 }
 
+/// # Window State
+///
+/// Whether the part of the site that a [`DatePurpose`] controls is open. Windows are made from
+/// the ranges of every date with that purpose, and a student's extension can keep a closed window
+/// open for them.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum WindowState {
+    /// No dates have the purpose, so nothing is restricted.
+    Unscheduled,
+    /// The window hasn't opened yet. Contains when it opens.
+    Upcoming(DateTime<FixedOffset>),
+    /// The window is open. Contains when it closes.
+    Open(DateTime<FixedOffset>),
+    /// The window has closed, but there's an extension. Contains when the extension ends.
+    Extended(DateTime<FixedOffset>),
+    /// The window has closed. Contains when it closed.
+    Closed(DateTime<FixedOffset>),
+}
+
+impl WindowState {
+    /// Finds the state of a purpose's window at the given time. Single dates don't have a range,
+    /// so they're ignored.
+    pub fn for_purpose(dates: &[DateInfo], purpose: DatePurpose, now: DateTime<Utc>) -> Self {
+        let ranges = dates
+            .iter()
+            .filter(|info| info.purpose == Some(purpose))
+            .filter_map(|info| match info.date {
+                DateRange::Range(start, end) => Some((start, end)),
+                DateRange::Single(_) => None,
+            })
+            .collect::<Vec<_>>();
+
+        if let Some((_, end)) = ranges
+            .iter()
+            .filter(|(start, end)| *start <= now && now < *end)
+            .max_by_key(|(_, end)| *end)
+        {
+            return WindowState::Open(*end);
+        }
+        if let Some((start, _)) = ranges
+            .iter()
+            .filter(|(start, _)| now < *start)
+            .min_by_key(|(start, _)| *start)
+        {
+            return WindowState::Upcoming(*start);
+        }
+
+        match ranges.iter().map(|(_, end)| *end).max() {
+            Some(end) => WindowState::Closed(end),
+            None => WindowState::Unscheduled,
+        }
+    }
+
+    /// Applies an extension, which keeps a window open until it ends. Extensions don't open
+    /// windows early and don't shorten open windows.
+    pub fn with_extension(self, until: Option<DateTime<FixedOffset>>, now: DateTime<Utc>) -> Self {
+        match (self, until) {
+            (WindowState::Closed(_), Some(until)) if now < until => WindowState::Extended(until),
+            (state, _) => state,
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        matches!(
+            self,
+            WindowState::Unscheduled | WindowState::Open(_) | WindowState::Extended(_)
+        )
+    }
+
+    /// Describes the state of a purpose's window for the people it affects, such as
+    /// `This form closed on ...` for the student forms.
+    pub fn describe(&self, purpose: DatePurpose) -> String {
+        let format = |date: &DateTime<FixedOffset>| date.format("%A %B %d at %I:%M%p").to_string();
+        let subject = purpose.window_subject();
+
+        match self {
+            WindowState::Unscheduled => format!("{} is open.", subject),
+            WindowState::Upcoming(date) => format!("{} opens on {}.", subject, format(date)),
+            WindowState::Open(date) => format!("{} closes on {}.", subject, format(date)),
+            WindowState::Extended(date) => {
+                format!("You have an extension until {}.", format(date))
+            }
+            WindowState::Closed(date) => format!("{} closed on {}.", subject, format(date)),
+        }
+    }
+
+    /// Checks that a purpose's window is open, for use by server functions that write data.
+    pub fn check_open(&self, purpose: DatePurpose) -> Result<(), String> {
+        if self.is_open() {
+            Ok(())
+        } else {
+            Err(format!("{} Changes can't be saved.", self.describe(purpose)))
+        }
+    }
+}
+
+/// The format of a `datetime-local` input's value.
+const LOCAL_INPUT_FORMAT: &str = "%Y-%m-%dT%H:%M";
+
+/// Parses the value of a `datetime-local` input, which has no time zone, as a time at the given
+/// offset.
+pub fn parse_local_input(input: &str, offset: FixedOffset) -> Option<DateTime<FixedOffset>> {
+    NaiveDateTime::parse_from_str(input, LOCAL_INPUT_FORMAT)
+        .ok()?
+        .and_local_timezone(offset)
+        .single()
+}
+
+/// Formats a time as the value of a `datetime-local` input, in the time's own offset.
+pub fn to_local_input(date: &DateTime<FixedOffset>) -> String {
+    date.format(LOCAL_INPUT_FORMAT).to_string()
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, PartialOrd, Eq, Ord)]
 pub enum DateStatus {
     Closed,
@@ -61,3 +240,144 @@ pub enum DateStatus {
     Upcoming,
     Blank,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(text: &str) -> DateTime<FixedOffset> {
+        DateTime::parse_from_rfc3339(text).unwrap()
+    }
+
+    fn window(purpose: DatePurpose, start: &str, end: &str) -> DateInfo {
+        DateInfo {
+            id: format!("{start}-{end}"),
+            title: "Window".to_string(),
+            date: DateRange::Range(time(start), time(end)),
+            description: String::new(),
+            purpose: Some(purpose),
+        }
+    }
+
+    #[test]
+    fn finds_window_state() {
+        let dates = vec![
+            window(
+                DatePurpose::StudentForms,
+                "2026-03-03T07:15:00-05:00",
+                "2026-03-27T14:05:00-05:00",
+            ),
+            window(
+                DatePurpose::ProviderForms,
+                "2026-01-20T07:15:00-05:00",
+                "2026-03-03T23:59:00-05:00",
+            ),
+        ];
+        let state = |now: &str| {
+            WindowState::for_purpose(&dates, DatePurpose::StudentForms, time(now).to_utc())
+        };
+
+        assert_eq!(
+            state("2026-03-01T00:00:00-05:00"),
+            WindowState::Upcoming(time("2026-03-03T07:15:00-05:00"))
+        );
+        assert_eq!(
+            state("2026-03-10T00:00:00-05:00"),
+            WindowState::Open(time("2026-03-27T14:05:00-05:00"))
+        );
+        assert_eq!(
+            state("2026-03-27T14:05:00-05:00"),
+            WindowState::Closed(time("2026-03-27T14:05:00-05:00"))
+        );
+        assert!(!state("2026-04-01T00:00:00-05:00").is_open());
+        assert_eq!(
+            WindowState::for_purpose(
+                &dates,
+                DatePurpose::DecisionWindow,
+                time("2026-04-01T00:00:00-05:00").to_utc()
+            ),
+            WindowState::Unscheduled
+        );
+    }
+
+    #[test]
+    fn applies_extensions() {
+        let now = time("2026-03-30T00:00:00-05:00").to_utc();
+        let closed = WindowState::Closed(time("2026-03-27T14:05:00-05:00"));
+        let extension = time("2026-04-01T00:00:00-05:00");
+
+        assert_eq!(
+            closed.clone().with_extension(Some(extension), now),
+            WindowState::Extended(extension)
+        );
+        assert!(
+            closed
+                .clone()
+                .with_extension(Some(time("2026-03-29T00:00:00-05:00")), now)
+                .check_open(DatePurpose::StudentForms)
+                .is_err()
+        );
+        assert_eq!(
+            WindowState::Upcoming(extension).with_extension(Some(extension), now),
+            WindowState::Upcoming(extension)
+        );
+    }
+
+    #[test]
+    fn describes_by_purpose() {
+        let closed = WindowState::Closed(time("2026-03-27T14:05:00-05:00"));
+
+        assert_eq!(
+            closed.describe(DatePurpose::StudentForms),
+            "This form closed on Friday March 27 at 02:05PM."
+        );
+        assert_eq!(
+            WindowState::Upcoming(time("2026-03-03T07:15:00-05:00"))
+                .describe(DatePurpose::ProviderForms),
+            "Scholarship editing opens on Tuesday March 03 at 07:15AM."
+        );
+        assert_eq!(
+            closed.check_open(DatePurpose::DecisionWindow),
+            Err("Applicant review closed on Friday March 27 at 02:05PM. Changes can't be saved."
+                .to_string())
+        );
+        assert_eq!(
+            WindowState::Unscheduled.describe(DatePurpose::DecisionWindow),
+            "Applicant review is open."
+        );
+    }
+    #[test]
+    fn validates_dates() {
+        let mut date = window(
+            DatePurpose::StudentForms,
+            "2026-03-03T07:15:00-05:00",
+            "2026-03-27T14:05:00-05:00",
+        );
+        assert_eq!(date.validate(), Ok(()));
+
+        date.date = DateRange::Range(
+            time("2026-03-27T14:05:00-05:00"),
+            time("2026-03-03T07:15:00-05:00"),
+        );
+        assert!(date.validate().is_err());
+
+        date.date = DateRange::Single(time("2026-03-03T07:15:00-05:00"));
+        assert!(date.validate().is_err());
+        date.purpose = None;
+        assert_eq!(date.validate(), Ok(()));
+
+        date.title = " ".to_string();
+        assert!(date.validate().is_err());
+    }
+
+    #[test]
+    fn parses_local_inputs() {
+        let eastern = FixedOffset::west_opt(5 * 3600).unwrap();
+        let date = parse_local_input("2026-03-27T14:05", eastern).unwrap();
+
+        assert_eq!(date, time("2026-03-27T14:05:00-05:00"));
+        assert_eq!(to_local_input(&date), "2026-03-27T14:05");
+        assert_eq!(parse_local_input("", eastern), None);
+        assert_eq!(parse_local_input("2026-03-27", eastern), None);
+    }
+}
@@ -0,0 +1,521 @@
+use crate::common::{
+    ComparisonData, ExpandableInfo, ScoreBreakdown, ValuePath, ValueType, score_student,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// The form that contains the student's FAFSA upload.
+pub static FAFSA_FORM: &str = "financial_info";
+/// The input on the [`FAFSA_FORM`] that contains the student's FAFSA upload.
+pub static FAFSA_INPUT: &str = "fafsa";
+/// The form that contains the student's scholarship essays. Each essay is stored using the
+/// scholarship's ID as the input name.
+pub static ESSAY_FORM: &str = "scholarship_essays";
+
+/// How the requirements within a single category are combined.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum CombinationMode {
+    /// The student must pass every requirement in the category.
+    All,
+    /// The student must pass at least one requirement in the category. This is the default, and is
+    /// used by scholarships that were saved before combination modes existed.
+    #[default]
+    Any,
+    /// The student must pass at least this many requirements in the category. If the category has
+    /// fewer requirements than this, the student must pass all of them.
+    AtLeast(usize),
+}
+
+impl CombinationMode {
+    /// Checks if a category passes given the number of requirements that passed and failed. Empty
+    /// categories always pass.
+    pub fn passes(&self, passed: usize, failed: usize) -> bool {
+        let total = passed + failed;
+
+        match self {
+            CombinationMode::All => failed == 0,
+            CombinationMode::Any => passed > 0 || total == 0,
+            CombinationMode::AtLeast(count) => passed >= (*count).min(total),
+        }
+    }
+
+    /// The name of the mode, as stored in the `mode` key of a requirement category.
+    pub fn name(&self) -> &'static str {
+        match self {
+            CombinationMode::All => "All",
+            CombinationMode::Any => "Any",
+            CombinationMode::AtLeast(_) => "AtLeast",
+        }
+    }
+}
+
+/// # Requirement Category
+///
+/// The requirements of a single category in a scholarship's `requirements` map. Categories are
+/// stored in one of two formats:
+///  - A list of comparison IDs, which uses [`CombinationMode::Any`]. This is the original format.
+///  - A map containing the comparison IDs in `ids`, the mode name in `mode`, and the number of
+///    requirements used by [`CombinationMode::AtLeast`] in `count`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct RequirementCategory {
+    pub ids: Vec<String>,
+    pub mode: CombinationMode,
+}
+
+impl RequirementCategory {
+    /// Reads a category from either of the stored formats. Unknown modes fall back to
+    /// [`CombinationMode::Any`].
+    pub fn from_value(value: &ValueType) -> Self {
+        let map = value.as_map().ok().flatten();
+        let ids = match &map {
+            Some(map) => map.get("ids").cloned().unwrap_or_default(),
+            None => value.clone(),
+        };
+        let ids = ids
+            .as_list()
+            .ok()
+            .flatten()
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|v| v.as_string().ok().flatten())
+            .collect::<Vec<String>>();
+
+        let get_text = |key: &str| {
+            map.as_ref()
+                .and_then(|map| map.get(key))
+                .map(|v| v.to_string())
+                .unwrap_or_default()
+        };
+        let mode = match get_text("mode").as_str() {
+            "All" => CombinationMode::All,
+            "AtLeast" => CombinationMode::AtLeast(get_text("count").trim().parse().unwrap_or(1)),
+            _ => CombinationMode::Any,
+        };
+
+        Self { ids, mode }
+    }
+
+    /// Converts the category into the stored format. Categories using [`CombinationMode::Any`] are
+    /// stored as a plain list, so that they stay readable by anything expecting the original
+    /// format.
+    pub fn to_value(&self) -> ValueType {
+        let ids = ValueType::List(Some(
+            self.ids
+                .iter()
+                .map(|id| ValueType::String(Some(id.clone())))
+                .collect(),
+        ));
+
+        match self.mode {
+            CombinationMode::Any => ids,
+            mode => {
+                let mut map = HashMap::from([
+                    ("ids".to_string(), ids),
+                    ("mode".to_string(), ValueType::String(Some(mode.name().to_string()))),
+                ]);
+                if let CombinationMode::AtLeast(count) = mode {
+                    map.insert("count".to_string(), ValueType::Number(Some(count.to_string())));
+                }
+
+                ValueType::Map(Some(map))
+            }
+        }
+    }
+}
+
+/// The result of checking a single requirement category on a scholarship.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CategoryResult {
+    /// The key of the category within the scholarship's requirements, such as `gpa`.
+    pub category: String,
+    /// How the category's requirements were combined.
+    pub mode: CombinationMode,
+    /// Whether the student passed this category, according to its [`CombinationMode`].
+    pub passed: bool,
+    /// The IDs of the requirements that the student passed.
+    pub passed_ids: Vec<String>,
+    /// The IDs of the requirements that the student failed.
+    pub failed_ids: Vec<String>,
+    /// The IDs of the requirements that couldn't be found in the comparisons list. These are
+    /// ignored when checking the category.
+    pub missing_ids: Vec<String>,
+}
+
+/// # Eligibility Result
+///
+/// The result of checking a student against a single scholarship. Created using
+/// [`evaluate_scholarship`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct EligibilityResult {
+    /// The ID of the scholarship that was checked.
+    pub scholarship_id: String,
+    /// Whether the student passed every requirement category.
+    pub requirements_met: bool,
+    /// Whether the scholarship requires the student's FAFSA.
+    pub fafsa_required: bool,
+    /// Whether the student has uploaded their FAFSA.
+    pub fafsa_found: bool,
+    /// Whether the scholarship requires an essay.
+    pub essay_required: bool,
+    /// Whether the student has uploaded an essay for this scholarship.
+    pub essay_found: bool,
+    /// The result of each requirement category, sorted by category key.
+    pub categories: Vec<CategoryResult>,
+    /// The student's score from the scholarship's scoring criteria. Used to rank applicants.
+    pub score: ScoreBreakdown,
+}
+
+impl EligibilityResult {
+    /// Checks if the student is eligible for the scholarship. This requires that every category
+    /// passed and that all required files have been uploaded.
+    pub fn is_eligible(&self) -> bool {
+        self.requirements_met
+            && (!self.fafsa_required || self.fafsa_found)
+            && (!self.essay_required || self.essay_found)
+    }
+}
+
+/// Checks if a scholarship requires the student's FAFSA.
+pub fn fafsa_required(scholarship: &ExpandableInfo) -> bool {
+    get_string(scholarship, "fafsa_required") == "Yes"
+}
+
+/// Checks if a scholarship requires an essay. This is the case when it has an essay prompt.
+pub fn essay_required(scholarship: &ExpandableInfo) -> bool {
+    !get_string(scholarship, "essay_prompt").is_empty()
+}
+
+/// Checks if the list of file keys contains an upload for the given form and input. The file keys
+/// are the sort keys of the student's file entries, in the format `FILE#form#input#file_name`.
+pub fn has_file(files: &[String], form_id: &str, input_name: &str) -> bool {
+    let prefix = format!("FILE#{form_id}#{input_name}#");
+    files.iter().any(|file| file.starts_with(&prefix))
+}
+
+/// Gets the requirements of a scholarship, keyed by category.
+pub fn get_requirements(scholarship: &ExpandableInfo) -> HashMap<String, RequirementCategory> {
+    scholarship
+        .get_map("requirements")
+        .into_iter()
+        .flatten()
+        .map(|(category, value)| (category.clone(), RequirementCategory::from_value(value)))
+        .collect()
+}
+
+/// Removes a comparison ID from every category of a scholarship's requirements, keeping each
+/// category's combination mode. Returns whether the ID was found.
+pub fn remove_requirement(scholarship: &mut ExpandableInfo, comparison_id: &str) -> bool {
+    let mut requirements = get_requirements(scholarship);
+    let mut removed = false;
+
+    for requirement in requirements.values_mut() {
+        let count = requirement.ids.len();
+        requirement.ids.retain(|id| id != comparison_id);
+        removed |= requirement.ids.len() != count;
+    }
+
+    if !removed {
+        return false;
+    }
+
+    let requirements = requirements
+        .into_iter()
+        .map(|(category, requirement)| (category, requirement.to_value()))
+        .collect();
+    scholarship
+        .set_path("requirements", ValueType::Map(Some(requirements)))
+        .is_ok()
+}
+
+/// # Evaluate Scholarship
+///
+/// Checks a student's data against a scholarship's requirements.
+///
+/// The requirements are stored as a map of category keys to [`RequirementCategory`] values, whose
+/// comparison IDs are resolved using the given `comparisons`. The student must pass every
+/// category, and passes a category according to its [`CombinationMode`]. Empty categories always
+/// pass. Requirements that can't be evaluated, such as when the student hasn't filled out the
+/// related field, fail.
+///
+/// The `files` are the sort keys of the student's file entries, and are used to check whether the
+/// student has uploaded their FAFSA and an essay for this scholarship. These are reported
+/// separately from the requirements, as students need to know that they're eligible before they
+/// can write an essay. Use [`EligibilityResult::is_eligible`] to check both.
+///
+/// The student is also scored using the scholarship's scoring criteria, refer to
+/// [`score_student`] for details.
+pub fn evaluate_scholarship(
+    student: &HashMap<String, ValueType>,
+    scholarship: &ExpandableInfo,
+    comparisons: &[ComparisonData],
+    files: &[String],
+) -> EligibilityResult {
+    let mut categories = get_requirements(scholarship)
+        .into_iter()
+        .map(|(category, requirements)| {
+            let mut result = CategoryResult {
+                category,
+                mode: requirements.mode,
+                passed: false,
+                passed_ids: vec![],
+                failed_ids: vec![],
+                missing_ids: vec![],
+            };
+
+            for id in requirements.ids {
+                match comparisons.iter().find(|comparison| comparison.id == id) {
+                    Some(comparison) if comparison.compare(student).unwrap_or(false) => {
+                        result.passed_ids.push(id)
+                    }
+                    Some(_) => result.failed_ids.push(id),
+                    None => result.missing_ids.push(id),
+                }
+            }
+
+            result.passed = requirements
+                .mode
+                .passes(result.passed_ids.len(), result.failed_ids.len());
+            result
+        })
+        .collect::<Vec<CategoryResult>>();
+
+    categories.sort_by(|a, b| a.category.cmp(&b.category));
+
+    EligibilityResult {
+        scholarship_id: scholarship.subject.clone(),
+        requirements_met: categories.iter().all(|category| category.passed),
+        fafsa_required: fafsa_required(scholarship),
+        fafsa_found: has_file(files, FAFSA_FORM, FAFSA_INPUT),
+        essay_required: essay_required(scholarship),
+        essay_found: has_file(files, ESSAY_FORM, &scholarship.subject),
+        categories,
+        score: score_student(student, scholarship),
+    }
+}
+
+fn get_string(info: &ExpandableInfo, key: &str) -> String {
+    info.get_str(key).unwrap_or_default().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::comparison::test_utils::text;
+    use crate::common::{ComparisonType, NumberComparison, TextComparison};
+
+    fn id_list(ids: &[&str]) -> ValueType {
+        ValueType::List(Some(ids.iter().map(|id| text(id)).collect()))
+    }
+
+    fn create_comparisons() -> Vec<ComparisonData> {
+        vec![
+            ComparisonData::new(
+                "gpa_at_least_3",
+                "gpa",
+                ComparisonType::Number(NumberComparison::GreaterThanOrEqual),
+                ValueType::Number(Some("3.0".to_string())),
+                "GPA Limits",
+                "GPA of at least 3.0",
+            ),
+            ComparisonData::new(
+                "town_middlebury",
+                "town",
+                ComparisonType::Text(TextComparison::Matches),
+                text("Middlebury"),
+                "Residency",
+                "Middlebury",
+            ),
+            ComparisonData::new(
+                "town_southbury",
+                "town",
+                ComparisonType::Text(TextComparison::Matches),
+                text("Southbury"),
+                "Residency",
+                "Southbury",
+            ),
+        ]
+    }
+
+    fn create_scholarship(requirements: &[(&str, &[&str])]) -> ExpandableInfo {
+        let mut scholarship = ExpandableInfo::new("scholarship_1");
+        let requirements = requirements
+            .iter()
+            .map(|(category, ids)| (category.to_string(), id_list(ids)))
+            .collect();
+
+        scholarship
+            .data
+            .insert("requirements".to_string(), ValueType::Map(Some(requirements)));
+        scholarship
+    }
+
+    fn create_student(gpa: &str, town: &str) -> HashMap<String, ValueType> {
+        HashMap::from([
+            ("gpa".to_string(), ValueType::Number(Some(gpa.to_string()))),
+            ("town".to_string(), text(town)),
+        ])
+    }
+
+    #[test]
+    fn all_categories_must_pass() {
+        let scholarship = create_scholarship(&[
+            ("gpa", &["gpa_at_least_3"]),
+            ("residency", &["town_middlebury", "town_southbury"]),
+        ]);
+        let comparisons = create_comparisons();
+
+        let passing = evaluate_scholarship(
+            &create_student("3.5", "Southbury"),
+            &scholarship,
+            &comparisons,
+            &[],
+        );
+        assert!(passing.requirements_met);
+        assert!(passing.is_eligible());
+        assert_eq!(passing.categories[1].passed_ids, vec!["town_southbury"]);
+        assert_eq!(passing.categories[1].failed_ids, vec!["town_middlebury"]);
+
+        let failing = evaluate_scholarship(
+            &create_student("2.5", "Southbury"),
+            &scholarship,
+            &comparisons,
+            &[],
+        );
+        assert!(!failing.requirements_met);
+        assert!(!failing.categories[0].passed);
+        assert!(failing.categories[1].passed);
+    }
+
+    #[test]
+    fn empty_and_unresolved_categories_pass() {
+        let scholarship =
+            create_scholarship(&[("gpa", &[]), ("residency", &["removed_comparison"])]);
+
+        let result = evaluate_scholarship(
+            &create_student("2.0", "Oxford"),
+            &scholarship,
+            &create_comparisons(),
+            &[],
+        );
+
+        assert!(result.requirements_met);
+        assert_eq!(result.categories[1].missing_ids, vec!["removed_comparison"]);
+    }
+
+    #[test]
+    fn missing_student_data_fails() {
+        let scholarship = create_scholarship(&[("gpa", &["gpa_at_least_3"])]);
+
+        let result =
+            evaluate_scholarship(&HashMap::new(), &scholarship, &create_comparisons(), &[]);
+
+        assert!(!result.requirements_met);
+    }
+
+    #[test]
+    fn combination_modes() {
+        let mut scholarship = create_scholarship(&[]);
+        let category = |mode| {
+            RequirementCategory {
+                ids: vec![
+                    "gpa_at_least_3".to_string(),
+                    "town_middlebury".to_string(),
+                    "town_southbury".to_string(),
+                ],
+                mode,
+            }
+            .to_value()
+        };
+        let mut evaluate = |mode| {
+            let requirements = HashMap::from([("mixed".to_string(), category(mode))]);
+            scholarship
+                .data
+                .insert("requirements".to_string(), ValueType::Map(Some(requirements)));
+
+            evaluate_scholarship(
+                &create_student("3.5", "Southbury"),
+                &scholarship,
+                &create_comparisons(),
+                &[],
+            )
+            .requirements_met
+        };
+
+        assert!(evaluate(CombinationMode::Any));
+        assert!(!evaluate(CombinationMode::All));
+        assert!(evaluate(CombinationMode::AtLeast(2)));
+        assert!(!evaluate(CombinationMode::AtLeast(3)));
+    }
+
+    #[test]
+    fn requirement_category_formats() {
+        let legacy = id_list(&["a", "b"]);
+        let category = RequirementCategory::from_value(&legacy);
+        assert_eq!(category.mode, CombinationMode::Any);
+        assert_eq!(category.ids, vec!["a", "b"]);
+        assert_eq!(category.to_value(), legacy);
+
+        let at_least = RequirementCategory {
+            ids: vec!["a".to_string()],
+            mode: CombinationMode::AtLeast(2),
+        };
+        assert_eq!(RequirementCategory::from_value(&at_least.to_value()), at_least);
+
+        // Fewer requirements than the count means that all of them are needed.
+        assert!(CombinationMode::AtLeast(2).passes(1, 0));
+        assert!(!CombinationMode::AtLeast(2).passes(1, 1));
+        assert!(CombinationMode::All.passes(0, 0));
+    }
+
+    #[test]
+    fn remove_requirement_keeps_modes() {
+        let mut scholarship = create_scholarship(&[("gpa", &["gpa_at_least_3"])]);
+        let residency = RequirementCategory {
+            ids: vec!["town_middlebury".to_string(), "town_southbury".to_string()],
+            mode: CombinationMode::All,
+        };
+        if let Some(ValueType::Map(Some(requirements))) = scholarship.data.get_mut("requirements") {
+            requirements.insert("residency".to_string(), residency.to_value());
+        }
+
+        assert!(remove_requirement(&mut scholarship, "town_southbury"));
+        assert!(!remove_requirement(&mut scholarship, "town_southbury"));
+
+        let requirements = get_requirements(&scholarship);
+        assert_eq!(requirements["residency"].ids, vec!["town_middlebury"]);
+        assert_eq!(requirements["residency"].mode, CombinationMode::All);
+        assert_eq!(requirements["gpa"].ids, vec!["gpa_at_least_3"]);
+    }
+
+    #[test]
+    fn required_files() {
+        let mut scholarship = create_scholarship(&[]);
+        scholarship
+            .data
+            .insert("fafsa_required".to_string(), text("Yes"));
+        scholarship
+            .data
+            .insert("essay_prompt".to_string(), text("Why?"));
+        let student = create_student("3.0", "Middlebury");
+
+        let without_files = evaluate_scholarship(&student, &scholarship, &[], &[]);
+        assert!(without_files.requirements_met);
+        assert!(without_files.fafsa_required && !without_files.fafsa_found);
+        assert!(without_files.essay_required && !without_files.essay_found);
+        assert!(!without_files.is_eligible());
+
+        let files = vec![
+            "FILE#financial_info#fafsa#fafsa.pdf".to_string(),
+            "FILE#scholarship_essays#scholarship_10#essay.pdf".to_string(),
+        ];
+        let wrong_essay = evaluate_scholarship(&student, &scholarship, &[], &files);
+        assert!(wrong_essay.fafsa_found);
+        assert!(!wrong_essay.essay_found);
+
+        let files = vec![
+            "FILE#financial_info#fafsa#fafsa.pdf".to_string(),
+            "FILE#scholarship_essays#scholarship_1#essay.pdf".to_string(),
+        ];
+        assert!(evaluate_scholarship(&student, &scholarship, &[], &files).is_eligible());
+    }
+}
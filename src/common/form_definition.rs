@@ -0,0 +1,1013 @@
+use crate::common::{
+    InputType, ValueType, check_email, format_currency, format_phone, parse_date,
+    student_form_checks,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// Validation errors for a form submission, keyed by the path of each field that has a problem.
+/// Fields inside a `MultiEntry` use paths such as `sports_participation[0].sport_name`.
+pub type FieldErrors = HashMap<String, String>;
+
+/// The result of a form submission that was accepted by the server. `Err` contains the
+/// [`FieldErrors`] if the submission didn't match its [`FormDefinition`].
+pub type SubmissionResult = Result<(), FieldErrors>;
+
+/// # Form Definition
+///
+/// Describes one of the student forms: the data type that it's stored under, where it's shown, and
+/// every input that it contains. Forms are rendered from their definitions, and submissions are
+/// validated against them on the server, so the same rules apply no matter which client sent the
+/// data.
+///
+/// Example usage:
+/// ```ignore
+/// FormDefinition::new("academics", "Academic Information", "Your grades and test scores.")
+///     .tab("Academics", "additional/academics")
+///     .section(FormSection::new("", "", vec![
+///         input!(Number, "unweighted_gpa", "Unweighted GPA:", true, "4.0"),
+///     ]))
+/// ```
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct FormDefinition {
+    /// The form's ID, which is also the data type that its data is stored under, such as
+    /// `demographics`.
+    pub id: String,
+    /// The title of the form.
+    pub title: String,
+    /// A short description of the form.
+    pub description: String,
+    /// The text of the form's tab in the student sidebar.
+    pub tab_text: String,
+    /// The form's route, relative to the student pages, such as `additional/academics`. Forms
+    /// with a nested route are shown as a sub-tab of their parent route.
+    pub path: String,
+    /// The sections of the form, in the order that they're shown.
+    pub sections: Vec<FormSection>,
+    /// The published version of the form. The definitions in the code are version `0`, and each
+    /// version published by an administrator is one higher than the last.
+    #[serde(default)]
+    pub version: u32,
+    /// The paths of fields that were retired from the form. Students' stored values are kept, so
+    /// these keys are accepted in submissions even though they aren't shown.
+    #[serde(default)]
+    pub retired: Vec<String>,
+}
+
+/// # Field Migration
+///
+/// Declares what happens to students' stored values when a field is removed from a published
+/// [`FormDefinition`]. Every removed key needs a migration, so that publishing a new version
+/// never leaves stored answers behind without saying so. Fields inside a `MultiEntry` are named
+/// by their path, such as `sports_participation.sport_name`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum FieldMigration {
+    /// The field was renamed. Stored values are moved from `from` to `to`, which must be a field
+    /// of the new version in the same list.
+    Rename { from: String, to: String },
+    /// The field is no longer asked for. Stored values are kept under the same key.
+    Retire { key: String },
+}
+
+impl FieldMigration {
+    /// Gets the key of the removed field that this migration covers.
+    pub fn removed_key(&self) -> &str {
+        match self {
+            FieldMigration::Rename { from, .. } => from,
+            FieldMigration::Retire { key } => key,
+        }
+    }
+}
+
+/// # Form Check
+///
+/// A check over the data of a whole form, for rules that involve more than one input, such as
+/// requiring either an SAT or an ACT score. Checks run in the browser while the form is being
+/// filled out, and again on the server when it's submitted. A form can't be published without
+/// the inputs that its checks use.
+///
+/// Example usage:
+/// ```ignore
+/// FormCheck::new(&["sat_score", "act_score"], |data| {
+///     if has_value(data, "sat_score") || has_value(data, "act_score") {
+///         Ok(())
+///     } else {
+///         Err("Enter an SAT or an ACT score.".to_string())
+///     }
+/// })
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct FormCheck {
+    /// The keys of the inputs that the check's error is shown on.
+    pub keys: &'static [&'static str],
+    /// The keys of any other inputs that the check reads.
+    pub reads: &'static [&'static str],
+    check: fn(&HashMap<String, ValueType>) -> Result<(), String>,
+}
+
+impl FormCheck {
+    /// Creates a new [`FormCheck`] that shows its error on each of the `keys`.
+    pub const fn new(
+        keys: &'static [&'static str],
+        check: fn(&HashMap<String, ValueType>) -> Result<(), String>,
+    ) -> Self {
+        Self {
+            keys,
+            reads: &[],
+            check,
+        }
+    }
+
+    /// Sets the keys of the other inputs that the check reads, besides the `keys` that its error
+    /// is shown on.
+    pub const fn reads(mut self, reads: &'static [&'static str]) -> Self {
+        self.reads = reads;
+        self
+    }
+
+    /// Gets the key of every input that the check uses.
+    pub fn inputs(&self) -> impl Iterator<Item = &'static str> {
+        self.keys.iter().chain(self.reads).copied()
+    }
+
+    /// Runs the check, adding its error to `errors` under each of its keys. Keys that already
+    /// have an error keep it, since problems with a single input should be fixed first.
+    pub fn run_into(&self, data: &HashMap<String, ValueType>, errors: &mut FieldErrors) {
+        if let Err(message) = (self.check)(data) {
+            for key in self.keys {
+                errors
+                    .entry(key.to_string())
+                    .or_insert_with(|| message.clone());
+            }
+        }
+    }
+}
+
+/// Gets a number from a form's data, if the value under `key` is filled out with one.
+pub fn number_value(data: &HashMap<String, ValueType>, key: &str) -> Option<f64> {
+    match data.get(key)? {
+        ValueType::Number(Some(text)) | ValueType::String(Some(text)) => text.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+/// Returns true if the value under `key` in a form's data is filled out.
+pub fn has_value(data: &HashMap<String, ValueType>, key: &str) -> bool {
+    data.get(key).is_some_and(|value| !is_blank(value))
+}
+
+/// A group of inputs within a [`FormDefinition`], shown under its own title.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct FormSection {
+    /// The title of the section. Sections without a title are shown without a header.
+    pub title: String,
+    /// A short description of the section.
+    pub description: String,
+    /// The inputs of the section, in the order that they're shown.
+    pub fields: Vec<InputType>,
+}
+
+impl FormSection {
+    /// Creates a new [`FormSection`].
+    pub fn new(
+        title: impl Into<String>,
+        description: impl Into<String>,
+        fields: Vec<InputType>,
+    ) -> Self {
+        Self {
+            title: title.into(),
+            description: description.into(),
+            fields,
+        }
+    }
+}
+
+impl FormDefinition {
+    /// Creates a new [`FormDefinition`] without any sections. The tab text and path default to
+    /// the title and ID.
+    pub fn new(
+        id: impl Into<String>,
+        title: impl Into<String>,
+        description: impl Into<String>,
+    ) -> Self {
+        let id = id.into();
+        let title = title.into();
+
+        Self {
+            tab_text: title.clone(),
+            path: id.clone(),
+            id,
+            title,
+            description: description.into(),
+            sections: Vec::new(),
+            version: 0,
+            retired: Vec::new(),
+        }
+    }
+
+    /// Sets the text and route of the form's tab.
+    pub fn tab(mut self, text: impl Into<String>, path: impl Into<String>) -> Self {
+        self.tab_text = text.into();
+        self.path = path.into();
+        self
+    }
+
+    /// Adds a section to the end of the form.
+    pub fn section(mut self, section: FormSection) -> Self {
+        self.sections.push(section);
+        self
+    }
+
+    /// Gets every input of the form, in order.
+    pub fn fields(&self) -> impl Iterator<Item = &InputType> {
+        self.sections.iter().flat_map(|section| &section.fields)
+    }
+
+    /// Gets the input that edits the given key.
+    pub fn field(&self, key: &str) -> Option<&InputType> {
+        self.fields().find(|field| field.key() == key)
+    }
+
+    /// Gets the path of every value that the form stores, including the inputs inside each
+    /// `MultiEntry`, such as `sports_participation.sport_name`.
+    pub fn field_paths(&self) -> Vec<String> {
+        let mut paths = Vec::new();
+        collect_paths(self.fields(), "", &mut paths);
+        paths
+    }
+
+    /// Gets the paths that `previous` stores but this form doesn't. The paths inside a removed
+    /// `MultiEntry` are left out, since their values move or stay along with it.
+    pub fn removed_paths(&self, previous: &FormDefinition) -> Vec<String> {
+        let paths = self.field_paths();
+        let removed = previous
+            .field_paths()
+            .into_iter()
+            .filter(|path| !paths.contains(path))
+            .collect::<Vec<String>>();
+
+        removed
+            .iter()
+            .filter(|path| {
+                !removed
+                    .iter()
+                    .any(|parent| path.starts_with(&format!("{parent}.")))
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Validates a complete submission. Every field must have the correct type, required fields
+    /// must be filled out, and keys that aren't part of this form are rejected. Fields that are
+    /// hidden by their conditions are skipped. The form's [`FormCheck`]s are run as well.
+    pub fn validate(&self, data: &HashMap<String, ValueType>) -> SubmissionResult {
+        let mut errors = FieldErrors::new();
+
+        for field in self.fields() {
+            field.validate_into(field.key(), data, &mut errors);
+        }
+        for check in student_form_checks(&self.id) {
+            check.run_into(data, &mut errors);
+        }
+        for key in data
+            .keys()
+            .filter(|key| self.field(key).is_none() && !self.retired.contains(key))
+        {
+            errors.insert(
+                key.clone(),
+                "This field isn't part of the form.".to_string(),
+            );
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Checks that the form can be shown and stored. The form's path must be routable, every
+    /// input needs a key that can be used as a path segment, keys can't repeat, choice inputs
+    /// need at least one option, and the inputs used by the form's [`FormCheck`]s must exist.
+    pub fn check_structure(&self) -> Result<(), String> {
+        if self.id.trim().is_empty() {
+            return Err("The form needs an ID.".to_string());
+        }
+        check_path(&self.path)?;
+        if self
+            .sections
+            .iter()
+            .all(|section| section.fields.is_empty())
+        {
+            return Err("The form needs at least one question.".to_string());
+        }
+
+        check_fields(self.fields(), "")?;
+
+        match student_form_checks(&self.id)
+            .iter()
+            .flat_map(FormCheck::inputs)
+            .find(|key| self.field(key).is_none())
+        {
+            Some(key) => Err(format!("{key:?} is needed by one of the form's checks.")),
+            None => Ok(()),
+        }
+    }
+
+    /// Creates the next published version of the form, replacing `previous`. Every path of
+    /// `previous` that isn't part of this form, including the inputs inside a `MultiEntry`, must
+    /// be covered by exactly one of the `migrations`. Retired keys are carried forward, unless a
+    /// field uses the key again.
+    pub fn publish_over(
+        &self,
+        previous: &FormDefinition,
+        migrations: &[FieldMigration],
+    ) -> Result<FormDefinition, String> {
+        if !self.id.eq_ignore_ascii_case(&previous.id) {
+            return Err(format!(
+                "{:?} can't be published over {:?}.",
+                self.id, previous.id
+            ));
+        }
+        self.check_structure()?;
+
+        let paths = self.field_paths();
+        let previous_paths = previous.field_paths();
+        let removed = self.removed_paths(previous);
+        let mut covered = HashSet::new();
+        let mut targets = HashSet::new();
+
+        for migration in migrations {
+            let key = migration.removed_key();
+            if !removed.iter().any(|path| path == key) {
+                return Err(format!("{key:?} isn't removed by this version."));
+            }
+            if !covered.insert(key) {
+                return Err(format!("{key:?} has more than one migration."));
+            }
+
+            if let FieldMigration::Rename { from, to } = migration {
+                if !paths.contains(to) {
+                    return Err(format!("{to:?} isn't a field of this version."));
+                }
+                if parent_path(from) != parent_path(to) {
+                    return Err(format!(
+                        "{from:?} can only be renamed to a question in the same list."
+                    ));
+                }
+                if previous_paths.contains(to) || previous.retired.contains(to) {
+                    return Err(format!("{to:?} already has stored values."));
+                }
+                if !targets.insert(to) {
+                    return Err(format!("More than one field is renamed to {to:?}."));
+                }
+            }
+        }
+
+        let mut missing = removed
+            .iter()
+            .filter(|path| !covered.contains(path.as_str()))
+            .collect::<Vec<_>>();
+        if !missing.is_empty() {
+            missing.sort();
+            return Err(format!(
+                "{missing:?} would be removed without a migration. Rename or retire them so that \
+                stored answers aren't lost."
+            ));
+        }
+
+        let mut next = self.clone();
+        next.version = previous.version + 1;
+        next.retired = previous
+            .retired
+            .iter()
+            .map(String::as_str)
+            .chain(migrations.iter().filter_map(|migration| match migration {
+                FieldMigration::Retire { key } => Some(key.as_str()),
+                FieldMigration::Rename { .. } => None,
+            }))
+            .filter(|key| !paths.iter().any(|path| path == key))
+            .map(str::to_string)
+            .collect();
+        next.retired.sort();
+        next.retired.dedup();
+
+        Ok(next)
+    }
+}
+
+/// Gets the parent of a stored value's path, such as `sports_participation` for
+/// `sports_participation.sport_name`. Top-level fields have an empty parent.
+pub fn parent_path(path: &str) -> &str {
+    path.rsplit_once('.').map_or("", |(parent, _)| parent)
+}
+
+/// Renames the start of a stored value's path, such as `sports.name` to `sports.sport_name`,
+/// along with the paths inside it. Returns `None` if the path isn't `from` or inside it.
+pub fn rename_path(path: &str, from: &str, to: &str) -> Option<String> {
+    if path == from {
+        return Some(to.to_string());
+    }
+
+    path.strip_prefix(from)?
+        .strip_prefix('.')
+        .map(|rest| format!("{to}.{rest}"))
+}
+
+/// Adds the path of each input to `paths`, followed by the paths inside it if it's a
+/// `MultiEntry`.
+fn collect_paths<'a>(
+    fields: impl Iterator<Item = &'a InputType>,
+    prefix: &str,
+    paths: &mut Vec<String>,
+) {
+    for field in fields {
+        let path = format!("{prefix}{}", field.key());
+        paths.push(path.clone());
+        if let InputType::MultiEntry(_, _, schema, ..) = field.input() {
+            collect_paths(schema.iter(), &format!("{path}."), paths);
+        }
+    }
+}
+
+/// The paths of the student pages that aren't forms, which forms can't use.
+const RESERVED_PATHS: [&str; 5] = [
+    "home",
+    "eligibility",
+    "callback",
+    "additional",
+    "additional/financials",
+];
+
+/// Checks that a form's path can be routed to. Forms are shown either at the top level, such as
+/// `demographics`, or under the "Additional Info" tab, such as `additional/athletics`.
+fn check_path(path: &str) -> Result<(), String> {
+    let segments = path.split('/').collect::<Vec<&str>>();
+    let valid_segment = |segment: &&str| {
+        !segment.is_empty()
+            && segment
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+    };
+
+    let routable = matches!(segments.as_slice(), [_] | ["additional", _]);
+    if !routable || !segments.iter().all(valid_segment) {
+        return Err(format!(
+            "{path:?} isn't a valid path. Use lowercase letters, numbers and dashes, optionally after \"additional/\"."
+        ));
+    }
+    if RESERVED_PATHS.contains(&path) {
+        return Err(format!("{path:?} is already used by another page."));
+    }
+
+    Ok(())
+}
+
+/// Checks the keys and options of a list of inputs, including the schemas of `MultiEntry`
+/// inputs. `parent` is used to name nested inputs in errors.
+fn check_fields<'a>(
+    fields: impl Iterator<Item = &'a InputType>,
+    parent: &str,
+) -> Result<(), String> {
+    let mut keys = HashSet::new();
+
+    for field in fields {
+        let key = field.key();
+        let name = format!("{parent}{key}");
+
+        if key.trim().is_empty() {
+            return Err(format!("Every question in {parent:?} needs a key."));
+        }
+        if key.contains(['.', '[', ']', '#']) || key == "HK" || key == "SK" {
+            return Err(format!("{name:?} can't be used as a key."));
+        }
+        if !keys.insert(key) {
+            return Err(format!("{name:?} is used by more than one question."));
+        }
+        if field.options().is_some_and(|options| options.is_empty()) {
+            return Err(format!("{name:?} needs at least one option."));
+        }
+        if let InputType::Email(_, _, _, domains, _) = field.input()
+            && domains.iter().all(|domain| domain.trim().is_empty())
+        {
+            return Err(format!("{name:?} needs at least one email domain."));
+        }
+        if let InputType::Conditional(_, conditions) = field {
+            conditions
+                .check()
+                .map_err(|err| format!("{name:?} has an invalid condition:\n{err}"))?;
+        }
+        if let InputType::MultiEntry(_, _, schema, limits, _) = field.input() {
+            if schema.is_empty() {
+                return Err(format!("{name:?} needs at least one question per entry."));
+            }
+            if limits.max.is_some_and(|max| max == 0 || max < limits.min) {
+                return Err(format!("{name:?} allows fewer entries than it needs."));
+            }
+            check_fields(schema.iter(), &format!("{name}."))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns true if a value should be treated as if it wasn't filled out.
+fn is_blank(value: &ValueType) -> bool {
+    match value {
+        ValueType::String(Some(text)) | ValueType::Number(Some(text)) => text.trim().is_empty(),
+        ValueType::List(Some(list)) => list.is_empty(),
+        ValueType::Map(Some(map)) => map.is_empty(),
+        value => value.is_empty(),
+    }
+}
+
+impl InputType {
+    /// Validates the value of this input in `scope`, which is the form's data or the entry that
+    /// contains the input, adding any errors to `errors` under the given path. The messages match
+    /// the ones shown by the input components.
+    fn validate_into(
+        &self,
+        path: &str,
+        scope: &HashMap<String, ValueType>,
+        errors: &mut FieldErrors,
+    ) {
+        if !self.is_shown(scope) {
+            return;
+        }
+
+        let value = scope.get(self.key());
+        let Some(value) = value.filter(|value| !is_blank(value)) else {
+            if self.is_required(scope) {
+                errors.insert(path.to_string(), "This field is required.".to_string());
+            } else if let InputType::MultiEntry(.., limits, _) = self.input()
+                && let Err(message) = limits.check(0)
+            {
+                errors.insert(path.to_string(), message);
+            }
+            return;
+        };
+
+        let options = self.options().unwrap_or_default();
+        let result = match (self.input(), value) {
+            (InputType::Text(..), ValueType::String(_)) => Ok(()),
+            (
+                InputType::Number(..),
+                ValueType::Number(Some(text)) | ValueType::String(Some(text)),
+            ) => text
+                .trim()
+                .parse::<f64>()
+                .map(|_| ())
+                .map_err(|_| "Value is not a valid number.".to_string()),
+            (InputType::Radio(..) | InputType::Select(..), ValueType::String(Some(text))) => {
+                if options.contains(text) {
+                    Ok(())
+                } else {
+                    Err(format!("{text:?} isn't one of the options."))
+                }
+            }
+            (InputType::Checkbox(..), ValueType::List(Some(list))) => {
+                list.iter().try_for_each(|item| match item {
+                    ValueType::String(Some(text)) if options.contains(text) => Ok(()),
+                    ValueType::String(Some(text)) => {
+                        Err(format!("{text:?} isn't one of the options."))
+                    }
+                    _ => Err("Every selection must be text.".to_string()),
+                })
+            }
+            (InputType::MultiEntry(_, _, schema, limits, _), ValueType::List(Some(entries))) => {
+                for (i, entry) in entries.iter().enumerate() {
+                    let entry_path = format!("{path}[{i}]");
+                    let ValueType::Map(Some(entry)) = entry else {
+                        errors.insert(entry_path, "Every entry must be a map.".to_string());
+                        continue;
+                    };
+
+                    for field in schema {
+                        let field_path = format!("{entry_path}.{}", field.key());
+                        field.validate_into(&field_path, entry, errors);
+                    }
+                }
+                limits.check(entries.len())
+            }
+            (InputType::Date(..), ValueType::String(Some(text))) => parse_date(text).map(|_| ()),
+            (InputType::TextArea(.., limits, _), ValueType::String(Some(text))) => {
+                limits.check(text)
+            }
+            (InputType::Email(.., domains, _), ValueType::String(Some(text))) => {
+                check_email(text, domains)
+            }
+            (InputType::Phone(..), ValueType::String(Some(text))) => format_phone(text).map(|_| ()),
+            (
+                InputType::Currency(..),
+                ValueType::Number(Some(text)) | ValueType::String(Some(text)),
+            ) => format_currency(text).map(|_| ()),
+            (InputType::Text(..) | InputType::TextArea(..), _) => {
+                Err("Value must be text.".to_string())
+            }
+            (InputType::Date(..), _) => Err("Value is not a valid date.".to_string()),
+            (InputType::Email(..), _) => Err("Invalid email address.".to_string()),
+            (InputType::Phone(..), _) => Err("Value must be a phone number.".to_string()),
+            (InputType::Currency(..), _) => Err("Value is not a valid dollar amount.".to_string()),
+            (InputType::Number(..), _) => Err("Value is not a valid number.".to_string()),
+            (InputType::Radio(..) | InputType::Select(..), _) => {
+                Err("Value must be one of the options.".to_string())
+            }
+            (InputType::Checkbox(..), _) => Err("Value must be a list of options.".to_string()),
+            (InputType::MultiEntry(..), _) => Err("Value must be a list of entries.".to_string()),
+            (InputType::Conditional(..), _) => unreachable!("input() removes conditions"),
+        };
+
+        if let Err(message) = result {
+            errors.insert(path.to_string(), message);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::comparison::test_utils::text;
+    use crate::common::{EntryLimits, TextLimits};
+    use crate::input;
+
+    fn create_definition() -> FormDefinition {
+        FormDefinition::new("athletics", "Athletics", "").section(FormSection::new(
+            "",
+            "",
+            vec![
+                input!(Number, "gpa", "GPA:", true, "4.0"),
+                input!(Text, "honors", "Honors:", false, ""),
+                input!(Select, "town", "Town:", true, ["Southbury", "Middlebury"]),
+                InputType::MultiEntry(
+                    "sports".to_string(),
+                    "Sports:".to_string(),
+                    vec![
+                        input!(Text, "sport_name", "Sport Name:", true, ""),
+                        input!(Checkbox, "grades", "Grades:", false, ["9th", "10th"]),
+                    ],
+                    EntryLimits::default(),
+                    false,
+                ),
+            ],
+        ))
+    }
+
+    #[test]
+    fn accepts_valid_submissions() {
+        let data = HashMap::from([
+            (
+                "gpa".to_string(),
+                ValueType::Number(Some("3.8".to_string())),
+            ),
+            ("honors".to_string(), text("")),
+            ("town".to_string(), text("Southbury")),
+            (
+                "sports".to_string(),
+                ValueType::List(Some(vec![ValueType::Map(Some(HashMap::from([
+                    ("uuid".to_string(), text("1234")),
+                    ("sport_name".to_string(), text("Golf")),
+                    (
+                        "grades".to_string(),
+                        ValueType::List(Some(vec![text("9th")])),
+                    ),
+                ])))])),
+            ),
+        ]);
+
+        assert_eq!(create_definition().validate(&data), Ok(()));
+    }
+
+    #[test]
+    fn reports_field_errors() {
+        let data = HashMap::from([
+            (
+                "gpa".to_string(),
+                ValueType::Number(Some("high".to_string())),
+            ),
+            ("town".to_string(), text("Oxford")),
+            ("first_name".to_string(), text("Jane")),
+            (
+                "sports".to_string(),
+                ValueType::List(Some(vec![ValueType::Map(Some(HashMap::from([(
+                    "grades".to_string(),
+                    ValueType::List(Some(vec![text("13th")])),
+                )])))])),
+            ),
+        ]);
+
+        let errors = create_definition().validate(&data).unwrap_err();
+        assert_eq!(errors.len(), 5);
+        assert_eq!(errors["gpa"], "Value is not a valid number.");
+        assert!(errors["town"].contains("isn't one of the options"));
+        assert!(errors.contains_key("first_name"));
+        assert_eq!(errors["sports[0].sport_name"], "This field is required.");
+        assert!(errors.contains_key("sports[0].grades"));
+
+        let errors = create_definition().validate(&HashMap::new()).unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert!(errors.contains_key("gpa") && errors.contains_key("town"));
+    }
+
+    #[test]
+    fn skips_hidden_fields() {
+        let definition = FormDefinition::new("family", "Family", "").section(FormSection::new(
+            "",
+            "",
+            vec![
+                input!(
+                    Number,
+                    "num_children_college",
+                    "Children in college:",
+                    true,
+                    "0"
+                ),
+                input!(Text, "college_names", "Colleges:", false, "")
+                    .required_when("num_children_college > 0"),
+                input!(
+                    Radio,
+                    "has_second_parent",
+                    "Second parent?",
+                    true,
+                    ["Yes", "No"]
+                ),
+                input!(Text, "parent_two_name", "Parent 2 Name:", true, "")
+                    .shown_when(r#"has_second_parent == "Yes""#),
+            ],
+        ));
+        let number = |value: &str| ValueType::Number(Some(value.to_string()));
+
+        let data = HashMap::from([
+            ("num_children_college".to_string(), number("0")),
+            ("has_second_parent".to_string(), text("No")),
+        ]);
+        assert_eq!(definition.validate(&data), Ok(()));
+
+        let data = HashMap::from([
+            ("num_children_college".to_string(), number("2")),
+            ("has_second_parent".to_string(), text("Yes")),
+        ]);
+        let errors = definition.validate(&data).unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert!(errors.contains_key("college_names") && errors.contains_key("parent_two_name"));
+
+        // Stored values of hidden fields are kept, but aren't checked.
+        let data = HashMap::from([
+            ("num_children_college".to_string(), number("0")),
+            ("has_second_parent".to_string(), text("No")),
+            ("parent_two_name".to_string(), ValueType::Bool(Some(true))),
+        ]);
+        assert_eq!(definition.validate(&data), Ok(()));
+
+        let mut invalid = definition.clone();
+        invalid.sections[0]
+            .fields
+            .push(input!(Text, "notes", "Notes:", false, "").shown_when("gpa >="));
+        assert!(
+            invalid
+                .check_structure()
+                .unwrap_err()
+                .contains("invalid condition")
+        );
+    }
+
+    #[test]
+    fn validates_formatted_inputs() {
+        let definition = FormDefinition::new("profile", "Profile", "").section(FormSection::new(
+            "",
+            "",
+            vec![
+                input!(Date, "dob", "Date of Birth:", true),
+                input!(TextArea, "essay", "Essay:", true, "", TextLimits::words(3)),
+                input!(Email, "email", "Email:", true, "", ["region15.org"]),
+                input!(Phone, "phone", "Phone:", false, ""),
+                input!(Currency, "income", "Income:", false, ""),
+                input!(
+                    MultiEntry,
+                    "jobs",
+                    "Jobs:",
+                    false,
+                    [
+                        input!(Text, "title", "Title:", true, ""),
+                        input!(
+                            MultiEntry,
+                            "shifts",
+                            "Shifts:",
+                            false,
+                            [input!(Date, "day", "Day:", true),]
+                        ),
+                    ]
+                ),
+            ],
+        ));
+        assert_eq!(definition.check_structure(), Ok(()));
+
+        let data = HashMap::from([
+            ("dob".to_string(), text("2008-05-14")),
+            ("essay".to_string(), text("One two three")),
+            ("email".to_string(), text("me@region15.org")),
+            ("phone".to_string(), text("(203) 555-0134")),
+            (
+                "income".to_string(),
+                ValueType::Number(Some("1250.50".to_string())),
+            ),
+        ]);
+        assert_eq!(definition.validate(&data), Ok(()));
+
+        let shift = ValueType::Map(Some(HashMap::from([("day".to_string(), text("May 1"))])));
+        let job = HashMap::from([
+            ("title".to_string(), text("Cashier")),
+            ("shifts".to_string(), ValueType::List(Some(vec![shift]))),
+        ]);
+        let data = HashMap::from([
+            ("dob".to_string(), text("05/14/2008")),
+            ("essay".to_string(), text("One two three four")),
+            ("email".to_string(), text("me@example.com")),
+            ("phone".to_string(), text("555-0134")),
+            ("income".to_string(), text("-5")),
+            (
+                "jobs".to_string(),
+                ValueType::List(Some(vec![ValueType::Map(Some(job))])),
+            ),
+        ]);
+        let errors = definition.validate(&data).unwrap_err();
+        assert_eq!(errors.len(), 6);
+        assert!(errors.contains_key("jobs[0].shifts[0].day"));
+    }
+
+    #[test]
+    fn checks_entry_counts() {
+        let jobs = |limits: EntryLimits| {
+            FormDefinition::new("jobs", "Jobs", "").section(FormSection::new(
+                "",
+                "",
+                vec![input!(MultiEntry, "jobs", "Jobs:", false, [
+                    input!(Text, "job_title", "Job Title:", true, ""),
+                ], limits)],
+            ))
+        };
+        let entries = |count: usize| {
+            let job = HashMap::from([("job_title".to_string(), text("Cashier"))]);
+            HashMap::from([(
+                "jobs".to_string(),
+                ValueType::List(Some(vec![ValueType::Map(Some(job)); count])),
+            )])
+        };
+        let definition = jobs(EntryLimits::between(1, 2));
+
+        assert_eq!(definition.validate(&entries(2)), Ok(()));
+        assert_eq!(
+            definition.validate(&HashMap::new()).unwrap_err()["jobs"],
+            "Add at least one entry."
+        );
+        assert_eq!(
+            definition.validate(&entries(3)).unwrap_err()["jobs"],
+            "Remove entries until there are at most 2."
+        );
+        assert_eq!(jobs(EntryLimits::default()).validate(&HashMap::new()), Ok(()));
+
+        assert_eq!(definition.check_structure(), Ok(()));
+        assert!(jobs(EntryLimits::between(3, 2)).check_structure().is_err());
+    }
+
+    #[test]
+    fn checks_structure() {
+        assert_eq!(create_definition().check_structure(), Ok(()));
+
+        let mut definition = create_definition();
+        definition.sections[0]
+            .fields
+            .push(input!(Text, "gpa", "GPA again:", false, ""));
+        assert!(
+            definition
+                .check_structure()
+                .unwrap_err()
+                .contains("more than one")
+        );
+
+        let mut definition = create_definition();
+        definition.sections[0].fields.push(InputType::MultiEntry(
+            "jobs".to_string(),
+            "Jobs:".to_string(),
+            vec![input!(Select, "job.title", "Title:", true, ["Cashier"])],
+            EntryLimits::default(),
+            false,
+        ));
+        assert!(
+            definition
+                .check_structure()
+                .unwrap_err()
+                .contains("jobs.job.title")
+        );
+
+        let mut definition = create_definition();
+        definition.sections[0]
+            .fields
+            .push(input!(Radio, "attend_bas", "Attended BAS?", true, []));
+        assert!(definition.check_structure().unwrap_err().contains("option"));
+
+        for path in ["additional/university", "work-experience"] {
+            assert_eq!(create_definition().tab("", path).check_structure(), Ok(()));
+        }
+        for path in ["", "Athletics", "other/athletics", "additional/", "home", "additional/financials"] {
+            assert!(create_definition().tab("", path).check_structure().is_err());
+        }
+    }
+
+    #[test]
+    fn publishes_with_migrations() {
+        let previous = create_definition();
+        let mut next = create_definition();
+        next.sections[0]
+            .fields
+            .retain(|field| field.key() != "honors");
+        next.sections[0].fields[0] = input!(Number, "unweighted_gpa", "GPA:", true, "4.0");
+
+        let err = next.publish_over(&previous, &[]).unwrap_err();
+        assert!(err.contains("\"gpa\"") && err.contains("\"honors\""));
+
+        let migrations = [
+            FieldMigration::Rename {
+                from: "gpa".to_string(),
+                to: "unweighted_gpa".to_string(),
+            },
+            FieldMigration::Retire {
+                key: "honors".to_string(),
+            },
+        ];
+        let published = next.publish_over(&previous, &migrations).unwrap();
+        assert_eq!(published.version, 1);
+        assert_eq!(published.retired, vec!["honors".to_string()]);
+
+        let data = HashMap::from([
+            (
+                "unweighted_gpa".to_string(),
+                ValueType::Number(Some("3.8".to_string())),
+            ),
+            ("honors".to_string(), text("High Honors")),
+            ("town".to_string(), text("Southbury")),
+        ]);
+        assert_eq!(published.validate(&data), Ok(()));
+
+        let clashing = [FieldMigration::Rename {
+            from: "gpa".to_string(),
+            to: "town".to_string(),
+        }];
+        assert!(next.publish_over(&previous, &clashing).is_err());
+
+        let unrelated = [FieldMigration::Retire {
+            key: "town".to_string(),
+        }];
+        assert!(next.publish_over(&previous, &unrelated).is_err());
+
+        // Using a retired key again brings its stored values back.
+        let mut restored = create_definition();
+        restored.sections[0].fields.remove(0);
+        let restored = restored
+            .publish_over(
+                &published,
+                &[FieldMigration::Retire {
+                    key: "unweighted_gpa".to_string(),
+                }],
+            )
+            .unwrap();
+        assert_eq!(restored.version, 2);
+        assert_eq!(restored.retired, vec!["unweighted_gpa".to_string()]);
+    }
+
+    #[test]
+    fn publishes_nested_migrations() {
+        let previous = create_definition();
+        let mut next = create_definition();
+        let InputType::MultiEntry(_, _, schema, ..) = &mut next.sections[0].fields[3] else {
+            unreachable!();
+        };
+        schema[0] = input!(Text, "sport", "Sport:", true, "");
+
+        assert_eq!(next.removed_paths(&previous), vec!["sports.sport_name".to_string()]);
+        assert!(next.publish_over(&previous, &[]).is_err());
+
+        let rename = |to: &str| FieldMigration::Rename {
+            from: "sports.sport_name".to_string(),
+            to: to.to_string(),
+        };
+        assert!(next.publish_over(&previous, &[rename("sports.sport")]).is_ok());
+        assert!(next.publish_over(&previous, &[rename("honors")]).is_err());
+
+        // The inputs of a removed list don't need their own migrations.
+        let mut without_sports = create_definition();
+        without_sports.sections[0].fields.remove(3);
+        assert_eq!(without_sports.removed_paths(&previous), vec!["sports".to_string()]);
+    }
+
+    #[test]
+    fn renames_paths() {
+        assert_eq!(rename_path("sports", "sports", "athletics"), Some("athletics".to_string()));
+        assert_eq!(
+            rename_path("sports.sport_name", "sports", "athletics"),
+            Some("athletics.sport_name".to_string())
+        );
+        assert_eq!(rename_path("sports_participation", "sports", "athletics"), None);
+        assert_eq!(parent_path("sports.sport_name"), "sports");
+        assert_eq!(parent_path("gpa"), "");
+    }
+}
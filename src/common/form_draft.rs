@@ -0,0 +1,92 @@
+use crate::common::ValueType;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// # Form Draft
+///
+/// Unsubmitted changes to one of a student's forms, which are autosaved so that they aren't lost
+/// when a session expires or a tab is closed. Drafts are kept in the browser's local storage and
+/// optionally on the server.
+///
+/// The draft remembers the saved data that it was started from, so that a draft is only offered
+/// if the form hasn't been submitted since. Otherwise, restoring it would undo the newer
+/// submission.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct FormDraft {
+    /// The unsaved data.
+    pub data: HashMap<String, ValueType>,
+    /// The saved data that the changes were made to.
+    pub base: HashMap<String, ValueType>,
+    /// When the draft was saved, in milliseconds since the Unix epoch.
+    pub saved_at: i64,
+}
+
+impl FormDraft {
+    /// Checks whether the draft can be restored over the saved data. The draft must have been
+    /// started from the saved data and must actually change it.
+    pub fn is_restorable(&self, saved: &HashMap<String, ValueType>) -> bool {
+        self.base == *saved && self.data != *saved
+    }
+
+    /// Picks the newest of the drafts that can be restored over the saved data.
+    pub fn newest_restorable(
+        drafts: impl IntoIterator<Item = Option<FormDraft>>,
+        saved: &HashMap<String, ValueType>,
+    ) -> Option<FormDraft> {
+        drafts
+            .into_iter()
+            .flatten()
+            .filter(|draft| draft.is_restorable(saved))
+            .max_by_key(|draft| draft.saved_at)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn map(name: &str) -> HashMap<String, ValueType> {
+        HashMap::from([(
+            "first_name".to_string(),
+            ValueType::String(Some(name.to_string())),
+        )])
+    }
+
+    fn draft(data: &str, base: &str, saved_at: i64) -> FormDraft {
+        FormDraft {
+            data: map(data),
+            base: map(base),
+            saved_at,
+        }
+    }
+
+    #[test]
+    fn picks_newest_restorable_draft() {
+        let saved = map("Ada");
+
+        assert_eq!(
+            FormDraft::newest_restorable(
+                [
+                    Some(draft("Grace", "Ada", 1)),
+                    Some(draft("Alan", "Ada", 2))
+                ],
+                &saved
+            ),
+            Some(draft("Alan", "Ada", 2))
+        );
+        // Drafts started from older data or without any changes are skipped.
+        assert_eq!(
+            FormDraft::newest_restorable(
+                [
+                    Some(draft("Grace", "Ada", 1)),
+                    Some(draft("Alan", "Edsger", 3)),
+                    Some(draft("Ada", "Ada", 4)),
+                    None,
+                ],
+                &saved
+            ),
+            Some(draft("Grace", "Ada", 1))
+        );
+        assert_eq!(FormDraft::newest_restorable([None, None], &saved), None);
+    }
+}
@@ -1,5 +1,8 @@
-use crate::common::ValueType;
-use crate::components::{CheckboxList, OutlinedTextField, RadioList, Row, Select, TextFieldType};
+use crate::common::{EntryLimits, Rule, TextLimits, ValueType};
+use crate::components::{
+    CheckboxList, MultiEntry, OutlinedTextArea, OutlinedTextField, RadioList, Row, Select,
+    TextFieldType,
+};
 use leptos::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -23,9 +26,205 @@ pub enum InputType {
     /// Represents a dropdown input. Requires a `String` key, `String` label, and a series
     /// of `String` options. The selected value will be returned as a `String`.
     Select(String, String, Vec<String>, bool),
+    /// Represents a list of entries. Requires a `String` key, `String` label, the series of
+    /// `InputType`s that each entry contains, and the [`EntryLimits`] of the list. The entries
+    /// will be returned as a `Vec` of maps. Entries may contain their own `MultiEntry` inputs.
+    MultiEntry(String, String, Vec<InputType>, EntryLimits, bool),
+    /// Represents a date picker. Requires a `String` key and `String` label. The date will be
+    /// returned as a `String` in the `YYYY-MM-DD` format.
+    Date(String, String, bool),
+    /// Represents a multi-line text input. Requires a `String` key, `String` label, `String`
+    /// placeholder, and the [`TextLimits`] of the text. The entered value will be returned as a
+    /// single `String` value.
+    TextArea(String, String, String, TextLimits, bool),
+    /// Represents an email input. Requires a `String` key, `String` label, `String` placeholder,
+    /// and the email domains that are allowed, or `*` for any domain. The entered value will be
+    /// returned as a `String`.
+    Email(String, String, String, Vec<String>, bool),
+    /// Represents a US phone number input. Requires a `String` key, `String` label, and `String`
+    /// placeholder. The entered value will be returned as a `String` formatted as `000-000-0000`.
+    Phone(String, String, String, bool),
+    /// Represents a dollar amount input. Requires a `String` key, `String` label, and `String`
+    /// placeholder. The entered value will be returned as a number with two decimal places.
+    Currency(String, String, String, bool),
+    /// Wraps another input that is only shown, or only required, depending on the rest of the
+    /// form's data. Created using [`InputType::shown_when`] and [`InputType::required_when`].
+    Conditional(Box<InputType>, InputConditions),
+}
+
+/// # Input Conditions
+///
+/// The conditions of an [`InputType::Conditional`] input. Each condition is a [`Rule`], such as
+/// `num_children_college > 0`, which is checked against the data of the form that contains the
+/// input, or against the entry for inputs inside a `MultiEntry`. Empty conditions always pass.
+///
+/// The rules are stored as text so that they can be edited in the form builder. Rules that can't
+/// be parsed are ignored, and [`FormDefinition::check_structure`](super::FormDefinition) rejects
+/// them before a form is published.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct InputConditions {
+    /// The input is only shown while this rule passes. Hidden inputs aren't validated, so they're
+    /// never required.
+    #[serde(default)]
+    pub shown_when: String,
+    /// The input is only required while this rule passes, instead of using its own `required`
+    /// flag.
+    #[serde(default)]
+    pub required_when: String,
+}
+
+impl InputConditions {
+    /// Parses a condition, returning `None` if it's empty or invalid.
+    fn parse(rule: &str) -> Option<Rule> {
+        if rule.trim().is_empty() {
+            None
+        } else {
+            Rule::parse(rule).ok()
+        }
+    }
+
+    /// Parses both conditions, returning the first error.
+    pub fn check(&self) -> Result<(), String> {
+        for rule in [&self.shown_when, &self.required_when] {
+            if !rule.trim().is_empty() {
+                Rule::parse(rule).map_err(|err| err.render(rule))?;
+            }
+        }
+
+        Ok(())
+    }
 }
 
 impl InputType {
+    /// Gets the key of the data member that this input edits.
+    pub fn key(&self) -> &str {
+        match self {
+            InputType::Text(key, ..)
+            | InputType::Number(key, ..)
+            | InputType::Radio(key, ..)
+            | InputType::Checkbox(key, ..)
+            | InputType::Select(key, ..)
+            | InputType::MultiEntry(key, ..)
+            | InputType::Date(key, ..)
+            | InputType::TextArea(key, ..)
+            | InputType::Email(key, ..)
+            | InputType::Phone(key, ..)
+            | InputType::Currency(key, ..) => key,
+            InputType::Conditional(input, _) => input.key(),
+        }
+    }
+
+    /// Gets the label that is shown with the input.
+    pub fn label(&self) -> &str {
+        match self {
+            InputType::Text(_, label, ..)
+            | InputType::Number(_, label, ..)
+            | InputType::Radio(_, label, ..)
+            | InputType::Checkbox(_, label, ..)
+            | InputType::Select(_, label, ..)
+            | InputType::MultiEntry(_, label, ..)
+            | InputType::Date(_, label, ..)
+            | InputType::TextArea(_, label, ..)
+            | InputType::Email(_, label, ..)
+            | InputType::Phone(_, label, ..)
+            | InputType::Currency(_, label, ..) => label,
+            InputType::Conditional(input, _) => input.label(),
+        }
+    }
+
+    /// Returns true if this input must be filled out. Conditional inputs return their own flag,
+    /// since their conditions depend on the data; use [`InputType::is_required`] to check them.
+    pub fn required(&self) -> bool {
+        match self {
+            InputType::Text(.., required)
+            | InputType::Number(.., required)
+            | InputType::Radio(.., required)
+            | InputType::Checkbox(.., required)
+            | InputType::Select(.., required)
+            | InputType::MultiEntry(.., required)
+            | InputType::Date(.., required)
+            | InputType::TextArea(.., required)
+            | InputType::Email(.., required)
+            | InputType::Phone(.., required)
+            | InputType::Currency(.., required) => *required,
+            InputType::Conditional(input, _) => input.required(),
+        }
+    }
+
+    /// Gets the options that can be chosen, if this input has a fixed list of options.
+    pub fn options(&self) -> Option<&[String]> {
+        match self {
+            InputType::Radio(_, _, options, _)
+            | InputType::Checkbox(_, _, options, _)
+            | InputType::Select(_, _, options, _) => Some(options),
+            InputType::Conditional(input, _) => input.options(),
+            _ => None,
+        }
+    }
+
+    /// Gets the input without any conditions.
+    pub fn input(&self) -> &InputType {
+        match self {
+            InputType::Conditional(input, _) => input.input(),
+            input => input,
+        }
+    }
+
+    /// Returns true if this input is shown for the given data.
+    pub fn is_shown(&self, data: &HashMap<String, ValueType>) -> bool {
+        match self {
+            InputType::Conditional(input, conditions) => {
+                InputConditions::parse(&conditions.shown_when)
+                    .is_none_or(|rule| rule.evaluate(data))
+                    && input.is_shown(data)
+            }
+            _ => true,
+        }
+    }
+
+    /// Returns true if this input must be filled out for the given data. Hidden inputs are never
+    /// required.
+    pub fn is_required(&self, data: &HashMap<String, ValueType>) -> bool {
+        if !self.is_shown(data) {
+            return false;
+        }
+
+        match self {
+            InputType::Conditional(input, conditions) => {
+                InputConditions::parse(&conditions.required_when)
+                    .map(|rule| rule.evaluate(data))
+                    .unwrap_or_else(|| input.is_required(data))
+            }
+            input => input.required(),
+        }
+    }
+
+    /// Only shows this input while a [`Rule`] passes, such as `has_second_parent == "Yes"`.
+    ///
+    /// Example usage:
+    /// ```ignore
+    /// input!(Text, "parent_two_name", "Parent/Guardian 2 Name:", true, "John Smith")
+    ///     .shown_when(r#"has_second_parent == "Yes""#)
+    /// ```
+    pub fn shown_when(self, rule: impl Into<String>) -> Self {
+        self.with_conditions(|conditions| conditions.shown_when = rule.into())
+    }
+
+    /// Only requires this input while a [`Rule`] passes, such as `num_children_college > 0`.
+    pub fn required_when(self, rule: impl Into<String>) -> Self {
+        self.with_conditions(|conditions| conditions.required_when = rule.into())
+    }
+
+    fn with_conditions(self, edit: impl FnOnce(&mut InputConditions)) -> Self {
+        let (input, mut conditions) = match self {
+            InputType::Conditional(input, conditions) => (input, conditions),
+            input => (Box::new(input), InputConditions::default()),
+        };
+        edit(&mut conditions);
+
+        InputType::Conditional(input, conditions)
+    }
+
     /// Converts an `InputType` into an `impl IntoView`. A name must be specified in the case that
     /// the component is a checkbox/radio list, which requires a unique name to function properly.
     /// Many times, the name will be the same as the data member.
@@ -34,9 +233,21 @@ impl InputType {
         data_map: RwSignal<HashMap<String, ValueType>>,
         name: String,
     ) -> impl IntoView {
+        let required = self.required();
+        self.into_view_with(data_map, name, Signal::stored(required))
+    }
+
+    /// Converts an `InputType` into a view, using the `required` signal instead of the input's
+    /// own flag.
+    fn into_view_with(
+        self,
+        data_map: RwSignal<HashMap<String, ValueType>>,
+        name: String,
+        required: Signal<bool>,
+    ) -> AnyView {
         view! {
             {match self {
-                InputType::Text(member, label, placeholder, required) => {
+                InputType::Text(member, label, placeholder, _) => {
                     view! {
                         <Row>
                             <OutlinedTextField
@@ -50,7 +261,7 @@ impl InputType {
                     }
                         .into_any()
                 }
-                InputType::Number(member, label, placeholder, required) => {
+                InputType::Number(member, label, placeholder, _) => {
                     view! {
                         <OutlinedTextField
                             label=label.clone()
@@ -63,7 +274,7 @@ impl InputType {
                     }
                         .into_any()
                 }
-                InputType::Checkbox(member, label, options, required) => {
+                InputType::Checkbox(member, label, options, _) => {
                     view! {
                         <CheckboxList
                             label=label.clone()
@@ -76,7 +287,7 @@ impl InputType {
                     }
                         .into_any()
                 }
-                InputType::Radio(member, label, options, required) => {
+                InputType::Radio(member, label, options, _) => {
                     view! {
                         <RadioList
                             label=label.clone()
@@ -88,7 +299,7 @@ impl InputType {
                     }
                         .into_any()
                 }
-                InputType::Select(member, label, options, required) => {
+                InputType::Select(member, label, options, _) => {
                     view! {
                         <Select
                             label=label.clone()
@@ -100,23 +311,190 @@ impl InputType {
                     }
                         .into_any()
                 }
+                InputType::MultiEntry(member, label, schema, limits, _) => {
+                    let min = limits.min;
+                    view! {
+                        <MultiEntry
+                            label=label.clone()
+                            data_member=member.clone()
+                            data_map=data_map
+                            schema=schema.clone()
+                            min=Signal::derive(move || min.max(usize::from(required.get())))
+                            max=limits.max
+                        />
+                    }
+                        .into_any()
+                }
+                InputType::Date(member, label, _) => {
+                    view! {
+                        <OutlinedTextField
+                            label=label.clone()
+                            data_member=member.clone()
+                            data_map=data_map
+                            input_type=TextFieldType::Date
+                            required=required
+                        />
+                    }
+                        .into_any()
+                }
+                InputType::TextArea(member, label, placeholder, limits, _) => {
+                    view! {
+                        <Row>
+                            <OutlinedTextArea
+                                label=label.clone()
+                                placeholder=placeholder.clone()
+                                data_member=member.clone()
+                                data_map=data_map
+                                limits=limits.clone()
+                                required=required
+                            />
+                        </Row>
+                    }
+                        .into_any()
+                }
+                InputType::Email(member, label, placeholder, domains, _) => {
+                    view! {
+                        <OutlinedTextField
+                            label=label.clone()
+                            placeholder=placeholder.clone()
+                            data_member=member.clone()
+                            data_map=data_map
+                            input_type=TextFieldType::Email(domains.clone())
+                            required=required
+                        />
+                    }
+                        .into_any()
+                }
+                InputType::Phone(member, label, placeholder, _) => {
+                    view! {
+                        <OutlinedTextField
+                            label=label.clone()
+                            placeholder=placeholder.clone()
+                            data_member=member.clone()
+                            data_map=data_map
+                            input_type=TextFieldType::Phone
+                            required=required
+                        />
+                    }
+                        .into_any()
+                }
+                InputType::Currency(member, label, placeholder, _) => {
+                    view! {
+                        <OutlinedTextField
+                            label=label.clone()
+                            placeholder=placeholder.clone()
+                            data_member=member.clone()
+                            data_map=data_map
+                            input_type=TextFieldType::Currency
+                            required=required
+                        />
+                    }
+                        .into_any()
+                }
+                InputType::Conditional(input, conditions) => {
+                    // Hidden inputs are unmounted, which removes them from the form's
+                    // FormValidationRegistry until they're shown again.
+                    let shown_when = InputConditions::parse(&conditions.shown_when);
+                    let shown = Signal::derive(move || {
+                        shown_when
+                            .as_ref()
+                            .is_none_or(|rule| data_map.with(|data| rule.evaluate(data)))
+                    });
+                    let required = match InputConditions::parse(&conditions.required_when) {
+                        Some(rule) => Signal::derive(move || data_map.with(|data| rule.evaluate(data))),
+                        None => required,
+                    };
+
+                    view! {
+                        <Show when=move || shown.get()>
+                            {(*input).clone().into_view_with(data_map, name.clone(), required)}
+                        </Show>
+                    }
+                        .into_any()
+                }
             }}
         }
+        .into_any()
     }
 }
 
-/// Shorthand macro for creating `InputType` enums.
+/// Shorthand macro for creating `InputType` enums. The arguments are the variant, key, label and
+/// whether the input is required, followed by the variant's other values.
 ///
 /// Example usage:
 /// ```
 /// // To create an InputType::Text
-/// let text_input = input!(Text, "member", "label", "placeholder");
+/// let text_input = input!(Text, "member", "label", true, "placeholder");
 ///
 /// // To create an InputType::Select
-/// let select_input = input!(Select, "member", "label", ["option1", "option2"]);
+/// let select_input = input!(Select, "member", "label", true, ["option1", "option2"]);
+///
+/// // To create an InputType::Date
+/// let date_input = input!(Date, "member", "label", false);
+///
+/// // To create an InputType::TextArea with a word limit
+/// let essay_input = input!(TextArea, "member", "label", true, "placeholder", TextLimits::words(250));
+///
+/// // To create an InputType::Email that accepts any domain
+/// let email_input = input!(Email, "member", "label", true, "me@example.com", ["*"]);
+///
+/// // To create an InputType::MultiEntry, whose entries contain other inputs
+/// let list_input = input!(MultiEntry, "member", "label", false, [
+///     input!(Text, "name", "Name:", true, "placeholder"),
+/// ]);
+///
+/// // To create an InputType::MultiEntry that allows at most three entries
+/// let short_list_input = input!(MultiEntry, "member", "label", false, [
+///     input!(Text, "name", "Name:", true, "placeholder"),
+/// ], EntryLimits::between(0, 3));
 /// ```
 #[macro_export]
 macro_rules! input {
+    (MultiEntry, $key:expr, $label:expr, $required:expr, [$($field:expr),* $(,)?], $limits:expr) => {
+        $crate::common::InputType::MultiEntry(
+            $key.to_string(),
+            $label.to_string(),
+            vec![$($field), *],
+            $limits,
+            $required
+        )
+    };
+
+    (MultiEntry, $key:expr, $label:expr, $required:expr, [$($field:expr),* $(,)?]) => {
+        $crate::input!(
+            MultiEntry,
+            $key,
+            $label,
+            $required,
+            [$($field), *],
+            $crate::common::EntryLimits::default()
+        )
+    };
+
+    (TextArea, $key:expr, $label:expr, $required:expr, $placeholder:expr, $limits:expr) => {
+        $crate::common::InputType::TextArea(
+            $key.to_string(),
+            $label.to_string(),
+            $placeholder.to_string(),
+            $limits,
+            $required
+        )
+    };
+
+    (Email, $key:expr, $label:expr, $required:expr, $placeholder:expr, [$($domain:expr),* $(,)?]) => {
+        $crate::common::InputType::Email(
+            $key.to_string(),
+            $label.to_string(),
+            $placeholder.to_string(),
+            vec![$($domain.to_string()), *],
+            $required
+        )
+    };
+
+    ($variant:ident, $key:expr, $label:expr, $required:expr) => {
+        $crate::common::InputType::$variant($key.to_string(), $label.to_string(), $required)
+    };
+
     ($variant:ident, $key:expr, $label:expr, $required:expr, [$($option:expr),* $(,)?]) => {
         $crate::common::InputType::$variant(
             $key.to_string(),
@@ -0,0 +1,295 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// # Text Limits
+///
+/// The limits of a `TextArea` input. Limits that are `None` aren't checked, so the default limits
+/// allow any amount of text.
+///
+/// Example usage:
+/// ```ignore
+/// input!(TextArea, "essay", "Essay:", true, "Tell us about yourself...", TextLimits::words(250))
+/// ```
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct TextLimits {
+    /// The maximum number of characters.
+    #[serde(default)]
+    pub max_chars: Option<usize>,
+    /// The maximum number of words, which are separated by whitespace.
+    #[serde(default)]
+    pub max_words: Option<usize>,
+}
+
+impl TextLimits {
+    /// Creates limits with a maximum number of characters.
+    pub fn chars(max_chars: usize) -> Self {
+        Self {
+            max_chars: Some(max_chars),
+            ..Default::default()
+        }
+    }
+
+    /// Creates limits with a maximum number of words.
+    pub fn words(max_words: usize) -> Self {
+        Self {
+            max_words: Some(max_words),
+            ..Default::default()
+        }
+    }
+
+    /// Checks that the text is within both limits.
+    pub fn check(&self, text: &str) -> Result<(), String> {
+        if let Some(max) = self.max_chars
+            && text.chars().count() > max
+        {
+            return Err(format!("Must be at most {max} characters."));
+        }
+        if let Some(max) = self.max_words
+            && count_words(text) > max
+        {
+            return Err(format!("Must be at most {max} words."));
+        }
+
+        Ok(())
+    }
+
+    /// Describes how much of each limit the text uses, such as `120/500 characters`. Empty if
+    /// there aren't any limits.
+    pub fn describe_usage(&self, text: &str) -> String {
+        let mut usage = Vec::new();
+        if let Some(max) = self.max_chars {
+            usage.push(format!("{}/{max} characters", text.chars().count()));
+        }
+        if let Some(max) = self.max_words {
+            usage.push(format!("{}/{max} words", count_words(text)));
+        }
+
+        usage.join(" · ")
+    }
+}
+
+/// # Entry Limits
+///
+/// The limits on the number of entries in a `MultiEntry` input. A required input needs at least
+/// one entry even if `min` is lower, so the default limits only apply the `required` flag.
+///
+/// Example usage:
+/// ```ignore
+/// input!(MultiEntry, "jobs", "Jobs:", false, [
+///     input!(Text, "job_title", "Job Title:", true, ""),
+/// ], EntryLimits::between(0, 3))
+/// ```
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct EntryLimits {
+    /// The fewest entries that can be saved.
+    #[serde(default)]
+    pub min: usize,
+    /// The most entries that can be saved, if there's a limit.
+    #[serde(default)]
+    pub max: Option<usize>,
+}
+
+impl EntryLimits {
+    /// Creates limits with both a minimum and a maximum number of entries.
+    pub fn between(min: usize, max: usize) -> Self {
+        Self {
+            min,
+            max: Some(max),
+        }
+    }
+
+    /// Checks that the number of entries is within both limits.
+    pub fn check(&self, count: usize) -> Result<(), String> {
+        if count < self.min {
+            return Err(match self.min {
+                1 => "Add at least one entry.".to_string(),
+                min => format!("Add at least {min} entries."),
+            });
+        }
+        if let Some(max) = self.max
+            && count > max
+        {
+            return Err(format!("Remove entries until there are at most {max}."));
+        }
+
+        Ok(())
+    }
+}
+
+/// Counts the words in some text, which are separated by whitespace.
+pub fn count_words(text: &str) -> usize {
+    text.split_whitespace().count()
+}
+
+/// Checks an email address against a list of allowed domains, which are the part of the address
+/// after the `@`. A domain of `*` allows any domain.
+pub fn check_email(input: &str, domains: &[String]) -> Result<(), String> {
+    let Some((name, domain)) = input.trim().split_once('@') else {
+        return Err("Invalid email address.".to_string());
+    };
+    if name.is_empty() || domain.is_empty() || domain.contains('@') {
+        return Err("Invalid email address.".to_string());
+    }
+
+    if domains
+        .iter()
+        .any(|allowed| allowed == "*" || allowed.eq_ignore_ascii_case(domain))
+    {
+        Ok(())
+    } else {
+        Err(format!(
+            "Email address must match one of the following: {}",
+            domains.join(", ")
+        ))
+    }
+}
+
+/// Formats a US phone number as `000-000-0000`. Spaces, dashes, dots, parentheses and a leading
+/// `+1` are accepted.
+pub fn format_phone(input: &str) -> Result<String, String> {
+    if input
+        .chars()
+        .any(|c| !c.is_ascii_digit() && !" -.()+".contains(c))
+    {
+        return Err(
+            "Phone numbers can only contain digits, spaces, dashes and parentheses.".to_string(),
+        );
+    }
+
+    let mut digits = input
+        .chars()
+        .filter(char::is_ascii_digit)
+        .collect::<String>();
+    if digits.len() == 11 && digits.starts_with('1') {
+        digits.remove(0);
+    }
+    if digits.len() != 10 {
+        return Err("Phone numbers must have 10 digits.".to_string());
+    }
+
+    Ok(format!(
+        "{}-{}-{}",
+        &digits[..3],
+        &digits[3..6],
+        &digits[6..]
+    ))
+}
+
+/// Formats a dollar amount with two decimal places, such as `$1,250.5` as `1250.50`. Negative
+/// amounts and fractions of a cent aren't accepted.
+pub fn format_currency(input: &str) -> Result<String, String> {
+    let error = || "Value is not a valid dollar amount.".to_string();
+
+    let amount = input.trim();
+    let amount = amount.strip_prefix('$').unwrap_or(amount).replace(',', "");
+    let (dollars, cents) = amount.split_once('.').unwrap_or((&amount, ""));
+
+    if (dollars.is_empty() && cents.is_empty())
+        || !dollars.chars().all(|c| c.is_ascii_digit())
+        || !cents.chars().all(|c| c.is_ascii_digit())
+        || cents.len() > 2
+    {
+        return Err(error());
+    }
+
+    let dollars = dollars.trim_start_matches('0');
+    Ok(format!(
+        "{}.{:0<2}",
+        if dollars.is_empty() { "0" } else { dollars },
+        cents
+    ))
+}
+
+/// Parses a date from a date picker, which uses the `YYYY-MM-DD` format.
+pub fn parse_date(input: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(input.trim(), "%Y-%m-%d")
+        .map_err(|_| "Value is not a valid date.".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checks_text_limits() {
+        let limits = TextLimits {
+            max_chars: Some(20),
+            max_words: Some(3),
+        };
+
+        assert_eq!(limits.check("One two three"), Ok(()));
+        assert_eq!(
+            limits.check("One two three four"),
+            Err("Must be at most 3 words.".to_string())
+        );
+        assert!(limits.check("Supercalifragilistic!").is_err());
+        assert_eq!(
+            limits.describe_usage("One two"),
+            "7/20 characters · 2/3 words"
+        );
+        assert_eq!(TextLimits::default().check(&"a ".repeat(1000)), Ok(()));
+    }
+
+    #[test]
+    fn checks_entry_limits() {
+        let limits = EntryLimits::between(2, 3);
+
+        assert_eq!(limits.check(2), Ok(()));
+        assert_eq!(limits.check(1), Err("Add at least 2 entries.".to_string()));
+        assert_eq!(
+            limits.check(4),
+            Err("Remove entries until there are at most 3.".to_string())
+        );
+        assert_eq!(EntryLimits::default().check(0), Ok(()));
+    }
+
+    #[test]
+    fn checks_emails() {
+        let any = vec!["*".to_string()];
+        let school = vec!["region15.org".to_string()];
+
+        assert_eq!(check_email("me@example.com", &any), Ok(()));
+        assert_eq!(check_email("me@Region15.org", &school), Ok(()));
+        assert!(
+            check_email("me@example.com", &school)
+                .unwrap_err()
+                .contains("region15.org")
+        );
+        assert!(check_email("me.example.com", &any).is_err());
+        assert!(check_email("@example.com", &any).is_err());
+    }
+
+    #[test]
+    fn formats_phone_numbers() {
+        assert_eq!(
+            format_phone("(203) 555-0134").as_deref(),
+            Ok("203-555-0134")
+        );
+        assert_eq!(
+            format_phone("+1 203.555.0134").as_deref(),
+            Ok("203-555-0134")
+        );
+        assert!(format_phone("555-0134").is_err());
+        assert!(format_phone("203-555-01x4").is_err());
+    }
+
+    #[test]
+    fn formats_currency() {
+        assert_eq!(format_currency("$1,250.5").as_deref(), Ok("1250.50"));
+        assert_eq!(format_currency("0012").as_deref(), Ok("12.00"));
+        assert_eq!(format_currency(".75").as_deref(), Ok("0.75"));
+        assert!(format_currency("-5").is_err());
+        assert!(format_currency("1.005").is_err());
+        assert!(format_currency("$").is_err());
+    }
+
+    #[test]
+    fn parses_dates() {
+        assert_eq!(
+            parse_date("2026-03-27"),
+            Ok(NaiveDate::from_ymd_opt(2026, 3, 27).unwrap())
+        );
+        assert!(parse_date("03/27/2026").is_err());
+        assert!(parse_date("2026-02-30").is_err());
+    }
+}
@@ -1,16 +1,27 @@
 mod comparison;
+mod completion;
 mod date_info;
+mod eligibility;
 mod expandable_info;
+mod form_definition;
+mod form_draft;
 mod form_params;
 mod form_schema;
+mod input_format;
 mod submit_status;
 mod user;
 mod value_type;
+mod value_path;
+mod value_map;
+mod value_patch;
 pub mod animations;
 mod tab_info;
 mod schema_node;
+mod scoring;
+mod student_forms;
 
 pub use self::{
-    comparison::*, date_info::*, expandable_info::*, form_params::*, form_schema::*,
-    submit_status::*, user::*, value_type::*, tab_info::*, schema_node::*,
+    comparison::*, completion::*, date_info::*, eligibility::*, expandable_info::*, form_definition::*, form_draft::*, form_params::*, form_schema::*, input_format::*,
+    submit_status::*, user::*, value_type::*, value_path::*, value_map::*, value_patch::*, tab_info::*, schema_node::*, scoring::*,
+    student_forms::*,
 };
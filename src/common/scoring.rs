@@ -0,0 +1,538 @@
+use crate::common::{ExpandableInfo, NumberComparison, Rule, ValuePath, ValueType, rename_path};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// The highest number of points that a scaled criterion can award, before its weight is applied.
+pub const MAX_SCALED_POINTS: f64 = 10.0;
+
+/// An example of a [`ScoringMethod::Buckets`] criterion's `buckets`, as shown to providers.
+pub const BUCKETS_EXAMPLE: &str = ">= 40: 10, >= 20: 5";
+
+/// An example of a [`ScoringMethod::Rule`] criterion's `rule`, as shown to providers.
+pub const RULE_EXAMPLE: &str = r#"gpa >= 3.5 and town == "Middlebury""#;
+
+/// A single range of values in a [`ScoringMethod::Buckets`] criterion, like `>= 40: 10`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ScoreBucket {
+    pub comparison: NumberComparison,
+    pub value: f64,
+    pub points: f64,
+}
+
+/// The ways that a criterion can turn a student's data into points.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum ScoringMethod {
+    /// Scales a number linearly, so that `min` is worth no points and `max` is worth
+    /// [`MAX_SCALED_POINTS`]. Values outside the range are clamped. Setting `min` above `max`
+    /// reverses the scale, which is useful for need-based criteria like household income.
+    Scaled { min: f64, max: f64 },
+    /// Awards the points of the first bucket that the number falls into, or none if it doesn't
+    /// fall into any of them.
+    Buckets(Vec<ScoreBucket>),
+    /// Awards the given points when the student passes a rule. Refer to [`Rule`] for the syntax.
+    Rule { rule: Rule, points: f64 },
+}
+
+/// # Scoring Criterion
+///
+/// A weighted criterion used to rank the eligible students of a scholarship. Scholarships store
+/// their criteria as a list of maps under the `scoring` key, which is edited using a
+/// `MultiEntry` component. Each map contains:
+///  - `label`: the name displayed in score breakdowns.
+///  - `method`: one of `Scaled`, `Buckets`, or `Rule`.
+///  - `field`: the student field to read, like `gpa`. Lists of numbers are summed, and lists of
+///    maps can be summed using a key, like `community_service.hours`. Not used by `Rule`.
+///  - `weight`: the multiplier applied to the criterion's points. Defaults to 1.
+///  - `min` and `max`: the range used by `Scaled`.
+///  - `buckets`: the buckets used by `Buckets`, like `>= 40: 10, >= 20: 5, > 0: 2`.
+///  - `rule` and `points`: the rule and points used by `Rule`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ScoringCriterion {
+    pub label: String,
+    pub field: String,
+    pub weight: f64,
+    pub method: ScoringMethod,
+}
+
+/// The points a student received from a single criterion.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ScoreComponent {
+    pub label: String,
+    /// The points awarded before the weight is applied.
+    pub points: f64,
+    pub weight: f64,
+    /// The points awarded after the weight is applied. This is added to the total score.
+    pub weighted: f64,
+    /// Explains why no points were awarded, such as missing student data or an invalid criterion.
+    pub note: Option<String>,
+}
+
+/// # Score Breakdown
+///
+/// A student's total score for a scholarship, along with the points from each criterion.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct ScoreBreakdown {
+    pub total: f64,
+    pub components: Vec<ScoreComponent>,
+}
+
+impl ScoreBreakdown {
+    /// Describes each component on its own line, for use in tooltips.
+    pub fn describe(&self) -> String {
+        self.components
+            .iter()
+            .map(|component| match &component.note {
+                Some(note) => format!("{}: 0 ({note})", component.label),
+                None => format!(
+                    "{}: {} × {} = {}",
+                    component.label,
+                    round(component.points),
+                    round(component.weight),
+                    round(component.weighted)
+                ),
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+impl ScoringCriterion {
+    /// Reads a criterion from one of the maps stored in a scholarship's `scoring` list.
+    pub fn from_map(map: &HashMap<String, ValueType>) -> Result<Self, String> {
+        let label = get_text(map, "label");
+        let field = get_text(map, "field");
+        let weight = get_number(map, "weight")?.unwrap_or(1.0);
+
+        let method = match get_text(map, "method").as_str() {
+            "Scaled" => {
+                let min = get_number(map, "min")?.ok_or("Scaled criteria need a minimum.")?;
+                let max = get_number(map, "max")?.ok_or("Scaled criteria need a maximum.")?;
+                if min == max {
+                    return Err("The minimum and maximum must be different.".to_string());
+                }
+                ScoringMethod::Scaled { min, max }
+            }
+            "Buckets" => ScoringMethod::Buckets(parse_buckets(&get_text(map, "buckets"))?),
+            "Rule" => {
+                let text = get_text(map, "rule");
+                let rule = Rule::parse(&text).map_err(|err| err.render(&text))?;
+                let points = get_number(map, "points")?.unwrap_or(MAX_SCALED_POINTS);
+                ScoringMethod::Rule { rule, points }
+            }
+            other => return Err(format!("Unknown scoring method {other:?}.")),
+        };
+
+        if field.is_empty() && !matches!(method, ScoringMethod::Rule { .. }) {
+            return Err("This criterion needs a student field.".to_string());
+        }
+
+        Ok(Self {
+            label,
+            field,
+            weight,
+            method,
+        })
+    }
+
+    /// Gets the student members that this criterion reads.
+    pub fn members(&self) -> Vec<String> {
+        match &self.method {
+            ScoringMethod::Rule { rule, .. } => rule
+                .groups
+                .iter()
+                .flatten()
+                .map(|condition| condition.member.clone())
+                .collect(),
+            _ => vec![self.field.split('.').next().unwrap_or_default().to_string()],
+        }
+    }
+
+    /// Scores a student using this criterion.
+    pub fn score(&self, student: &HashMap<String, ValueType>) -> ScoreComponent {
+        let points = match &self.method {
+            ScoringMethod::Rule { rule, points } => {
+                Ok(if rule.evaluate(student) { *points } else { 0.0 })
+            }
+            ScoringMethod::Scaled { min, max } => get_field_number(student, &self.field)
+                .map(|value| ((value - min) / (max - min)).clamp(0.0, 1.0) * MAX_SCALED_POINTS),
+            ScoringMethod::Buckets(buckets) => {
+                get_field_number(student, &self.field).map(|value| {
+                    buckets
+                        .iter()
+                        .find(|bucket| compare_numbers(&bucket.comparison, value, bucket.value))
+                        .map(|bucket| bucket.points)
+                        .unwrap_or(0.0)
+                })
+            }
+        };
+
+        match points {
+            Ok(points) => ScoreComponent {
+                label: self.label.clone(),
+                points,
+                weight: self.weight,
+                weighted: points * self.weight,
+                note: None,
+            },
+            Err(note) => ScoreComponent {
+                label: self.label.clone(),
+                points: 0.0,
+                weight: self.weight,
+                weighted: 0.0,
+                note: Some(note),
+            },
+        }
+    }
+}
+
+/// Gets the scoring criteria stored in a scholarship. Criteria that can't be read are returned as
+/// errors, labelled with the criterion's label.
+pub fn get_scoring_criteria(
+    scholarship: &ExpandableInfo,
+) -> Vec<Result<ScoringCriterion, (String, String)>> {
+    scholarship
+        .data
+        .get("scoring")
+        .and_then(|v| v.as_list().ok().flatten())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|entry| entry.as_map().ok().flatten())
+        .map(|map| ScoringCriterion::from_map(&map).map_err(|err| (get_text(&map, "label"), err)))
+        .collect()
+}
+
+/// Renames a stored field in a scholarship's scoring criteria, in both the `field` of each
+/// criterion and the conditions of its rule. Returns whether anything changed.
+pub fn rename_scoring_field(scholarship: &mut ExpandableInfo, from: &str, to: &str) -> bool {
+    let Some(mut entries) = scholarship
+        .data
+        .get("scoring")
+        .and_then(|v| v.as_list().ok().flatten())
+    else {
+        return false;
+    };
+
+    let mut renamed = false;
+    for entry in &mut entries {
+        let ValueType::Map(Some(map)) = entry else {
+            continue;
+        };
+
+        if let Some(field) = rename_path(&get_text(map, "field"), from, to) {
+            map.insert("field".to_string(), ValueType::String(Some(field)));
+            renamed = true;
+        }
+
+        if let Ok(mut rule) = Rule::parse(&get_text(map, "rule"))
+            && rule.rename_field(from, to)
+        {
+            map.insert("rule".to_string(), ValueType::String(Some(rule.to_string())));
+            renamed = true;
+        }
+    }
+
+    if renamed {
+        scholarship
+            .data
+            .insert("scoring".to_string(), ValueType::List(Some(entries)));
+    }
+    renamed
+}
+
+/// # Score Student
+///
+/// Scores a student using all the criteria stored in a scholarship. The total is the sum of each
+/// criterion's weighted points. Criteria that are invalid, or that are missing student data, award
+/// no points and explain why in their component's `note`.
+pub fn score_student(
+    student: &HashMap<String, ValueType>,
+    scholarship: &ExpandableInfo,
+) -> ScoreBreakdown {
+    let components = get_scoring_criteria(scholarship)
+        .into_iter()
+        .map(|criterion| match criterion {
+            Ok(criterion) => criterion.score(student),
+            Err((label, err)) => ScoreComponent {
+                label,
+                points: 0.0,
+                weight: 0.0,
+                weighted: 0.0,
+                note: Some(err),
+            },
+        })
+        .collect::<Vec<ScoreComponent>>();
+
+    ScoreBreakdown {
+        total: components.iter().map(|component| component.weighted).sum(),
+        components,
+    }
+}
+
+/// Parses a list of buckets, like `>= 40: 10, >= 20: 5, > 0: 2`.
+pub fn parse_buckets(text: &str) -> Result<Vec<ScoreBucket>, String> {
+    let buckets = text
+        .split(',')
+        .map(str::trim)
+        .filter(|bucket| !bucket.is_empty())
+        .map(|bucket| {
+            let invalid = || format!("{bucket:?} should look like \">= 40: 10\".");
+            let (condition, points) = bucket.split_once(':').ok_or_else(invalid)?;
+            let condition = condition.trim();
+
+            let (comparison, value) = [
+                (">=", NumberComparison::GreaterThanOrEqual),
+                ("<=", NumberComparison::LessThanOrEqual),
+                ("==", NumberComparison::Equal),
+                ("!=", NumberComparison::NotEqual),
+                (">", NumberComparison::GreaterThan),
+                ("<", NumberComparison::LessThan),
+            ]
+            .into_iter()
+            .find_map(|(symbol, comparison)| {
+                condition
+                    .strip_prefix(symbol)
+                    .map(|value| (comparison, value.trim()))
+            })
+            .ok_or_else(invalid)?;
+
+            Ok(ScoreBucket {
+                comparison,
+                value: value.parse().map_err(|_| invalid())?,
+                points: points.trim().parse().map_err(|_| invalid())?,
+            })
+        })
+        .collect::<Result<Vec<ScoreBucket>, String>>()?;
+
+    if buckets.is_empty() {
+        Err("Bucket criteria need at least one bucket.".to_string())
+    } else {
+        Ok(buckets)
+    }
+}
+
+/// Gets a number from the student's data. The field may be a single member, like `gpa`, or a
+/// member and key, like `community_service.hours`. Lists are summed.
+fn get_field_number(student: &HashMap<String, ValueType>, field: &str) -> Result<f64, String> {
+    let (member, key) = match field.split_once('.') {
+        Some((member, key)) => (member, Some(key)),
+        None => (field, None),
+    };
+
+    if !student.contains_key(member) {
+        return Err(format!("The student hasn't entered {member}."));
+    }
+    let not_a_number = || format!("{field} is not a number.");
+
+    match (student.get_list(member), key) {
+        (Some(entries), Some(key)) => entries
+            .iter()
+            .filter(|entry| entry.get_path(key).is_some())
+            .map(|entry| entry.get_f64(key).ok_or_else(not_a_number))
+            .sum(),
+        (Some(values), None) => values
+            .iter()
+            .map(|value| match value {
+                ValueType::Number(Some(n)) | ValueType::String(Some(n)) => {
+                    n.trim().parse::<f64>().map_err(|_| not_a_number())
+                }
+                _ => Err(not_a_number()),
+            })
+            .sum(),
+        (None, None) => student.get_f64(member).ok_or_else(not_a_number),
+        (None, Some(_)) => Err(format!("{member} is not a list of entries.")),
+    }
+}
+
+fn compare_numbers(comparison: &NumberComparison, value: f64, target: f64) -> bool {
+    match comparison {
+        NumberComparison::GreaterThan => value > target,
+        NumberComparison::LessThan => value < target,
+        NumberComparison::Equal => value == target,
+        NumberComparison::NotEqual => value != target,
+        NumberComparison::GreaterThanOrEqual => value >= target,
+        NumberComparison::LessThanOrEqual => value <= target,
+    }
+}
+
+fn get_text(map: &HashMap<String, ValueType>, key: &str) -> String {
+    map.get(key)
+        .and_then(|v| match v {
+            ValueType::String(s) | ValueType::Number(s) => s.clone(),
+            _ => None,
+        })
+        .unwrap_or_default()
+        .trim()
+        .to_string()
+}
+
+fn get_number(map: &HashMap<String, ValueType>, key: &str) -> Result<Option<f64>, String> {
+    let text = get_text(map, key);
+    if text.is_empty() {
+        return Ok(None);
+    }
+
+    text.parse::<f64>()
+        .map(Some)
+        .map_err(|_| format!("{key} must be a number."))
+}
+
+/// Rounds a score to two decimal places for display.
+fn round(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::comparison::test_utils::{number, text};
+
+    fn criterion(entries: &[(&str, &str)]) -> ValueType {
+        ValueType::Map(Some(
+            entries
+                .iter()
+                .map(|(key, value)| (key.to_string(), text(value)))
+                .collect(),
+        ))
+    }
+
+    fn create_student() -> HashMap<String, ValueType> {
+        let service = |hours: &str| {
+            ValueType::Map(Some(HashMap::from([("hours".to_string(), number(hours))])))
+        };
+
+        HashMap::from([
+            ("gpa".to_string(), number("3.5")),
+            ("household_income".to_string(), number("40000")),
+            ("attend_bas".to_string(), text("Yes")),
+            (
+                "community_service".to_string(),
+                ValueType::List(Some(vec![service("20"), service("15")])),
+            ),
+        ])
+    }
+
+    #[test]
+    fn parse_bucket_list() {
+        let buckets = parse_buckets(">= 40: 10, >= 20: 5,> 0:2").unwrap();
+
+        assert_eq!(buckets.len(), 3);
+        assert_eq!(
+            buckets[1],
+            ScoreBucket {
+                comparison: NumberComparison::GreaterThanOrEqual,
+                value: 20.0,
+                points: 5.0,
+            }
+        );
+        assert!(parse_buckets("").is_err());
+        assert!(parse_buckets("40: 10").is_err());
+        assert!(parse_buckets(">= forty: 10").is_err());
+    }
+
+    #[test]
+    fn parse_examples() {
+        assert_eq!(parse_buckets(BUCKETS_EXAMPLE).map(|buckets| buckets.len()), Ok(2));
+        assert!(Rule::parse(RULE_EXAMPLE).is_ok());
+    }
+
+    #[test]
+    fn score_breakdown() {
+        let mut scholarship = ExpandableInfo::new("scholarship_1");
+        scholarship.data.insert(
+            "scoring".to_string(),
+            ValueType::List(Some(vec![
+                criterion(&[
+                    ("label", "GPA"),
+                    ("method", "Scaled"),
+                    ("field", "gpa"),
+                    ("min", "2.0"),
+                    ("max", "4.0"),
+                    ("weight", "2"),
+                ]),
+                criterion(&[
+                    ("label", "Service"),
+                    ("method", "Buckets"),
+                    ("field", "community_service.hours"),
+                    ("buckets", ">= 40: 10, >= 20: 5"),
+                ]),
+                criterion(&[
+                    ("label", "Need"),
+                    ("method", "Scaled"),
+                    ("field", "household_income"),
+                    ("min", "100000"),
+                    ("max", "20000"),
+                ]),
+                criterion(&[
+                    ("label", "BAS"),
+                    ("method", "Rule"),
+                    ("rule", r#"attend_bas == "Yes""#),
+                    ("points", "3"),
+                ]),
+                criterion(&[
+                    ("label", "SAT"),
+                    ("method", "Scaled"),
+                    ("field", "sat"),
+                    ("min", "400"),
+                    ("max", "1600"),
+                ]),
+                criterion(&[("label", "Broken"), ("method", "Sorted")]),
+            ])),
+        );
+
+        let breakdown = score_student(&create_student(), &scholarship);
+        let weighted = breakdown
+            .components
+            .iter()
+            .map(|component| component.weighted)
+            .collect::<Vec<f64>>();
+
+        assert_eq!(weighted, vec![15.0, 5.0, 7.5, 3.0, 0.0, 0.0]);
+        assert_eq!(breakdown.total, 30.5);
+        assert!(breakdown.components[4].note.is_some());
+        assert!(breakdown.components[5].note.is_some());
+    }
+
+    #[test]
+    fn renames_scoring_fields() {
+        let mut scholarship = ExpandableInfo::new("scholarship_1");
+        scholarship.data.insert(
+            "scoring".to_string(),
+            ValueType::List(Some(vec![
+                criterion(&[
+                    ("label", "Service"),
+                    ("method", "Buckets"),
+                    ("field", "community_service.hours"),
+                    ("buckets", ">= 40: 10, >= 20: 5"),
+                ]),
+                criterion(&[
+                    ("label", "BAS"),
+                    ("method", "Rule"),
+                    ("rule", r#"attend_bas == "Yes" and gpa >= 3"#),
+                    ("points", "3"),
+                ]),
+            ])),
+        );
+
+        assert!(!rename_scoring_field(&mut scholarship, "hours", "service_hours"));
+        assert!(rename_scoring_field(
+            &mut scholarship,
+            "community_service.hours",
+            "community_service.service_hours"
+        ));
+        assert!(rename_scoring_field(&mut scholarship, "attend_bas", "bas"));
+
+        let criteria = get_scoring_criteria(&scholarship)
+            .into_iter()
+            .collect::<Result<Vec<ScoringCriterion>, _>>()
+            .unwrap();
+        assert_eq!(criteria[0].field, "community_service.service_hours");
+        assert_eq!(criteria[1].members(), vec!["bas", "gpa"]);
+    }
+
+    #[test]
+    fn no_criteria() {
+        let breakdown = score_student(&create_student(), &ExpandableInfo::new("scholarship_1"));
+
+        assert_eq!(breakdown, ScoreBreakdown::default());
+    }
+}
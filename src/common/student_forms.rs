@@ -0,0 +1,511 @@
+use crate::common::{
+    EntryLimits, FormCheck, FormDefinition, FormSection, InputType, has_value, number_value,
+};
+use crate::input;
+
+/// Shows the second parent's questions once the student says that there is a second parent.
+const SECOND_PARENT: &str = r#"has_second_parent == "Yes""#;
+
+/// Gets the definitions of every student form, in the order that they're shown to students.
+pub fn student_form_definitions() -> Vec<FormDefinition> {
+    vec![
+        FormDefinition::new(
+            "demographics",
+            "Student Demographic Form",
+            "Here you'll set up some basic information about yourself.",
+        )
+        .tab("Demographics", "demographics")
+        .section(FormSection::new(
+            "",
+            "",
+            vec![
+                input!(Text, "first_name", "First Name:", true, "John"),
+                input!(Text, "last_name", "Last Name:", true, "Smith"),
+                input!(Text, "dob", "Date of Birth:", true, "MM/DD/YYYY"),
+                input!(
+                    Select,
+                    "gender",
+                    "Gender:",
+                    true,
+                    ["Male", "Female", "Prefer not to answer/Other"]
+                ),
+                input!(Number, "id_number", "Student ID Number:", true, "Your 7 digit number..."),
+                input!(Email, "email", "Preferred Email:", true, "me@example.com", ["*"]),
+                input!(Phone, "phone_number", "Preferred Phone Number:", true, "000-000-0000"),
+                input!(Text, "street_address", "Street Address:", true, "234 Judd Rd"),
+                input!(Select, "town", "Town:", true, ["Southbury", "Middlebury"]),
+            ],
+        )),
+        FormDefinition::new(
+            "academics",
+            "Academic Information",
+            "This information will allow you to be eligible for any scholarships that have specific academic/grade requirements.",
+        )
+        .tab("Academics", "additional/academics")
+        .section(FormSection::new(
+            "",
+            "",
+            vec![
+                input!(Number, "unweighted_gpa", "Unweighted GPA:", true, "4.0"),
+                input!(Number, "weighted_gpa", "Weighted GPA:", true, "4.5"),
+                input!(Number, "sat_score", "Highest SAT Score:", false, "1600"),
+                input!(Number, "act_score", "Highest ACT Score:", false, "36"),
+                input!(Text, "academic_honors", "Academic Honors:", false, "High Honors, etc."),
+            ],
+        )),
+        FormDefinition::new(
+            "athletics",
+            "Athletics Information",
+            "This information will allow you to be eligible for any scholarships that have specific sports requirements.",
+        )
+        .tab("Athletics", "additional/athletics")
+        .section(FormSection::new(
+            "",
+            "Leave blank if not applicable.",
+            vec![
+                InputType::MultiEntry(
+                    "sports_participation".to_string(),
+                    "Athletics Activities:".to_string(),
+                    vec![
+                        input!(
+                            Select,
+                            "sport_name",
+                            "Sport Name:",
+                            true,
+                            [
+                                "Football",
+                                "Soccer",
+                                "Cheerleading",
+                                "Field Hockey",
+                                "Swimming",
+                                "Golf",
+                                "Basketball",
+                                "Track",
+                                "Gymnastics",
+                                "Ice Hockey",
+                                "Ski",
+                                "Wrestling",
+                                "Lacrosse",
+                                "Softball",
+                                "Tennis",
+                                "Baseball",
+                                "Volleyball",
+                            ]
+                        ),
+                        input!(
+                            Checkbox,
+                            "grades",
+                            "Grades Participated:",
+                            true,
+                            ["9th", "10th", "11th", "12th"]
+                        ),
+                        input!(
+                            Text,
+                            "achievements",
+                            "Special Achievements:",
+                            false,
+                            "Example 1, Example 2, etc..."
+                        ),
+                    ],
+                    EntryLimits::default(),
+                    false
+                ),
+            ],
+        )),
+        FormDefinition::new(
+            "workexp",
+            "Student Work Experience",
+            "Here you may add information about your past/current workplace.",
+        )
+        .tab("Work Experience", "additional/work-experience")
+        .section(FormSection::new(
+            "",
+            "Leave blank if not applicable.",
+            vec![
+                InputType::MultiEntry(
+                    "extracurricular".to_string(),
+                    "Work Experience:".to_string(),
+                    vec![
+                        input!(Text, "job_title", "Job Title:", true, "Waiter, Cashier, etc..."),
+                        input!(Text, "employer", "Employer:", true, "Your employer's name..."),
+                        input!(Text, "start_date", "Approximate start date:", true, "01/01/2000"),
+                        input!(
+                            Text,
+                            "end_date",
+                            "End date:",
+                            true,
+                            "01/01/2026, or current if not applicable"
+                        ),
+                        input!(
+                            Number,
+                            "num_hours",
+                            "Approximate number of hours per week:",
+                            true,
+                            "25"
+                        ),
+                    ],
+                    EntryLimits::default(),
+                    false
+                ),
+            ],
+        )),
+        FormDefinition::new(
+            "extracurriculars",
+            "Extracurricular and Community Service Information",
+            "This information will allow you to be eligible for any scholarships that have specific extracurricular requirements.",
+        )
+        .tab("Extracurriculars", "additional/extracurriculars")
+        .section(FormSection::new(
+            "",
+            "",
+            vec![
+                input!(
+                    Number,
+                    "service_hours",
+                    "Total number of service hours:",
+                    true,
+                    "Any number..."
+                ),
+            ],
+        ))
+        .section(FormSection::new(
+            "",
+            "Leave blank if not applicable.",
+            vec![
+                InputType::MultiEntry(
+                    "extracurricular".to_string(),
+                    "Extracurricular Activities:".to_string(),
+                    vec![
+                        input!(Text, "activity_name", "Activity Name:", true, "Some activity..."),
+                        input!(Number, "num_hours", "Number of hours completed:", true, "40"),
+                        input!(Number, "num_weeks", "Number of weeks participated:", true, "3"),
+                        input!(
+                            Text,
+                            "special_involvement",
+                            "Any special involvement:",
+                            false,
+                            "Club president, etc."
+                        ),
+                        input!(
+                            Checkbox,
+                            "grades",
+                            "Grades Participated:",
+                            true,
+                            ["9th", "10th", "11th", "12th"]
+                        ),
+                    ],
+                    EntryLimits::default(),
+                    false
+                ),
+            ],
+        )),
+        FormDefinition::new(
+            "university",
+            "University Information",
+            "Here you may fill out general information about the university you plan to attend.",
+        )
+        .tab("University Info", "additional/university")
+        .section(FormSection::new(
+            "",
+            "",
+            vec![
+                input!(Text, "college_name", "University Name:", true, "University of Example"),
+                input!(Text, "college_city", "University Street Address:", true, "123 Example Rd"),
+                input!(Text, "college_state", "University State:", true, "CT, AZ, etc."),
+                input!(Text, "college_zip", "University ZIP:", true, "12345"),
+                input!(
+                    Select,
+                    "college_acceptance",
+                    "Have you been sent an acceptance to the university?",
+                    true,
+                    ["Yes", "No"]
+                ),
+                input!(
+                    Select,
+                    "major",
+                    "Intended Major/Field of Study:",
+                    true,
+                    [
+                        "Music",
+                        "Education",
+                        "Special Education",
+                        "Speech Pathology",
+                        "School Psychology",
+                        "School Counseling",
+                        "Occupational Therapy",
+                        "Physical Therapy",
+                        "Nursing",
+                        "Allied Health",
+                        "Fine/Performing Arts",
+                        "Writing/Communication",
+                        "History",
+                        "Government",
+                        "Political Science",
+                        "Social Work",
+                        "Sports Medicine",
+                        "Athletic Training",
+                        "Horticulture",
+                        "Conservation Studies",
+                        "Ecology",
+                        "Environmental Studies",
+                        "Urban Planning",
+                        "Landscaping",
+                        "Legal Studies",
+                        "Criminal Justice",
+                        "None of the above",
+                    ]
+                ),
+                input!(
+                    Text,
+                    "intended_career",
+                    "Intended Career:",
+                    false,
+                    "Engineer, Artist, etc."
+                ),
+            ],
+        )),
+        FormDefinition::new(
+            "family",
+            "Family Information",
+            "General family information. You must fill out information about at least one parent.",
+        )
+        .tab("Family Info", "additional/family-info")
+        .section(FormSection::new(
+            "",
+            "",
+            vec![
+                input!(
+                    Number,
+                    "num_children",
+                    "Total number of children in family:",
+                    true,
+                    "Any number..."
+                ),
+                input!(
+                    Number,
+                    "num_children_college",
+                    "Total number of children currently attending college:",
+                    true,
+                    "Any number..."
+                ),
+            ],
+        ))
+        .section(FormSection::new(
+            "Parent/Guardian 1",
+            "",
+            vec![
+                input!(Text, "parent_one_name", "Parent/Guardian 1 Name:", true, "John Smith"),
+                input!(
+                    Text,
+                    "parent_one_relationship",
+                    "Parent/Guardian 1 Relationship:",
+                    true,
+                    "Mother/Father"
+                ),
+                input!(
+                    Text,
+                    "parent_one_occupation",
+                    "Parent/Guardian 1 Occupation:",
+                    true,
+                    "Milkman"
+                ),
+                input!(
+                    Text,
+                    "parent_one_employer",
+                    "Parent/Guardian 1 Employer:",
+                    true,
+                    "Example Employer"
+                ),
+            ],
+        ))
+        .section(FormSection::new(
+            "Parent/Guardian 2",
+            "",
+            vec![
+                input!(
+                    Radio,
+                    "has_second_parent",
+                    "Is there a second parent/guardian?",
+                    true,
+                    ["Yes", "No"]
+                ),
+                input!(Text, "parent_two_name", "Parent/Guardian 2 Name:", true, "John Smith")
+                    .shown_when(SECOND_PARENT),
+                input!(
+                    Text,
+                    "parent_two_relationship",
+                    "Parent/Guardian 2 Relationship:",
+                    true,
+                    "Mother/Father"
+                )
+                .shown_when(SECOND_PARENT),
+                input!(
+                    Text,
+                    "parent_two_occupation",
+                    "Parent/Guardian 2 Occupation:",
+                    true,
+                    "Milkman"
+                )
+                .shown_when(SECOND_PARENT),
+                input!(
+                    Text,
+                    "parent_two_employer",
+                    "Parent/Guardian 2 Employer:",
+                    true,
+                    "Example Employer"
+                )
+                .shown_when(SECOND_PARENT),
+            ],
+        )),
+        FormDefinition::new(
+            "specifics",
+            "Scholarship-Specific Eligibility Requirements",
+            "These questions are specific to some scholarships. Indicate yes or no for each question.",
+        )
+        .tab("Specifics", "additional/specifics")
+        .section(FormSection::new(
+            "",
+            "",
+            vec![
+                input!(Radio, "attend_bas", "Have you attended BAS?", true, ["Yes", "No"]),
+                input!(
+                    Radio,
+                    "middsouth_church",
+                    "Are you a member of Midd-South Catholic Church?",
+                    true,
+                    ["Yes", "No"]
+                ),
+                input!(
+                    Radio,
+                    "family_military_service",
+                    "Do you have a family member that is or has served in the US military? (Note: you may be asked for proof of service)",
+                    true,
+                    ["Yes", "No"]
+                ),
+                input!(
+                    Radio,
+                    "youth_baseball",
+                    "Have you participated in Pomperaug Youth Baseball?",
+                    true,
+                    ["Yes", "No"]
+                ),
+                input!(
+                    Radio,
+                    "aquatic_club",
+                    "Have you participated in the Panthers Aquatic Club?",
+                    true,
+                    ["Yes", "No"]
+                ),
+                input!(
+                    Radio,
+                    "pea_member",
+                    "Are at least one of your parents a member of the Region 15 PEA?",
+                    true,
+                    ["Yes", "No"]
+                ),
+                input!(
+                    Radio,
+                    "music_program",
+                    "Have you participated in the PHS Music Program (Band, Chorus, etc)?",
+                    true,
+                    ["Yes", "No"]
+                ),
+            ],
+        )),
+    ]
+}
+
+/// The checks of the academics form, which compare its inputs with each other.
+static ACADEMIC_CHECKS: [FormCheck; 2] = [
+    FormCheck::new(&["weighted_gpa"], |data| {
+        match (
+            number_value(data, "unweighted_gpa"),
+            number_value(data, "weighted_gpa"),
+        ) {
+            (Some(unweighted), Some(weighted)) if weighted < unweighted => {
+                Err("Your weighted GPA can't be lower than your unweighted GPA.".to_string())
+            }
+            _ => Ok(()),
+        }
+    })
+    .reads(&["unweighted_gpa"]),
+    FormCheck::new(&["sat_score", "act_score"], |data| {
+        if has_value(data, "sat_score") || has_value(data, "act_score") {
+            Ok(())
+        } else {
+            Err("Enter your highest SAT or ACT score.".to_string())
+        }
+    }),
+];
+
+/// Gets the [`FormCheck`]s of the student form with the given ID. Forms that don't have any
+/// checks, including forms that only exist in the table, return an empty list.
+pub fn student_form_checks(id: &str) -> &'static [FormCheck] {
+    match id.to_lowercase().as_str() {
+        "academics" => &ACADEMIC_CHECKS,
+        _ => &[],
+    }
+}
+
+/// Gets the definition of the student form with the given ID. IDs aren't case-sensitive, since
+/// forms are stored using their uppercased ID.
+pub fn get_form_definition(id: &str) -> Option<FormDefinition> {
+    student_form_definitions()
+        .into_iter()
+        .find(|definition| definition.id.eq_ignore_ascii_case(id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::ValueType;
+    use std::collections::HashMap;
+
+    #[test]
+    fn definitions_are_valid() {
+        for definition in student_form_definitions() {
+            assert_eq!(definition.check_structure(), Ok(()), "{}", definition.id);
+            for check in student_form_checks(&definition.id) {
+                for key in check.inputs() {
+                    assert!(definition.field(key).is_some(), "{}", key);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn needs_checked_inputs() {
+        let mut definition = get_form_definition("academics").unwrap();
+        for section in &mut definition.sections {
+            section.fields.retain(|field| field.key() != "unweighted_gpa");
+        }
+
+        assert!(
+            definition
+                .check_structure()
+                .unwrap_err()
+                .contains("unweighted_gpa")
+        );
+    }
+
+    #[test]
+    fn checks_academics() {
+        let definition = get_form_definition("academics").unwrap();
+        let number = |value: &str| ValueType::Number(Some(value.to_string()));
+
+        let data = HashMap::from([
+            ("unweighted_gpa".to_string(), number("3.8")),
+            ("weighted_gpa".to_string(), number("4.2")),
+            ("act_score".to_string(), number("31")),
+        ]);
+        assert_eq!(definition.validate(&data), Ok(()));
+
+        let data = HashMap::from([
+            ("unweighted_gpa".to_string(), number("3.8")),
+            ("weighted_gpa".to_string(), number("3.5")),
+        ]);
+        let errors = definition.validate(&data).unwrap_err();
+        assert!(errors["weighted_gpa"].contains("weighted GPA"));
+        assert!(errors.contains_key("sat_score") && errors.contains_key("act_score"));
+    }
+}
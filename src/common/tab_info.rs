@@ -3,6 +3,8 @@ pub struct TabInfo {
     pub text: String,
     pub path: String,
     pub sub_paths: Vec<SubTabInfo>,
+    /// Short text shown next to the tab, such as a form's progress.
+    pub badge: Option<String>,
 }
 
 impl TabInfo {
@@ -14,22 +16,38 @@ impl TabInfo {
         Self {
             text: text.into(),
             path: path.into(),
-            sub_paths: sub_paths.unwrap_or_default()
+            sub_paths: sub_paths.unwrap_or_default(),
+            badge: None,
         }
     }
+
+    /// Shows a badge next to the tab.
+    pub fn badge(mut self, badge: Option<String>) -> Self {
+        self.badge = badge;
+        self
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct SubTabInfo {
     pub text: String,
     pub path: String,
+    /// Short text shown next to the tab, such as a form's progress.
+    pub badge: Option<String>,
 }
 
 impl SubTabInfo {
     pub fn new(text: impl Into<String>, path: impl Into<String>) -> Self {
         Self {
             text: text.into(),
-            path: path.into()
+            path: path.into(),
+            badge: None,
         }
     }
+
+    /// Shows a badge next to the tab.
+    pub fn badge(mut self, badge: Option<String>) -> Self {
+        self.badge = badge;
+        self
+    }
 }
@@ -0,0 +1,324 @@
+use crate::common::{ExpandableInfo, ValueType};
+use std::collections::HashMap;
+
+/// # Value Field
+///
+/// Converts a single Rust value to and from a [`ValueType`]. This is implemented for the types
+/// that can be stored as fields of a struct that derives [`ValueMap`](macros::ValueMap):
+/// `String`, numbers, `bool`, `Option<T>`, `Vec<T>`, `HashMap<String, ValueType>`, `ValueType`
+/// itself and any struct that derives `ValueMap`, which is stored as a nested `Map`.
+pub trait ValueField: Sized {
+    /// Converts this value into a `ValueType`.
+    fn to_value(&self) -> ValueType;
+
+    /// Converts a stored value into this type. Returns a message describing the problem if the
+    /// stored value has a different type.
+    fn from_value(value: &ValueType) -> Result<Self, String>;
+
+    /// Converts a value that might be missing. Missing and empty values are an error, except for
+    /// `Option<T>`, which becomes `None`.
+    fn from_field(value: Option<&ValueType>) -> Result<Self, String> {
+        match value.filter(|value| !value.is_empty()) {
+            Some(value) => Self::from_value(value),
+            None => Err("is missing".to_string()),
+        }
+    }
+}
+
+/// # Value Map
+///
+/// Converts a struct to and from the `HashMap<String, ValueType>` that forms and tables store,
+/// so that the values can be read as typed fields instead of being looked up by key. This
+/// should be derived using [`macros::ValueMap`] instead of being implemented manually.
+///
+/// ```ignore
+/// #[derive(ValueMap)]
+/// struct Contact {
+///     first_name: String,
+///     #[value(rename = "contact_email")]
+///     email: String,
+///     phone_number: Option<String>,
+///     #[value(default)]
+///     tags: Vec<String>,
+/// }
+///
+/// let contact = Contact::from_map(&map)?;
+/// let map = contact.to_map();
+/// ```
+pub trait ValueMap: Sized {
+    /// Converts this struct into a map, with one entry per field. Fields that are `None` are left
+    /// out of the map.
+    fn to_map(&self) -> HashMap<String, ValueType>;
+
+    /// Reads this struct from a map. Keys that aren't fields of the struct are ignored. Returns a
+    /// message naming the field if a required field is missing or has the wrong type.
+    fn from_map(map: &HashMap<String, ValueType>) -> Result<Self, String>;
+
+    /// Converts this struct into an [`ExpandableInfo`] with the given subject.
+    fn to_expandable(&self, subject: impl Into<String>) -> ExpandableInfo {
+        ExpandableInfo {
+            subject: subject.into(),
+            data: self.to_map(),
+        }
+    }
+
+    /// Reads this struct from an [`ExpandableInfo`]. The subject can be read by a field named
+    /// `subject`, unless the data has its own `subject` entry.
+    fn from_expandable(info: &ExpandableInfo) -> Result<Self, String> {
+        let mut map = info.data.clone();
+        map.entry("subject".to_string())
+            .or_insert_with(|| ValueType::String(Some(info.subject.clone())));
+
+        Self::from_map(&map)
+    }
+}
+
+fn type_error(expected: &str, value: &ValueType) -> String {
+    format!("expected {expected}, found {value:?}")
+}
+
+impl ValueField for ValueType {
+    fn to_value(&self) -> ValueType {
+        self.clone()
+    }
+
+    fn from_value(value: &ValueType) -> Result<Self, String> {
+        Ok(value.clone())
+    }
+}
+
+impl ValueField for String {
+    fn to_value(&self) -> ValueType {
+        ValueType::String(Some(self.clone()))
+    }
+
+    fn from_value(value: &ValueType) -> Result<Self, String> {
+        match value {
+            ValueType::String(Some(value)) | ValueType::Number(Some(value)) => Ok(value.clone()),
+            _ => Err(type_error("a string", value)),
+        }
+    }
+}
+
+/// Numbers are stored as strings, and strings that contain a number are also accepted, as some
+/// forms store numbers as text.
+macro_rules! number_field {
+    ($($t:ty),*) => {
+        $(
+            impl ValueField for $t {
+                fn to_value(&self) -> ValueType {
+                    ValueType::Number(Some(self.to_string()))
+                }
+
+                fn from_value(value: &ValueType) -> Result<Self, String> {
+                    match value {
+                        ValueType::Number(Some(number)) | ValueType::String(Some(number)) => number
+                            .trim()
+                            .parse()
+                            .map_err(|_| type_error("a number", value)),
+                        _ => Err(type_error("a number", value)),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+number_field!(f64, f32, i64, i32, u64, u32, usize);
+
+impl ValueField for bool {
+    fn to_value(&self) -> ValueType {
+        ValueType::Bool(Some(*self))
+    }
+
+    fn from_value(value: &ValueType) -> Result<Self, String> {
+        match value {
+            ValueType::Bool(Some(value)) => Ok(*value),
+            ValueType::String(Some(text)) => text.parse().map_err(|_| type_error("a bool", value)),
+            _ => Err(type_error("a bool", value)),
+        }
+    }
+}
+
+impl<T: ValueField> ValueField for Option<T> {
+    fn to_value(&self) -> ValueType {
+        self.as_ref().map(T::to_value).unwrap_or(ValueType::Null)
+    }
+
+    fn from_value(value: &ValueType) -> Result<Self, String> {
+        Self::from_field(Some(value))
+    }
+
+    fn from_field(value: Option<&ValueType>) -> Result<Self, String> {
+        value
+            .filter(|value| !value.is_empty())
+            .map(T::from_value)
+            .transpose()
+    }
+}
+
+impl<T: ValueField> ValueField for Vec<T> {
+    fn to_value(&self) -> ValueType {
+        ValueType::List(Some(self.iter().map(T::to_value).collect()))
+    }
+
+    fn from_value(value: &ValueType) -> Result<Self, String> {
+        // Sets are read the same as lists.
+        let items = match value {
+            ValueType::List(Some(list)) => list.clone(),
+            ValueType::StringSet(Some(set)) => set
+                .iter()
+                .map(|value| ValueType::String(Some(value.clone())))
+                .collect(),
+            ValueType::NumberSet(Some(set)) => set
+                .iter()
+                .map(|value| ValueType::Number(Some(value.clone())))
+                .collect(),
+            _ => return Err(type_error("a list", value)),
+        };
+
+        items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| T::from_value(item).map_err(|err| format!("[{i}]: {err}")))
+            .collect()
+    }
+}
+
+impl ValueField for HashMap<String, ValueType> {
+    fn to_value(&self) -> ValueType {
+        ValueType::Map(Some(self.clone()))
+    }
+
+    fn from_value(value: &ValueType) -> Result<Self, String> {
+        match value {
+            ValueType::Map(Some(map)) => Ok(map.clone()),
+            _ => Err(type_error("a map", value)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::comparison::test_utils::text;
+    use macros::ValueMap;
+
+    #[derive(ValueMap, Debug, Clone, PartialEq, Default)]
+    struct Address {
+        street: String,
+        town: String,
+    }
+
+    #[derive(ValueMap, Debug, Clone, PartialEq)]
+    struct Student {
+        first_name: String,
+        #[value(rename = "dob")]
+        date_of_birth: Option<String>,
+        gpa: f64,
+        athlete: bool,
+        #[value(default)]
+        sports: Vec<String>,
+        address: Address,
+        #[value(default, rename = "previous_address")]
+        previous: Address,
+    }
+
+
+    fn create_student() -> HashMap<String, ValueType> {
+        HashMap::from([
+            ("first_name".to_string(), text("Jane")),
+            (
+                "gpa".to_string(),
+                ValueType::Number(Some("3.8".to_string())),
+            ),
+            ("athlete".to_string(), ValueType::Bool(Some(true))),
+            (
+                "address".to_string(),
+                ValueType::Map(Some(HashMap::from([
+                    ("street".to_string(), text("234 Judd Rd")),
+                    ("town".to_string(), text("Southbury")),
+                ]))),
+            ),
+            ("unrelated".to_string(), text("ignored")),
+        ])
+    }
+
+    #[test]
+    fn reads_typed_fields() {
+        let student = Student::from_map(&create_student()).unwrap();
+
+        assert_eq!(student.first_name, "Jane");
+        assert_eq!(student.date_of_birth, None);
+        assert_eq!(student.gpa, 3.8);
+        assert!(student.athlete);
+        assert!(student.sports.is_empty());
+        assert_eq!(student.address.town, "Southbury");
+        assert_eq!(student.previous, Address::default());
+    }
+
+    #[test]
+    fn round_trips_through_maps() {
+        let mut map = create_student();
+        map.insert("dob".to_string(), text("01/02/2008"));
+        map.insert(
+            "sports".to_string(),
+            ValueType::List(Some(vec![text("Soccer"), text("Tennis")])),
+        );
+
+        let student = Student::from_map(&map).unwrap();
+        assert_eq!(student.date_of_birth.as_deref(), Some("01/02/2008"));
+        assert_eq!(student.sports, vec!["Soccer", "Tennis"]);
+
+        let written = student.to_map();
+        assert!(written.contains_key("dob"));
+        assert!(written.contains_key("previous_address"));
+        assert!(!written.contains_key("date_of_birth"));
+        assert_eq!(Student::from_map(&written), Ok(student.clone()));
+
+        let info = student.to_expandable("STUDENT#1");
+        assert_eq!(info.subject, "STUDENT#1");
+        assert_eq!(Student::from_expandable(&info), Ok(student));
+    }
+
+    #[test]
+    fn reports_missing_and_mistyped_fields() {
+        let mut map = create_student();
+        map.remove("first_name");
+        assert_eq!(
+            Student::from_map(&map),
+            Err("first_name: is missing".to_string())
+        );
+
+        let mut map = create_student();
+        map.insert("first_name".to_string(), ValueType::String(None));
+        assert_eq!(
+            Student::from_map(&map),
+            Err("first_name: is missing".to_string())
+        );
+
+        let mut map = create_student();
+        map.insert(
+            "address".to_string(),
+            ValueType::Map(Some(HashMap::from([(
+                "street".to_string(),
+                text("1 Main St"),
+            )]))),
+        );
+        assert_eq!(
+            Student::from_map(&map),
+            Err("address: town: is missing".to_string())
+        );
+
+        let mut map = create_student();
+        map.insert(
+            "sports".to_string(),
+            ValueType::List(Some(vec![text("Soccer"), ValueType::Bool(Some(true))])),
+        );
+        assert!(
+            Student::from_map(&map)
+                .unwrap_err()
+                .starts_with("sports: [1]: expected a string")
+        );
+    }
+}
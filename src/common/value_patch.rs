@@ -0,0 +1,307 @@
+use crate::common::{ValuePath, ValueType};
+use crate::common::value_path::{PathSegment, parse_path};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// # Value Patch
+///
+/// A partial update to a stored item. Paths use the same format as [`ValuePath`](super::ValuePath),
+/// such as `a.b[2].c`. Values in `set` are written to their paths, and the paths in `remove` are
+/// deleted. Anything that isn't mentioned is left alone, so that two edits to different fields
+/// don't overwrite each other.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct ValuePatch {
+    pub set: HashMap<String, ValueType>,
+    pub remove: Vec<String>,
+}
+
+impl ValuePatch {
+    /// Creates a patch that turns `old` into `new`, comparing top-level keys.
+    pub fn diff(old: &HashMap<String, ValueType>, new: &HashMap<String, ValueType>) -> Self {
+        let set = new
+            .iter()
+            .filter(|(key, value)| old.get(*key) != Some(*value))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+        let mut remove = old
+            .keys()
+            .filter(|key| !new.contains_key(*key))
+            .cloned()
+            .collect::<Vec<String>>();
+        remove.sort();
+
+        Self { set, remove }
+    }
+
+    /// Returns true if the patch doesn't change anything.
+    pub fn is_empty(&self) -> bool {
+        self.set.is_empty() && self.remove.is_empty()
+    }
+
+    /// Gets the top-level keys that the patch changes. Invalid paths are skipped.
+    pub fn fields(&self) -> HashSet<String> {
+        self.set
+            .keys()
+            .chain(&self.remove)
+            .filter_map(|path| match parse_path(path).ok()?.into_iter().next()? {
+                PathSegment::Key(key) => Some(key),
+                PathSegment::Index(_) => None,
+            })
+            .collect()
+    }
+
+    /// Applies the patch to a map, setting values before removing paths.
+    pub fn apply(&self, map: &mut HashMap<String, ValueType>) -> Result<(), String> {
+        for (path, value) in &self.set {
+            map.set_path(path, value.clone())?;
+        }
+        for path in &self.remove {
+            map.remove_path(path);
+        }
+
+        Ok(())
+    }
+
+    /// Compiles the patch into a DynamoDB update expression. Returns an error if a path is
+    /// invalid, if two paths overlap, or if a path changes one of the `protected` keys, such as
+    /// the table's keys.
+    pub fn to_update_expression(
+        &self,
+        encoding: ValueEncoding,
+        protected: &[&str],
+    ) -> Result<UpdateExpression, String> {
+        let mut set = self.set.iter().collect::<Vec<_>>();
+        set.sort_by(|a, b| a.0.cmp(b.0));
+
+        let mut parsed: Vec<(&str, Vec<PathSegment>)> = Vec::new();
+        for path in set
+            .iter()
+            .map(|(path, _)| path.as_str())
+            .chain(self.remove.iter().map(String::as_str))
+        {
+            let segments = parse_path(path)?;
+            if let Some(PathSegment::Key(key)) = segments.first()
+                && protected.contains(&key.as_str())
+            {
+                return Err(format!("{key:?} can't be changed"));
+            }
+            if let Some((other, _)) = parsed
+                .iter()
+                .find(|(_, other)| other.starts_with(&segments) || segments.starts_with(other))
+            {
+                return Err(format!("Paths {other:?} and {path:?} overlap"));
+            }
+            parsed.push((path, segments));
+        }
+
+        let mut expression = UpdateExpression::default();
+        let mut paths = parsed
+            .into_iter()
+            .map(|(_, segments)| expression.path(&segments, encoding))
+            .collect::<Vec<String>>();
+        let remove_paths = paths.split_off(set.len());
+
+        let set_actions = paths
+            .into_iter()
+            .zip(set)
+            .enumerate()
+            .map(|(i, (path, (_, value)))| {
+                let placeholder = format!(":v{i}");
+                expression.values.insert(placeholder.clone(), value.clone());
+                format!("{path} = {placeholder}")
+            })
+            .collect::<Vec<String>>();
+
+        let mut clauses = Vec::new();
+        if !set_actions.is_empty() {
+            clauses.push(format!("SET {}", set_actions.join(", ")));
+        }
+        if !remove_paths.is_empty() {
+            clauses.push(format!("REMOVE {}", remove_paths.join(", ")));
+        }
+        expression.expression = clauses.join(" ");
+
+        Ok(expression)
+    }
+}
+
+/// How a table stores [`ValueType`]s, which decides how nested paths are written.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ValueEncoding {
+    /// Values are stored as native attributes, such as in the main student table. Maps and lists
+    /// can be indexed directly.
+    Attributes,
+    /// Values are stored using their serde representation, such as in the scholarships table.
+    /// Every map and list is wrapped in a `Map` or `List` attribute.
+    Tagged,
+}
+
+/// # Update Expression
+///
+/// A DynamoDB update expression, created using [`ValuePatch::to_update_expression`]. Every
+/// attribute name is replaced with a `#n` placeholder, so that reserved words and special
+/// characters can be used as keys, and every value is replaced with a `:v` placeholder.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct UpdateExpression {
+    /// The expression, such as `SET #n0.#n1 = :v0 REMOVE #n2`. Empty if nothing changes.
+    pub expression: String,
+    /// The attribute name of each name placeholder.
+    pub names: HashMap<String, String>,
+    /// The value of each value placeholder.
+    pub values: HashMap<String, ValueType>,
+}
+
+impl UpdateExpression {
+    /// Gets the placeholder for an attribute name, reusing the placeholder if the name has
+    /// already been used.
+    fn name(&mut self, name: &str) -> String {
+        if let Some((placeholder, _)) = self.names.iter().find(|(_, existing)| *existing == name) {
+            return placeholder.clone();
+        }
+
+        let placeholder = format!("#n{}", self.names.len());
+        self.names.insert(placeholder.clone(), name.to_string());
+        placeholder
+    }
+
+    /// Writes a path using name placeholders.
+    fn path(&mut self, segments: &[PathSegment], encoding: ValueEncoding) -> String {
+        let mut path = String::new();
+
+        for segment in segments {
+            match segment {
+                PathSegment::Key(key) => {
+                    if !path.is_empty() {
+                        if encoding == ValueEncoding::Tagged {
+                            path = format!("{path}.{}", self.name("Map"));
+                        }
+                        path.push('.');
+                    }
+                    path.push_str(&self.name(key));
+                }
+                PathSegment::Index(index) => {
+                    if encoding == ValueEncoding::Tagged {
+                        path = format!("{path}.{}", self.name("List"));
+                    }
+                    path.push_str(&format!("[{index}]"));
+                }
+            }
+        }
+
+        path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::comparison::test_utils::text;
+
+    fn patch(set: &[(&str, ValueType)], remove: &[&str]) -> ValuePatch {
+        ValuePatch {
+            set: set
+                .iter()
+                .map(|(path, value)| (path.to_string(), value.clone()))
+                .collect(),
+            remove: remove.iter().map(|path| path.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn diff_maps() {
+        let old = HashMap::from([
+            ("first_name".to_string(), text("Jane")),
+            ("town".to_string(), text("Southbury")),
+            ("email".to_string(), text("jane@example.com")),
+        ]);
+        let new = HashMap::from([
+            ("first_name".to_string(), text("Jane")),
+            ("town".to_string(), text("Middlebury")),
+            (
+                "gpa".to_string(),
+                ValueType::Number(Some("3.8".to_string())),
+            ),
+        ]);
+
+        let diff = ValuePatch::diff(&old, &new);
+        assert_eq!(diff.set.len(), 2);
+        assert_eq!(diff.set.get("town"), Some(&text("Middlebury")));
+        assert_eq!(diff.remove, vec!["email".to_string()]);
+        assert_eq!(
+            diff.fields(),
+            HashSet::from(["town".to_string(), "gpa".to_string(), "email".to_string()])
+        );
+        assert!(ValuePatch::diff(&new, &new).is_empty());
+
+        let mut patched = old.clone();
+        diff.apply(&mut patched).unwrap();
+        assert_eq!(patched, new);
+    }
+
+    #[test]
+    fn attribute_expressions() {
+        let update = patch(
+            &[("name", text("Jane")), ("address.town", text("Southbury"))],
+            &["jobs[1].name"],
+        )
+        .to_update_expression(ValueEncoding::Attributes, &[])
+        .unwrap();
+
+        assert_eq!(
+            update.expression,
+            "SET #n0.#n1 = :v0, #n2 = :v1 REMOVE #n3[1].#n2"
+        );
+        assert_eq!(update.names.get("#n0").map(String::as_str), Some("address"));
+        assert_eq!(update.names.get("#n2").map(String::as_str), Some("name"));
+        assert_eq!(update.values.get(":v0"), Some(&text("Southbury")));
+        assert_eq!(update.values.get(":v1"), Some(&text("Jane")));
+    }
+
+    #[test]
+    fn tagged_expressions() {
+        let update = patch(&[("requirements.gpa[0]", text("gpa-3"))], &[])
+            .to_update_expression(ValueEncoding::Tagged, &[])
+            .unwrap();
+
+        assert_eq!(update.expression, "SET #n0.#n1.#n2.#n3[0] = :v0");
+        assert_eq!(
+            [
+                &update.names["#n0"],
+                &update.names["#n1"],
+                &update.names["#n2"],
+                &update.names["#n3"]
+            ],
+            ["requirements", "Map", "gpa", "List"]
+        );
+    }
+
+    #[test]
+    fn invalid_patches() {
+        let overlapping = patch(&[("address", text("1 Main St"))], &["address.town"]);
+        assert!(
+            overlapping
+                .to_update_expression(ValueEncoding::Attributes, &[])
+                .unwrap_err()
+                .contains("overlap")
+        );
+
+        let protected = patch(&[("HK", text("STUDENT#2"))], &[]);
+        assert!(
+            protected
+                .to_update_expression(ValueEncoding::Attributes, &["HK", "SK"])
+                .is_err()
+        );
+
+        let invalid = patch(&[], &["a..b"]);
+        assert!(
+            invalid
+                .to_update_expression(ValueEncoding::Attributes, &[])
+                .is_err()
+        );
+
+        let empty = ValuePatch::default()
+            .to_update_expression(ValueEncoding::Attributes, &[])
+            .unwrap();
+        assert_eq!(empty.expression, "");
+    }
+}
@@ -0,0 +1,402 @@
+use crate::common::{ExpandableInfo, ValueType};
+use std::collections::HashMap;
+
+/// A single step in a value path. Paths such as `a.b[2].c` are made up of map keys separated by
+/// dots, and list indexes inside square brackets.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+/// Splits a path such as `community_service[0].hours` into its segments.
+pub(super) fn parse_path(path: &str) -> Result<Vec<PathSegment>, String> {
+    let mut segments = Vec::new();
+
+    for part in path.split('.') {
+        let (key, mut indexes) = match part.find('[') {
+            Some(start) => part.split_at(start),
+            None => (part, ""),
+        };
+
+        if !key.is_empty() {
+            segments.push(PathSegment::Key(key.to_string()));
+        } else if indexes.is_empty() || !segments.is_empty() {
+            return Err(format!("Empty key in path {path:?}"));
+        }
+
+        while !indexes.is_empty() {
+            let end = indexes
+                .find(']')
+                .ok_or_else(|| format!("Missing \"]\" in path {path:?}"))?;
+            let index = indexes[1..end]
+                .parse::<usize>()
+                .map_err(|_| format!("Invalid index {:?} in path {path:?}", &indexes[1..end]))?;
+
+            segments.push(PathSegment::Index(index));
+            indexes = &indexes[end + 1..];
+            if !indexes.is_empty() && !indexes.starts_with('[') {
+                return Err(format!("Unexpected {indexes:?} in path {path:?}"));
+            }
+        }
+    }
+
+    Ok(segments)
+}
+
+/// Creates an empty container for the given segment to be set in.
+fn empty_container(segment: &PathSegment) -> ValueType {
+    match segment {
+        PathSegment::Key(_) => ValueType::Map(Some(HashMap::new())),
+        PathSegment::Index(_) => ValueType::List(Some(Vec::new())),
+    }
+}
+
+impl ValueType {
+    fn get_segments(&self, segments: &[PathSegment]) -> Option<&ValueType> {
+        segments
+            .iter()
+            .try_fold(self, |value, segment| match (value, segment) {
+                (ValueType::Map(Some(map)), PathSegment::Key(key)) => map.get(key),
+                (ValueType::List(Some(list)), PathSegment::Index(index)) => list.get(*index),
+                _ => None,
+            })
+    }
+
+    fn set_segments(&mut self, segments: &[PathSegment], value: ValueType) -> Result<(), String> {
+        let Some((segment, rest)) = segments.split_first() else {
+            *self = value;
+            return Ok(());
+        };
+
+        // Missing containers are created, so that setting `a.b` works on an empty map.
+        if matches!(
+            self,
+            ValueType::Map(None) | ValueType::List(None) | ValueType::Null
+        ) {
+            *self = empty_container(segment);
+        }
+
+        let child = match (self, segment) {
+            (ValueType::Map(Some(map)), PathSegment::Key(key)) => map
+                .entry(key.clone())
+                .or_insert_with(|| rest.first().map(empty_container).unwrap_or_default()),
+            (ValueType::List(Some(list)), PathSegment::Index(index)) => {
+                // Setting the index just past the end of the list appends to it.
+                if *index == list.len() {
+                    list.push(rest.first().map(empty_container).unwrap_or_default());
+                }
+                let length = list.len();
+                list.get_mut(*index).ok_or_else(|| {
+                    format!("Index {index} is out of bounds for a list of length {length}")
+                })?
+            }
+            (other, segment) => {
+                return Err(format!("Can't set {segment:?} on {other:?}"));
+            }
+        };
+
+        child.set_segments(rest, value)
+    }
+
+    fn remove_segments(&mut self, segments: &[PathSegment]) -> Option<ValueType> {
+        let (last, parents) = segments.split_last()?;
+
+        let mut parent = self;
+        for segment in parents {
+            parent = match (parent, segment) {
+                (ValueType::Map(Some(map)), PathSegment::Key(key)) => map.get_mut(key)?,
+                (ValueType::List(Some(list)), PathSegment::Index(index)) => list.get_mut(*index)?,
+                _ => return None,
+            };
+        }
+
+        match (parent, last) {
+            (ValueType::Map(Some(map)), PathSegment::Key(key)) => map.remove(key),
+            (ValueType::List(Some(list)), PathSegment::Index(index)) if *index < list.len() => {
+                Some(list.remove(*index))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// # Value Path
+///
+/// Typed access to nested values using paths such as `a.b[2].c`. Implemented for [`ValueType`],
+/// `HashMap<String, ValueType>` and [`ExpandableInfo`], which replaces chains such as:
+///
+/// ```ignore
+/// map.get("first_name").and_then(|v| v.as_string().ok().flatten()).unwrap_or_default()
+/// ```
+///
+/// with `map.get_str("first_name").unwrap_or_default()`. A plain key is a path with a single
+/// segment. Invalid paths are treated the same as missing values by the getters.
+pub trait ValuePath {
+    /// Gets the value at the given path.
+    fn get_path(&self, path: &str) -> Option<&ValueType>;
+
+    /// Sets the value at the given path, creating any missing maps and lists along the way. A list
+    /// index equal to the list's length appends the value to the list.
+    fn set_path(&mut self, path: &str, value: ValueType) -> Result<(), String>;
+
+    /// Removes the value at the given path, returning it if it existed.
+    fn remove_path(&mut self, path: &str) -> Option<ValueType>;
+
+    /// Deeply merges another value into this one. Maps are merged key by key, and any other value
+    /// in `other` replaces the value in `self`.
+    fn merge(&mut self, other: Self);
+
+    /// Gets the string at the given path. Returns `None` for any other type.
+    fn get_str(&self, path: &str) -> Option<&str> {
+        match self.get_path(path)? {
+            ValueType::String(Some(value)) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Gets the number at the given path. Strings that contain a number are also accepted, as
+    /// some forms store numbers as text.
+    fn get_f64(&self, path: &str) -> Option<f64> {
+        match self.get_path(path)? {
+            ValueType::Number(Some(value)) | ValueType::String(Some(value)) => {
+                value.trim().parse().ok()
+            }
+            _ => None,
+        }
+    }
+
+    /// Gets the list at the given path.
+    fn get_list(&self, path: &str) -> Option<&Vec<ValueType>> {
+        match self.get_path(path)? {
+            ValueType::List(Some(value)) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Gets the map at the given path.
+    fn get_map(&self, path: &str) -> Option<&HashMap<String, ValueType>> {
+        match self.get_path(path)? {
+            ValueType::Map(Some(value)) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl ValuePath for ValueType {
+    fn get_path(&self, path: &str) -> Option<&ValueType> {
+        self.get_segments(&parse_path(path).ok()?)
+    }
+
+    fn set_path(&mut self, path: &str, value: ValueType) -> Result<(), String> {
+        self.set_segments(&parse_path(path)?, value)
+    }
+
+    fn remove_path(&mut self, path: &str) -> Option<ValueType> {
+        self.remove_segments(&parse_path(path).ok()?)
+    }
+
+    fn merge(&mut self, other: Self) {
+        match (self, other) {
+            (ValueType::Map(Some(map)), ValueType::Map(Some(other))) => map.merge(other),
+            (value, other) => *value = other,
+        }
+    }
+}
+
+impl ValuePath for HashMap<String, ValueType> {
+    fn get_path(&self, path: &str) -> Option<&ValueType> {
+        let segments = parse_path(path).ok()?;
+        let (PathSegment::Key(key), rest) = segments.split_first()? else {
+            return None;
+        };
+
+        self.get(key)?.get_segments(rest)
+    }
+
+    fn set_path(&mut self, path: &str, value: ValueType) -> Result<(), String> {
+        let segments = parse_path(path)?;
+        let Some((PathSegment::Key(key), rest)) = segments.split_first() else {
+            return Err(format!("Path {path:?} must start with a key"));
+        };
+
+        self.entry(key.clone())
+            .or_insert_with(|| rest.first().map(empty_container).unwrap_or_default())
+            .set_segments(rest, value)
+    }
+
+    fn remove_path(&mut self, path: &str) -> Option<ValueType> {
+        let segments = parse_path(path).ok()?;
+        match segments.split_first()? {
+            (PathSegment::Key(key), []) => self.remove(key),
+            (PathSegment::Key(key), rest) => self.get_mut(key)?.remove_segments(rest),
+            _ => None,
+        }
+    }
+
+    fn merge(&mut self, other: Self) {
+        for (key, value) in other {
+            match self.get_mut(&key) {
+                Some(existing) => existing.merge(value),
+                None => {
+                    self.insert(key, value);
+                }
+            }
+        }
+    }
+}
+
+impl ValuePath for ExpandableInfo {
+    fn get_path(&self, path: &str) -> Option<&ValueType> {
+        self.data.get_path(path)
+    }
+
+    fn set_path(&mut self, path: &str, value: ValueType) -> Result<(), String> {
+        self.data.set_path(path, value)
+    }
+
+    fn remove_path(&mut self, path: &str) -> Option<ValueType> {
+        self.data.remove_path(path)
+    }
+
+    /// Merges the data of another [`ExpandableInfo`]. The subject is left unchanged.
+    fn merge(&mut self, other: Self) {
+        self.data.merge(other.data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::comparison::test_utils::{number, text};
+
+    fn create_student() -> HashMap<String, ValueType> {
+        let activity = |name: &str, hours: &str| {
+            ValueType::Map(Some(HashMap::from([
+                ("service_name".to_string(), text(name)),
+                ("service_hours".to_string(), number(hours)),
+            ])))
+        };
+
+        HashMap::from([
+            ("first_name".to_string(), text("Jane")),
+            ("gpa".to_string(), number("3.8")),
+            (
+                "community_involvement".to_string(),
+                ValueType::List(Some(vec![
+                    activity("Food Drive", "12"),
+                    activity("Library", "8"),
+                ])),
+            ),
+        ])
+    }
+
+    #[test]
+    fn parse_paths() {
+        assert_eq!(
+            parse_path("a.b[2].c"),
+            Ok(vec![
+                PathSegment::Key("a".to_string()),
+                PathSegment::Key("b".to_string()),
+                PathSegment::Index(2),
+                PathSegment::Key("c".to_string()),
+            ])
+        );
+        assert_eq!(
+            parse_path("a[0][1]"),
+            Ok(vec![
+                PathSegment::Key("a".to_string()),
+                PathSegment::Index(0),
+                PathSegment::Index(1),
+            ])
+        );
+        assert!(parse_path("a..b").is_err());
+        assert!(parse_path("a.[0]").is_err());
+        assert!(parse_path("").is_err());
+        assert!(parse_path("a[x]").is_err());
+        assert!(parse_path("a[0").is_err());
+        assert!(parse_path("a[0]b").is_err());
+    }
+
+    #[test]
+    fn typed_getters() {
+        let student = create_student();
+
+        assert_eq!(student.get_str("first_name"), Some("Jane"));
+        assert_eq!(student.get_str("gpa"), None);
+        assert_eq!(student.get_f64("gpa"), Some(3.8));
+        assert_eq!(
+            student.get_list("community_involvement").map(Vec::len),
+            Some(2)
+        );
+        assert_eq!(
+            student.get_str("community_involvement[1].service_name"),
+            Some("Library")
+        );
+        assert_eq!(
+            student.get_f64("community_involvement[0].service_hours"),
+            Some(12.0)
+        );
+        assert_eq!(
+            student.get_path("community_involvement[2].service_name"),
+            None
+        );
+        assert_eq!(student.get_path("first_name.nested"), None);
+    }
+
+    #[test]
+    fn set_and_remove_paths() {
+        let mut info = ExpandableInfo::new("student");
+        info.data = create_student();
+
+        info.set_path("address.town", text("Middlebury")).unwrap();
+        assert_eq!(info.get_str("address.town"), Some("Middlebury"));
+
+        info.set_path("community_involvement[2].service_name", text("Park"))
+            .unwrap();
+        assert_eq!(
+            info.get_list("community_involvement").map(Vec::len),
+            Some(3)
+        );
+        assert!(
+            info.set_path("community_involvement[5]", text("Gap"))
+                .is_err()
+        );
+        assert!(info.set_path("first_name.nested", text("Oops")).is_err());
+
+        assert_eq!(
+            info.remove_path("community_involvement[0].service_hours"),
+            Some(number("12"))
+        );
+        assert_eq!(
+            info.get_path("community_involvement[0].service_hours"),
+            None
+        );
+        assert_eq!(info.remove_path("community_involvement[0]").is_some(), true);
+        assert_eq!(
+            info.get_str("community_involvement[0].service_name"),
+            Some("Library")
+        );
+        assert_eq!(info.remove_path("first_name"), Some(text("Jane")));
+        assert_eq!(info.remove_path("first_name"), None);
+    }
+
+    #[test]
+    fn merge_maps() {
+        let mut student = create_student();
+        let mut address = HashMap::new();
+        address.set_path("address.town", text("Southbury")).unwrap();
+        address.set_path("address.state", text("CT")).unwrap();
+        student.merge(address);
+
+        let mut update = HashMap::new();
+        update.set_path("address.town", text("Middlebury")).unwrap();
+        update.set_path("gpa", number("3.9")).unwrap();
+        student.merge(update);
+
+        assert_eq!(student.get_str("address.town"), Some("Middlebury"));
+        assert_eq!(student.get_str("address.state"), Some("CT"));
+        assert_eq!(student.get_f64("gpa"), Some(3.9));
+        assert_eq!(student.get_str("first_name"), Some("Jane"));
+    }
+}
@@ -1,3 +1,4 @@
+use base64::Engine;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fmt::Display;
@@ -19,6 +20,19 @@ pub enum ValueType {
     List(Option<Vec<ValueType>>),
     /// Indicates a `HashMap<String, ValueType>`.
     Map(Option<HashMap<String, ValueType>>),
+    /// Indicates a `bool` value.
+    Bool(Option<bool>),
+    /// Indicates a value that is explicitly empty, such as a DynamoDB `NULL` attribute.
+    Null,
+    /// Indicates raw bytes, such as a DynamoDB `B` attribute.
+    Binary(Option<Vec<u8>>),
+    /// Indicates a set of unique strings, such as a DynamoDB `SS` attribute.
+    StringSet(Option<Vec<String>>),
+    /// Indicates a set of unique numbers, such as a DynamoDB `NS` attribute. Numbers are stored
+    /// as strings, the same as in `Number`.
+    NumberSet(Option<Vec<String>>),
+    /// Indicates a set of unique byte strings, such as a DynamoDB `BS` attribute.
+    BinarySet(Option<Vec<Vec<u8>>>),
 }
 
 impl ValueType {
@@ -94,6 +108,67 @@ impl ValueType {
             Err(self.clone())
         }
     }
+
+    /// Returns true if this is a `Bool`, otherwise false.
+    pub fn is_bool(&self) -> bool {
+        matches!(self, ValueType::Bool(_))
+    }
+
+    /// Attempts to get this value as a `bool`.
+    pub fn as_bool(&self) -> Result<Option<bool>, Self> {
+        if let ValueType::Bool(v) = self {
+            Ok(*v)
+        } else {
+            Err(self.clone())
+        }
+    }
+
+    /// Returns true if this is `Null`, otherwise false.
+    pub fn is_null(&self) -> bool {
+        matches!(self, ValueType::Null)
+    }
+
+    /// Returns true if this value holds nothing, either because it is `Null` or because its inner
+    /// value is `None`.
+    pub fn is_empty(&self) -> bool {
+        match self {
+            ValueType::String(v) | ValueType::Number(v) => v.is_none(),
+            ValueType::List(v) => v.is_none(),
+            ValueType::Map(v) => v.is_none(),
+            ValueType::Bool(v) => v.is_none(),
+            ValueType::Null => true,
+            ValueType::Binary(v) => v.is_none(),
+            ValueType::StringSet(v) | ValueType::NumberSet(v) => v.is_none(),
+            ValueType::BinarySet(v) => v.is_none(),
+        }
+    }
+
+    /// Attempts to get this value as raw bytes.
+    pub fn as_binary(&self) -> Result<Option<Vec<u8>>, Self> {
+        if let ValueType::Binary(v) = self {
+            Ok(v.clone())
+        } else {
+            Err(self.clone())
+        }
+    }
+
+    /// Attempts to get this value as a set of strings.
+    pub fn as_string_set(&self) -> Result<Option<Vec<String>>, Self> {
+        if let ValueType::StringSet(v) = self {
+            Ok(v.clone())
+        } else {
+            Err(self.clone())
+        }
+    }
+
+    /// Attempts to get this value as a set of numbers, which are stored as strings.
+    pub fn as_number_set(&self) -> Result<Option<Vec<String>>, Self> {
+        if let ValueType::NumberSet(v) = self {
+            Ok(v.clone())
+        } else {
+            Err(self.clone())
+        }
+    }
 }
 
 impl Default for ValueType {
@@ -109,29 +184,69 @@ impl Display for ValueType {
             ValueType::Number(n) => n.clone().unwrap_or_default(),
             ValueType::List(l) => format!("{l:?}"),
             ValueType::Map(m) => format!("{m:?}"),
+            ValueType::Bool(b) => b.map(|b| b.to_string()).unwrap_or_default(),
+            ValueType::Null => String::new(),
+            ValueType::Binary(b) => base64::engine::general_purpose::STANDARD
+                .encode(b.clone().unwrap_or_default()),
+            ValueType::StringSet(s) => format!("{s:?}"),
+            ValueType::NumberSet(n) => format!("{n:?}"),
+            ValueType::BinarySet(b) => format!("{b:?}"),
         };
         write!(f, "{}", str)
     }
 }
 
 #[cfg(feature = "ssr")]
-use aws_sdk_dynamodb::types::AttributeValue;
+use aws_sdk_dynamodb::{primitives::Blob, types::AttributeValue};
+
+/// Converts a DynamoDB attribute into a `ValueType`. Every attribute type is supported except for
+/// any that are added to DynamoDB in the future, which are reported as errors instead of being
+/// silently replaced.
+#[cfg(feature = "ssr")]
+impl TryFrom<AttributeValue> for ValueType {
+    type Error = String;
+
+    fn try_from(attr: AttributeValue) -> Result<Self, Self::Error> {
+        Ok(match attr {
+            AttributeValue::S(s) => ValueType::String(Some(s)),
+            AttributeValue::N(n) => ValueType::Number(Some(n)),
+            AttributeValue::Bool(b) => ValueType::Bool(Some(b)),
+            AttributeValue::Null(_) => ValueType::Null,
+            AttributeValue::B(b) => ValueType::Binary(Some(b.into_inner())),
+            AttributeValue::Ss(s) => ValueType::StringSet(Some(s)),
+            AttributeValue::Ns(n) => ValueType::NumberSet(Some(n)),
+            AttributeValue::Bs(b) => {
+                ValueType::BinarySet(Some(b.into_iter().map(Blob::into_inner).collect()))
+            }
+            AttributeValue::L(l) => ValueType::List(Some(
+                l.into_iter()
+                    .map(ValueType::try_from)
+                    .collect::<Result<_, _>>()?,
+            )),
+            AttributeValue::M(m) => ValueType::Map(Some(
+                m.into_iter()
+                    .map(|(k, v)| ValueType::try_from(v).map(|v| (k, v)))
+                    .collect::<Result<_, _>>()?,
+            )),
+            other => return Err(format!("Unsupported DynamoDB attribute type: {other:?}")),
+        })
+    }
+}
 
+/// Converts a DynamoDB attribute into a `ValueType`, using `Null` for unsupported attribute
+/// types. Use [`ValueType::try_from`] to handle those types separately.
 #[cfg(feature = "ssr")]
 impl From<&AttributeValue> for ValueType {
     fn from(attr: &AttributeValue) -> Self {
-        match attr {
-            AttributeValue::S(s) => ValueType::String(Some(s.clone())),
-            AttributeValue::N(n) => ValueType::Number(Some(n.clone())),
-            AttributeValue::L(l) => ValueType::List(Some(l.iter().map(|v| v.into()).collect())),
-            AttributeValue::M(m) => {
-                ValueType::Map(Some(m.iter().map(|(k, v)| (k.clone(), v.into())).collect()))
-            }
-            _ => ValueType::default(),
-        }
+        ValueType::try_from(attr.clone()).unwrap_or_else(|err| {
+            leptos::logging::error!("{}", err);
+            ValueType::Null
+        })
     }
 }
 
+/// Converts a `ValueType` into a DynamoDB attribute. DynamoDB doesn't allow empty sets, so empty
+/// or missing sets are stored as `NULL`.
 #[cfg(feature = "ssr")]
 impl From<ValueType> for AttributeValue {
     fn from(value: ValueType) -> Self {
@@ -150,6 +265,98 @@ impl From<ValueType> for AttributeValue {
                     .map(|(k, v)| (k, v.into()))
                     .collect(),
             ),
+            ValueType::Bool(Some(b)) => AttributeValue::Bool(b),
+            ValueType::Bool(None) | ValueType::Null => AttributeValue::Null(true),
+            ValueType::Binary(b) => AttributeValue::B(Blob::new(b.unwrap_or_default())),
+            ValueType::StringSet(Some(s)) if !s.is_empty() => AttributeValue::Ss(s),
+            ValueType::NumberSet(Some(n)) if !n.is_empty() => AttributeValue::Ns(n),
+            ValueType::BinarySet(Some(b)) if !b.is_empty() => {
+                AttributeValue::Bs(b.into_iter().map(Blob::new).collect())
+            }
+            ValueType::StringSet(_) | ValueType::NumberSet(_) | ValueType::BinarySet(_) => {
+                AttributeValue::Null(true)
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use super::*;
+    use proptest::collection::{hash_map, hash_set, vec};
+    use proptest::prelude::*;
+
+    /// Generates any value that DynamoDB can store. Sets are never empty, and numbers are always
+    /// valid, as DynamoDB rejects anything else.
+    fn value_strategy() -> impl Strategy<Value = ValueType> {
+        let leaf = prop_oneof![
+            any::<String>().prop_map(|s| ValueType::String(Some(s))),
+            any::<i64>().prop_map(|n| ValueType::Number(Some(n.to_string()))),
+            any::<bool>().prop_map(|b| ValueType::Bool(Some(b))),
+            Just(ValueType::Null),
+            vec(any::<u8>(), 0..16).prop_map(|b| ValueType::Binary(Some(b))),
+            hash_set(any::<String>(), 1..5)
+                .prop_map(|s| ValueType::StringSet(Some(s.into_iter().collect()))),
+            hash_set(any::<i64>(), 1..5).prop_map(|n| {
+                ValueType::NumberSet(Some(n.into_iter().map(|n| n.to_string()).collect()))
+            }),
+            hash_set(vec(any::<u8>(), 0..8), 1..4)
+                .prop_map(|b| ValueType::BinarySet(Some(b.into_iter().collect()))),
+        ];
+
+        leaf.prop_recursive(3, 32, 4, |inner| {
+            prop_oneof![
+                vec(inner.clone(), 0..4).prop_map(|l| ValueType::List(Some(l))),
+                hash_map("[a-z_]{1,8}", inner, 0..4).prop_map(|m| ValueType::Map(Some(m))),
+            ]
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn attribute_round_trip(value in value_strategy()) {
+            let attr = AttributeValue::from(value.clone());
+            prop_assert_eq!(ValueType::try_from(attr), Ok(value));
+        }
+
+        #[test]
+        fn serde_round_trip(value in value_strategy()) {
+            let attr: AttributeValue = serde_dynamo::to_attribute_value(&value).unwrap();
+            let parsed: ValueType = serde_dynamo::from_attribute_value(attr).unwrap();
+            prop_assert_eq!(parsed, value);
         }
     }
+
+    #[test]
+    fn previously_unsupported_attributes() {
+        let attr = AttributeValue::M(HashMap::from([
+            ("opted_in".to_string(), AttributeValue::Bool(true)),
+            ("middle_name".to_string(), AttributeValue::Null(true)),
+            (
+                "tags".to_string(),
+                AttributeValue::Ss(vec!["a".to_string(), "b".to_string()]),
+            ),
+            ("scores".to_string(), AttributeValue::Ns(vec!["1".to_string()])),
+            ("avatar".to_string(), AttributeValue::B(Blob::new(vec![1, 2, 3]))),
+        ]));
+
+        let value = ValueType::from(&attr);
+        let map = value.as_map().unwrap().unwrap();
+        assert_eq!(map["opted_in"], ValueType::Bool(Some(true)));
+        assert!(map["middle_name"].is_null());
+        assert_eq!(map["tags"].as_string_set().unwrap().unwrap(), vec!["a", "b"]);
+        assert_eq!(map["scores"].as_number_set().unwrap().unwrap(), vec!["1"]);
+        assert_eq!(map["avatar"].as_binary().unwrap().unwrap(), vec![1, 2, 3]);
+
+        // Saving the value again must not change it.
+        assert_eq!(AttributeValue::from(value), attr);
+    }
+
+    #[test]
+    fn empty_sets_are_stored_as_null() {
+        assert_eq!(
+            AttributeValue::from(ValueType::StringSet(Some(vec![]))),
+            AttributeValue::Null(true)
+        );
+    }
 }
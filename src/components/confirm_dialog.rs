@@ -0,0 +1,65 @@
+use crate::components::ActionButton;
+use leptos::html::Dialog;
+use leptos::prelude::*;
+
+/// # Confirm Dialog Component
+///
+/// A modal dialog that asks for confirmation before an action that can't be undone. The dialog
+/// is open while `message` is set, and both buttons should clear it once they're done.
+///
+/// Example usage:
+/// ```ignore
+/// let pending_delete = RwSignal::new(None::<String>);
+///
+/// view! {
+///     <ConfirmDialog
+///         message=pending_delete
+///         on_confirm=Callback::new(move |_| delete())
+///         on_cancel=Callback::new(move |_| pending_delete.set(None))
+///     />
+/// }
+/// ```
+#[component]
+pub fn ConfirmDialog(
+    /// The question to ask, which opens the dialog when set.
+    #[prop(into)]
+    message: Signal<Option<String>>,
+    on_confirm: Callback<()>,
+    on_cancel: Callback<()>,
+    /// Whether the confirm button is disabled, such as while the action is pending.
+    #[prop(optional, into)]
+    disabled: Signal<bool>,
+) -> impl IntoView {
+    let dialog_ref = NodeRef::<Dialog>::new();
+
+    Effect::new(move || {
+        if let Some(dialog) = dialog_ref.get() {
+            if message.get().is_some() {
+                if !dialog.open() {
+                    let _ = dialog.show_modal();
+                }
+            } else {
+                dialog.close();
+            }
+        }
+    });
+
+    view! {
+        <dialog
+            node_ref=dialog_ref
+            class="m-auto p-5 max-w-lg rounded-lg shadow-xl/50
+            backdrop:backdrop-blur-xs backdrop:transition-backdrop-filter"
+            // Pressing escape closes the dialog, which counts as cancelling.
+            on:cancel=move |_: leptos::ev::Event| on_cancel.run(())
+        >
+            <h2 class="text-2xl font-bold">"Are you sure?"</h2>
+            <p class="mb-3">{move || message.get()}</p>
+            <div class="flex mx-auto items-center justify-center">
+                <ActionButton on:click=move |_| on_confirm.run(()) disabled=disabled>
+                    "Yes"
+                </ActionButton>
+                <ActionButton on:click=move |_| on_cancel.run(())>"No"</ActionButton>
+            </div>
+        </dialog>
+    }
+}
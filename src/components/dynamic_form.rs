@@ -0,0 +1,106 @@
+use crate::common::{FieldErrors, FormDefinition, FormSection, ValueType, student_form_checks};
+use crate::components::{AsyncValidator, ValidatedForm};
+use leptos::prelude::*;
+use std::collections::HashMap;
+
+/// # Dynamic Form Component
+///
+/// Renders a [`FormDefinition`] as a [`ValidatedForm`], with every section shown in order. Each
+/// input edits its own key in the `data_map`, so this can be used with the `data_map` of a
+/// student form controller to render any of the student forms without writing their markup.
+/// The form's [`FormCheck`]s are run as the form is filled out.
+///
+/// [`FormCheck`]: crate::common::FormCheck
+///
+/// Example usage:
+/// ```ignore
+/// let controller = use_student_form("academics", true, false);
+/// view! {
+///     <DynamicForm
+///         definition=definition
+///         data_map=controller.data_map
+///         on_submit=controller.submit_action
+///         disabled=controller.submit_pending
+///     />
+/// }
+/// ```
+#[component]
+pub fn DynamicForm(
+    /// The definition of the form to render.
+    #[prop(into)] definition: Signal<FormDefinition>,
+    /// The data map that every input reads from and writes to.
+    #[prop()] data_map: RwSignal<HashMap<String, ValueType>>,
+    /// A callback that runs when the form successfully validates.
+    #[prop(into)] on_submit: Callback<()>,
+    /// A `Signal` that determines when the submit button should be disabled.
+    #[prop(optional, into)] disabled: Signal<bool>,
+    /// A `Signal` that shows a notice above the submit button when the form has unsaved changes.
+    #[prop(optional, into)] unsaved_changes: Signal<bool>,
+    /// A callback that discards unsaved changes.
+    #[prop(optional_no_strip, into)] on_discard: Option<Callback<()>>,
+    /// When the unsaved changes were last autosaved as a draft.
+    #[prop(optional_no_strip, into)] draft_saved_at: Option<Signal<Option<String>>>,
+    /// Errors returned by the server, keyed by data member.
+    #[prop(optional, into)] field_errors: Signal<FieldErrors>,
+    /// Checks that run in the background, such as checks that use a server function.
+    #[prop(optional)] async_validators: Vec<AsyncValidator>,
+) -> impl IntoView {
+    let title = Signal::derive(move || definition.with(|definition| definition.title.clone()));
+    let description =
+        Signal::derive(move || definition.with(|definition| definition.description.clone()));
+    let checks = definition.with_untracked(|definition| student_form_checks(&definition.id).to_vec());
+
+    view! {
+        <ValidatedForm
+            title=title
+            description=description
+            on_submit=on_submit
+            disabled=disabled
+            unsaved_changes=unsaved_changes
+            on_discard=on_discard
+            draft_saved_at=draft_saved_at
+            field_errors=field_errors
+            data_map=data_map
+            checks=checks
+            async_validators=async_validators
+        >
+            {move || {
+                definition
+                    .get()
+                    .sections
+                    .into_iter()
+                    .map(|section| view! { <DynamicFormSection section=section data_map=data_map /> })
+                    .collect_view()
+            }}
+        </ValidatedForm>
+    }
+}
+
+/// A single section of a [`DynamicForm`]. The header is only shown if the section has a title or
+/// a description.
+#[component]
+fn DynamicFormSection(
+    section: FormSection,
+    data_map: RwSignal<HashMap<String, ValueType>>,
+) -> impl IntoView {
+    let FormSection { title, description, fields } = section;
+    let show_header = !title.is_empty() || !description.is_empty();
+
+    view! {
+        <div class="flex flex-col">
+            <Show when=move || show_header>
+                <div class="flex flex-col gap-1 m-1.5 mt-4">
+                    <span class="text-xl font-bold">{title.clone()}</span>
+                    <span>{description.clone()}</span>
+                </div>
+            </Show>
+            {fields
+                .into_iter()
+                .map(|field| {
+                    let name = field.key().to_string();
+                    field.into_view(data_map, name)
+                })
+                .collect_view()}
+        </div>
+    }
+}
@@ -0,0 +1,119 @@
+use crate::common::{DatePurpose, WindowState};
+use crate::components::{Banner, Loading};
+use crate::pages::api::get_form_window;
+use leptos::logging::error;
+use leptos::prelude::*;
+
+/// # Form Window Gate Component
+///
+/// Only shows its children while the window of a [`DatePurpose`] is open, which is set by the
+/// important dates. Otherwise, a notice says when the window opens or closed. If a student's
+/// access token is given, their extension is applied.
+///
+/// The server checks the same window before saving anything, so this only keeps people from
+/// filling out forms that can't be submitted. If the window can't be loaded, the children are
+/// shown and the server decides.
+///
+/// Example usage:
+/// ```ignore
+/// view! {
+///     <FormWindowGate purpose=DatePurpose::StudentForms access_token=access_token>
+///         <StudentFinancialPage />
+///     </FormWindowGate>
+/// }
+/// ```
+#[component]
+pub fn FormWindowGate(
+    /// The purpose of the dates that open and close the window.
+    purpose: DatePurpose,
+    /// The access token of the student whose extension applies, if any.
+    #[prop(optional, into)]
+    access_token: Signal<Option<String>>,
+    /// Shows the provider banner with the closed notice, for provider pages that show their own
+    /// banner.
+    #[prop(optional)]
+    with_banner: bool,
+    children: ChildrenFn,
+) -> impl IntoView {
+    let window = Resource::new(
+        move || access_token.get(),
+        move |access_token| get_form_window(purpose, access_token),
+    );
+    let children = StoredValue::new(children);
+
+    view! {
+        <Transition fallback=Loading>
+            {move || {
+                window
+                    .get()
+                    .map(|result| {
+                        let state = result.unwrap_or_else(|err| {
+                            error!("Couldn't check whether the form is open: {}", err);
+                            WindowState::Unscheduled
+                        });
+                        let extension = match &state {
+                            WindowState::Extended(_) => Some(state.describe(purpose)),
+                            _ => None,
+                        };
+
+                        if state.is_open() {
+                            view! {
+                                {extension
+                                    .map(|extension| {
+                                        view! {
+                                            <div class="p-2 text-center text-sm bg-yellow-100 text-yellow-800">
+                                                {extension}
+                                            </div>
+                                        }
+                                    })}
+                                {children.read_value()()}
+                            }
+                                .into_any()
+                        } else {
+                            view! {
+                                {with_banner
+                                    .then(|| {
+                                        view! {
+                                            <Banner
+                                                title="R15 Scholarships"
+                                                logo="/PHS_Stacked_Acronym.png"
+                                                path="/providers"
+                                            />
+                                        }
+                                    })}
+                                <FormClosedNotice purpose=purpose message=state.describe(purpose) />
+                            }
+                                .into_any()
+                        }
+                    })
+            }}
+        </Transition>
+    }
+}
+
+/// Says that a purpose's window is closed, and who to contact about it.
+#[component]
+fn FormClosedNotice(purpose: DatePurpose, message: String) -> impl IntoView {
+    let (title, contact) = match purpose {
+        DatePurpose::StudentForms => (
+            "Form Closed",
+            "If you believe this is an error, please contact the guidance office.",
+        ),
+        DatePurpose::ProviderForms => (
+            "Scholarship Editing Closed",
+            "If you need to change a scholarship, please contact the scholarship administrators.",
+        ),
+        DatePurpose::DecisionWindow => (
+            "Applicant Review Closed",
+            "If you need to review applicants, please contact the scholarship administrators.",
+        ),
+    };
+
+    view! {
+        <div class="flex flex-col items-center mx-auto mt-10">
+            <h1 class="text-2xl font-bold">{title}</h1>
+            <p class="text-lg">{message}</p>
+            <p class="text-lg">{contact}</p>
+        </div>
+    }
+}
@@ -123,12 +123,13 @@ pub fn CheckboxList(
     let controller = use_selectable_list(
         data_member.clone(),
         data_map.clone(),
-        required.clone()
+        required.clone(),
+        label.clone(),
     );
 
     //#region Render Logic
     view! {
-        <div class="flex flex-col">
+        <div class="flex flex-col" data-member=controller.path>
             <div class="m-1.5 mt-0 mb-0">
                 <span class="font-bold">{label}</span>
                 {items
@@ -91,11 +91,12 @@ pub fn ChipsList(
     let controller = use_selectable_list(
         data_member.clone(),
         data_map.clone(),
-        required.clone()
+        required.clone(),
+        label.clone(),
     );
 
     view! {
-        <div class="flex flex-col">
+        <div class="flex flex-col" data-member=controller.path>
             <div class="flex flex-col gap-2 m-1.5 mt-0 mb-0">
                 <span class="font-bold">{label}</span>
                 <div class="flex flex-row flex-wrap gap-2 mb-1">
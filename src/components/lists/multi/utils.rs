@@ -8,6 +8,8 @@ pub struct ListController {
     pub dirty: RwSignal<bool>,
     pub show_errors: Signal<bool>,
     pub selected_list: RwSignal<Vec<String>>,
+    /// The list's path within the form's data, which is used to find it from the error summary.
+    pub path: Signal<String>,
 }
 
 fn value_type_to_vec(value_type: &ValueType) -> Vec<String> {
@@ -25,7 +27,8 @@ fn value_type_to_vec(value_type: &ValueType) -> Vec<String> {
 pub fn use_selectable_list(
     data_member: Signal<String>,
     data_map: RwSignal<HashMap<String, ValueType>>,
-    required: Signal<bool>
+    required: Signal<bool>,
+    label: String,
 ) -> ListController {
     //#region Central List Logic
 
@@ -82,22 +85,16 @@ pub fn use_selectable_list(
             ValidationState::Valid
         }
     });
+    let error = validation_context.with_field_error(data_member.get_untracked(), error);
 
     let show_errors = Signal::derive(move || dirty.get() && matches!(error.get(), ValidationState::Invalid(_)));
 
-    let validator = RwSignal::new(InputState::new(
+    validation_context.register(InputState::new(
         data_member.get(),
         error.clone(),
         dirty.clone()
-    ));
+    ).with_label(label));
 
-    validation_context.validators.update(|list| list.push(validator.clone()));
-
-    on_cleanup(move || {
-        validation_context.validators.update(|list| {
-            list.retain(|v| *v.get_untracked().input_name != *validator.get_untracked().input_name)
-        });
-    });
     
     //#endregion
 
@@ -106,5 +103,6 @@ pub fn use_selectable_list(
         dirty,
         show_errors,
         selected_list,
+        path: validation_context.path(data_member.get_untracked()),
     }
 }
@@ -201,24 +201,18 @@ pub fn RadioList(
         .expect("Could not find FormValidationRegistry context");
 
     let error = RwSignal::new(validate(selected_value.get_untracked(), required.get_untracked()));
+    let field_error = validation_context.with_field_error(data_member.get_untracked(), error);
     let dirty = RwSignal::new(false);
     let show_errors = Signal::derive(move || {
-        dirty.get() && matches!(error.get(), ValidationState::Invalid(_))
+        dirty.get() && matches!(field_error.get(), ValidationState::Invalid(_))
     });
 
-    let input_state = RwSignal::new(InputState::new(
+    validation_context.register(InputState::new(
         data_member.get_untracked(),
-        error.clone(),
+        field_error,
         dirty.clone()
-    ));
+    ).with_label(label.clone()));
 
-    validation_context.validators.update(|list| list.push(input_state));
-
-    on_cleanup(move || {
-        validation_context.validators.update(|list| {
-            list.retain(|v| *v.get_untracked().input_name != *input_state.get_untracked().input_name)
-        });
-    });
 
     // When the selected value changes, update the data map at the correct location and validate.
     Effect::new(move || {
@@ -232,7 +226,7 @@ pub fn RadioList(
     //#region Render Logic
 
     view! {
-        <div class="flex flex-col flex-1">
+        <div class="flex flex-col flex-1" data-member=validation_context.path(data_member.get_untracked())>
             <div class="m-1.5 mt-0 mb-0">
                 <span class="font-bold">{label}</span>
                 {items
@@ -285,7 +279,7 @@ pub fn RadioList(
             <Show when=move || show_errors.get()>
                 <div class="text-red-600 text-sm mr-1.5 ml-1.5">
                     {move || {
-                        match error.get() {
+                        match field_error.get() {
                             ValidationState::Invalid(msg) => msg,
                             _ => "There is no error - should not see this message.".to_string(),
                         }
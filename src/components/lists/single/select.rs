@@ -39,22 +39,17 @@ pub fn Select(
     //#endregion
     //#region Validation Logic
 
-    let error = Memo::new(move |_| validate(required.get(), value.get()));
-    let dirty = RwSignal::new(false);
-    let show_errors = Signal::derive(move || dirty.get() && matches!(error.get(), ValidationState::Invalid(_)));
-
-    let input_state = RwSignal::new(InputState::new(data_member.get(), error.clone(), dirty.clone()));
-
     let validation_context = use_validation_context()
         .expect("Could not find FormValidationRegistry context");
 
-    validation_context.validators.update(|list| list.push(input_state));
+    let error = Memo::new(move |_| validate(required.get(), value.get()));
+    let error = validation_context.with_field_error(data_member.get_untracked(), error);
+    let dirty = RwSignal::new(false);
+    let show_errors = Signal::derive(move || dirty.get() && matches!(error.get(), ValidationState::Invalid(_)));
 
-    on_cleanup(move || {
-        validation_context.validators.update(|list| {
-            list.retain(|v| *v.get_untracked().input_name != *input_state.get_untracked().input_name)
-        });
-    });
+    validation_context.register(
+        InputState::new(data_member.get(), error.clone(), dirty.clone()).with_label(label.clone())
+    );
 
     //#endregion
     //#region Event Logic
@@ -70,7 +65,7 @@ pub fn Select(
     //#endregion
 
     view! {
-        <div class="flex flex-col flex-1">
+        <div class="flex flex-col flex-1" data-member=validation_context.path(data_member.get_untracked())>
             <div class="flex flex-1">
                 <label class="flex flex-col flex-1">
                     <span class="ml-1.5 mb-0 font-bold">{label}</span>
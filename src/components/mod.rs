@@ -1,9 +1,12 @@
 mod action_button;
 mod banner;
+mod confirm_dialog;
 mod dashboard_button;
 mod data_display;
 mod date;
+mod dynamic_form;
 mod file_drop;
+mod form_window;
 mod header;
 mod lists;
 mod loading;
@@ -18,7 +21,7 @@ mod utils;
 mod validated_form;
 
 pub use self::{
-    action_button::*, banner::*, dashboard_button::*, data_display::*, date::*, file_drop::*,
+    action_button::*, banner::*, confirm_dialog::*, dashboard_button::*, data_display::*, date::*, dynamic_form::*, file_drop::*, form_window::*,
     header::*, lists::*, loading::*, multi_entry::*, panel::*, row::*, tabs::*, text_field::*,
     toasts::*, validated_form::*,
 };
@@ -1,5 +1,6 @@
-use crate::common::{InputType, ValueType};
-use crate::components::ActionButton;
+use crate::common::{EntryLimits, InputType, ValueType};
+use crate::components::{use_validation_context, ActionButton, InputState, ValidationState};
+use leptos::ev::{DragEvent, KeyboardEvent};
 use leptos::prelude::*;
 use std::collections::HashMap;
 use leptos::logging::log;
@@ -56,6 +57,11 @@ fn new_entry() -> ValueType {
 /// `InputType::Select` means that all `Entry` components will render a text and dropdown input
 /// component. The users shall be able to use these components to alter the data.
 ///
+/// Entries can be reordered by dragging their handle, or by focusing it and using the arrow keys.
+/// Each entry can be collapsed into a one-line summary. The inputs of every entry register with
+/// the parent form under paths such as `sports[0].sport_name`, so an invalid entry blocks the
+/// form, even while it's collapsed. The number of entries can be limited using `min` and `max`.
+///
 /// Example usage:
 /// ```
 /// let data_signal = RwSignal::new(HashMap::new());
@@ -67,6 +73,7 @@ fn new_entry() -> ValueType {
 ///             InputType::Text("first_name", "First Name:", "John"),
 ///             InputType::Text("last_name", "Last Name:", "Smith")
 ///         ]
+///         max = 5
 ///     />
 /// }
 /// ```
@@ -87,6 +94,12 @@ pub fn MultiEntry(
     /// The type and order of the inputs for each `Entry` component within this `MultiEntry` component.
     #[prop(optional, into)]
     schema: Signal<Vec<InputType>>,
+    /// The fewest entries that the form can be submitted with.
+    #[prop(optional, into)]
+    min: Signal<usize>,
+    /// The most entries that can be added, if there's a limit.
+    #[prop(optional, into)]
+    max: Signal<Option<usize>>,
 ) -> impl IntoView {
     // Make the values within the list reactive. Each of these values should be a ValueType::Map,
     // so we can pass these directly to the application.
@@ -108,16 +121,62 @@ pub fn MultiEntry(
         });
     };
 
+    //#region Form Validation
+
+    let validation_context = use_validation_context()
+        .expect("FormValidationRegistry was not found");
+
+    let dirty = RwSignal::new(false);
+    let error = Signal::derive(move || {
+        validate_count(data_list.with(Vec::len), min.get(), max.get())
+    });
+    let error = validation_context.with_field_error(data_member.get_untracked(), error);
+    let show_errors = Signal::derive(move || {
+        dirty.get() && matches!(error.get(), ValidationState::Invalid(_))
+    });
+
+    validation_context.register(
+        InputState::new(data_member.get_untracked(), error, dirty).with_label(label.get_untracked())
+    );
+
+    //#endregion
+
+    let at_max = Memo::new(move |_| {
+        max.get().is_some_and(|max| data_list.with(Vec::len) >= max)
+    });
+
     let add_entry = move |_| data_map.update(|map| {
+        if at_max.get_untracked() {
+            return;
+        }
         log!("Adding new entry.");
         let mut list = data_list.get_untracked();
         
         list.push(new_entry());
         map.insert(data_member.get(), ValueType::List(Some(list)));
+        dirty.set(true);
     });
 
+    // Moves the entry with the given UUID to a new position in the list.
+    let move_entry = move |uuid: &str, target: usize| {
+        let mut list = data_list.get_untracked();
+        let Some(index) = list.iter().position(|entry| get_uuid(entry.clone()).as_deref() == Some(uuid)) else {
+            return;
+        };
+        if target >= list.len() || target == index {
+            return;
+        }
+
+        let entry = list.remove(index);
+        list.insert(target, entry);
+        update_parent_list(list);
+    };
+
+    // The UUID of the entry that's being dragged, if there is one.
+    let dragged = RwSignal::new(None::<String>);
+
     view! {
-        <div class="flex flex-col flex-1">
+        <div class="flex flex-col flex-1" data-member=validation_context.path(data_member.get_untracked())>
             <div class="flex flex-col gap-2 p-2">
                 <span class="font-bold">{label}</span>
                 <Show when=move || !description.get().is_empty()>
@@ -143,6 +202,14 @@ pub fn MultiEntry(
                             let unique_id = Memo::new(move |_| {
                                 get_uuid_from_map(&map_signal.get()).unwrap_or_default()
                             });
+                            let index = Memo::new(move |_| {
+                                let uuid = unique_id.get();
+                                data_list.with(|list| {
+                                    list.iter()
+                                        .position(|entry| get_uuid(entry.clone()) == Some(uuid.clone()))
+                                        .unwrap_or_default()
+                                })
+                            });
                             let on_delete = move || {
                                 let mut list = data_list.get_untracked();
                                 let uuid = unique_id.get_untracked();
@@ -150,6 +217,17 @@ pub fn MultiEntry(
                                     get_uuid(map.clone()).unwrap_or_default() != uuid
                                 });
                                 update_parent_list(list);
+                                dirty.set(true);
+                            };
+                            let on_move = move |target: usize| {
+                                move_entry(&unique_id.get_untracked(), target);
+                            };
+                            let on_drag_start = move || dragged.set(Some(unique_id.get_untracked()));
+                            let on_drop = move || {
+                                if let Some(uuid) = dragged.get_untracked() {
+                                    move_entry(&uuid, index.get_untracked());
+                                }
+                                dragged.set(None);
                             };
                             Effect::new(move |_| {
                                 let child_map = map_signal.get();
@@ -158,12 +236,23 @@ pub fn MultiEntry(
                                 update_parent_list(list);
                             });
 
+                            // Inputs within the entry are registered under the entry's path.
+                            let scope = Signal::derive(move || {
+                                format!("{}[{}].", data_member.get(), index.get())
+                            });
+
                             view! {
                                 <Entry
                                     data_map=map_signal
                                     schema=schema
                                     id=unique_id
+                                    index=index
+                                    count=Signal::derive(move || data_list.with(Vec::len))
+                                    scope=scope
                                     on_delete=on_delete
+                                    on_move=on_move
+                                    on_drag_start=on_drag_start
+                                    on_drop=on_drop
                                 />
                             }
                         }
@@ -171,11 +260,47 @@ pub fn MultiEntry(
                 </Show>
             </div>
 
-            <ActionButton on:click=add_entry>"Add entry"</ActionButton>
+            <Show when=move || show_errors.get()>
+                <div class="text-red-600 text-sm mx-2">
+                    {move || match error.get() {
+                        ValidationState::Invalid(msg) => msg,
+                        _ => String::new(),
+                    }}
+                </div>
+            </Show>
+            <ActionButton on:click=add_entry disabled=at_max>"Add entry"</ActionButton>
         </div>
     }
 }
 
+/// Checks that the number of entries is within the limits of a [`MultiEntry`].
+fn validate_count(count: usize, min: usize, max: Option<usize>) -> ValidationState {
+    match (EntryLimits { min, max }).check(count) {
+        Ok(()) => ValidationState::Valid,
+        Err(message) => ValidationState::Invalid(message),
+    }
+}
+
+/// Describes an entry in a single line, using the values of its first inputs, such as
+/// `Football · 9th, 10th`. Empty if none of the inputs are filled out.
+fn summarize_entry(schema: &[InputType], entry: &HashMap<String, ValueType>) -> String {
+    schema
+        .iter()
+        .filter_map(|input| match entry.get(input.key())? {
+            ValueType::List(Some(list)) if !list.is_empty() && list.iter().all(ValueType::is_string) => {
+                Some(list.iter().map(ValueType::to_string).collect::<Vec<_>>().join(", "))
+            }
+            value @ (ValueType::String(Some(_)) | ValueType::Number(Some(_))) => {
+                Some(value.to_string())
+            }
+            _ => None,
+        })
+        .filter(|text| !text.trim().is_empty())
+        .take(2)
+        .collect::<Vec<String>>()
+        .join(" · ")
+}
+
 /// # Entry Component
 ///
 /// A single entry component. Should only ever be created from within a `MultiEntry` component.
@@ -202,6 +327,9 @@ pub fn MultiEntry(
 /// inputs will alter the `first_name` and `last_name` keys in the `data_map` respectively, and the
 /// `Entry` component will run a callback to its parent `MultiEntry` component to update the `data_map`
 /// accordingly.
+///
+/// Collapsed entries only hide their inputs, so that the inputs stay registered with the form.
+/// Entries with errors are always expanded.
 #[component]
 fn Entry(
     /// The data map that contains all information. Data is found within this map using the `member`
@@ -213,11 +341,75 @@ fn Entry(
     #[prop(into)] schema: Signal<Vec<InputType>>,
     /// The unique ID for this entry.
     #[prop(into)] id: Signal<String>,
+    /// The position of this entry in the list.
+    #[prop(into)] index: Signal<usize>,
+    /// The number of entries in the list.
+    #[prop(into)] count: Signal<usize>,
+    /// The path of this entry within the form's data, such as `sports[0].`.
+    #[prop(into)] scope: Signal<String>,
     /// The function to run when the delete button is clicked.
     #[prop(into)] on_delete: Callback<()>,
+    /// The function to run when this entry is moved to a new position using the keyboard.
+    #[prop(into)] on_move: Callback<usize>,
+    /// The function to run when this entry starts being dragged.
+    #[prop(into)] on_drag_start: Callback<()>,
+    /// The function to run when an entry is dropped onto this entry.
+    #[prop(into)] on_drop: Callback<()>,
 ) -> impl IntoView {
+    let validation_context = use_validation_context()
+        .expect("FormValidationRegistry was not found");
+    let validators = validation_context.validators;
+    let entry_context = validation_context.scoped(scope);
+    let entry_scope = entry_context.scope;
+    provide_context(entry_context);
+
+    let has_errors = Memo::new(move |_| {
+        let scope = entry_scope.get();
+        validators.with(|list| {
+            list.iter().any(|v| v.with(|state| state.path().starts_with(&scope) && state.shows_error()))
+        })
+    });
+    let collapsed = RwSignal::new(false);
+    let expanded = Memo::new(move |_| !collapsed.get() || has_errors.get());
+
+    let summary = move || {
+        let text = schema.with(|schema| data_map.with(|entry| summarize_entry(schema, entry)));
+        if text.is_empty() {
+            format!("Entry {}", index.get() + 1)
+        } else {
+            format!("{}. {}", index.get() + 1, text)
+        }
+    };
+
+    let on_handle_keydown = move |e: KeyboardEvent| {
+        let index = index.get_untracked();
+        let target = match e.key().as_str() {
+            "ArrowUp" if index > 0 => index - 1,
+            "ArrowDown" if index + 1 < count.get_untracked() => index + 1,
+            _ => return,
+        };
+        e.prevent_default();
+        on_move.run(target);
+    };
+
+    let on_drag_start = move |e: DragEvent| {
+        if let Some(transfer) = e.data_transfer() {
+            // Some browsers won't start a drag without any data.
+            let _ = transfer.set_data("text/plain", &id.get_untracked());
+        }
+        on_drag_start.run(());
+    };
+
     view! {
-        <div class="relative rounded-sm transition-shadow shadow-sm hover:shadow-lg/30">
+        <div
+            class="relative rounded-sm transition-shadow shadow-sm hover:shadow-lg/30"
+            data-member=move || entry_scope.get().trim_end_matches('.').to_string()
+            on:dragover=|e: DragEvent| e.prevent_default()
+            on:drop=move |e: DragEvent| {
+                e.prevent_default();
+                on_drop.run(());
+            }
+        >
             // Delete button
             <div
                 class="absolute rounded-full w-8 h-8 -top-4 -right-4 flex items-center justify-center bg-gray-100 text-gray-700 hover:text-red-600 transition-all cursor-pointer"
@@ -226,8 +418,36 @@ fn Entry(
                 <Icon icon=icondata::FaTrashCanRegular />
             </div>
 
+            // Header with the drag handle and the entry's summary
+            <div class="flex flex-row items-center gap-2 p-2 pb-0">
+                <button
+                    type="button"
+                    class="p-1 text-gray-600 cursor-grab"
+                    draggable="true"
+                    title="Drag, or use the arrow keys, to reorder"
+                    aria-label=move || format!("Reorder entry {}", index.get() + 1)
+                    on:dragstart=on_drag_start
+                    on:keydown=on_handle_keydown
+                >
+                    <Icon icon=icondata::FaGripVerticalSolid />
+                </button>
+                <button
+                    type="button"
+                    class="flex flex-row flex-1 items-center gap-2 text-left cursor-pointer"
+                    aria-expanded=move || expanded.get().to_string()
+                    on:click=move |_| collapsed.set(expanded.get_untracked())
+                >
+                    {move || if expanded.get() {
+                        view! { <Icon icon=icondata::FaChevronDownSolid /> }
+                    } else {
+                        view! { <Icon icon=icondata::FaChevronRightSolid /> }
+                    }}
+                    <span class="font-bold truncate">{summary}</span>
+                </button>
+            </div>
+
             // Input section
-            <div class="flex flex-col flex-1 p-2">
+            <div class="flex flex-col flex-1 p-2" class:hidden=move || !expanded.get()>
                 {move || {
                     schema
                         .get()
@@ -239,3 +459,48 @@ fn Entry(
         </div>
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input;
+
+    #[test]
+    fn validates_entry_counts() {
+        assert_eq!(validate_count(0, 0, None), ValidationState::Valid);
+        assert_eq!(
+            validate_count(0, 1, None),
+            ValidationState::Invalid("Add at least one entry.".to_string())
+        );
+        assert_eq!(
+            validate_count(1, 2, Some(3)),
+            ValidationState::Invalid("Add at least 2 entries.".to_string())
+        );
+        assert_eq!(
+            validate_count(4, 0, Some(3)),
+            ValidationState::Invalid("Remove entries until there are at most 3.".to_string())
+        );
+    }
+
+    #[test]
+    fn summarizes_entries() {
+        let schema = vec![
+            input!(Select, "sport_name", "Sport Name:", true, ["Football", "Soccer"]),
+            input!(Checkbox, "grades", "Grades:", true, ["9th", "10th"]),
+            input!(Text, "achievements", "Achievements:", false, ""),
+        ];
+        let text = |value: &str| ValueType::String(Some(value.to_string()));
+
+        let entry = HashMap::from([
+            ("uuid".to_string(), text("1234")),
+            ("sport_name".to_string(), text("Football")),
+            ("grades".to_string(), ValueType::List(Some(vec![text("9th"), text("10th")]))),
+            ("achievements".to_string(), text("Captain")),
+        ]);
+        assert_eq!(summarize_entry(&schema, &entry), "Football · 9th, 10th");
+
+        let entry = HashMap::from([("achievements".to_string(), text("Captain"))]);
+        assert_eq!(summarize_entry(&schema, &entry), "Captain");
+        assert_eq!(summarize_entry(&schema, &HashMap::new()), "");
+    }
+}
@@ -46,6 +46,8 @@ use crate::common::{SubTabInfo, TabInfo};
 ///     // Remember, no Outlets!
 /// }
 /// ```
+///
+/// Tabs and sub-tabs with a `badge` show it at the end of the tab, such as the progress of a form.
 #[component]
 pub fn TabSidebarList(
     #[prop(into)] base_path: Signal<String>,
@@ -58,10 +60,16 @@ pub fn TabSidebarList(
             <nav class="flex flex-col bg-red-900 w-64 overflow-scroll">
                 <For
                     each=move || tabs.get()
-                    key=|info| info.text.clone()
-                    let(TabInfo { text, path, sub_paths })
+                    key=|info| (info.text.clone(), info.badge.clone())
+                    let(TabInfo { text, path, sub_paths, badge })
                 >
-                    <SidebarTab base_path=base_path text=text path=path sub_paths=sub_paths />
+                    <SidebarTab
+                        base_path=base_path
+                        text=text
+                        path=path
+                        sub_paths=sub_paths
+                        badge=badge
+                    />
                 </For>
             </nav>
 
@@ -78,6 +86,7 @@ fn SidebarTab(
     #[prop(into)] text: Signal<String>,
     #[prop(into)] path: Signal<String>,
     #[prop(into)] sub_paths: Signal<Vec<SubTabInfo>>,
+    badge: Option<String>,
 ) -> impl IntoView {
     let location = use_location();
 
@@ -99,7 +108,10 @@ fn SidebarTab(
             class=(["bg-white", "text-black"], move || selected.get())
         >
             <A href=move || path.get()>
-                <div class="p-3 transition-bg font-bold">{text}</div>
+                <div class="flex flex-row items-center gap-2 p-3 transition-bg font-bold">
+                    {text}
+                    <TabBadge badge=badge />
+                </div>
             </A>
             <div
                 class="grid transition-all ease-in-out"
@@ -108,10 +120,10 @@ fn SidebarTab(
                 <div class="overflow-hidden min-h-0">
                     <For
                         each=move || sub_paths.get()
-                        key=|info| info.text.clone()
-                        let(SubTabInfo { text, path })
+                        key=|info| (info.text.clone(), info.badge.clone())
+                        let(SubTabInfo { text, path, badge })
                     >
-                        <SidebarSubTab parent_path=full_path text=text path=path />
+                        <SidebarSubTab parent_path=full_path text=text path=path badge=badge />
                     </For>
                 </div>
             </div>
@@ -124,6 +136,7 @@ fn SidebarSubTab(
     #[prop(into)] parent_path: Signal<String>,
     #[prop(into)] text: Signal<String>,
     #[prop(into)] path: Signal<String>,
+    badge: Option<String>,
 ) -> impl IntoView {
     let full_path = Memo::new(move |_| {
         format!("{}/{}", parent_path.get(), path.get())
@@ -133,9 +146,21 @@ fn SidebarSubTab(
         <A
             href=move || full_path.get()
             {..}
-            class="block p-3 pl-10 transition-all aria-[current]:font-bold"
+            class="flex flex-row items-center gap-2 p-3 pl-10 transition-all aria-[current]:font-bold"
         >
             {text}
+            <TabBadge badge=badge />
         </A>
     }
 }
+
+#[component]
+fn TabBadge(badge: Option<String>) -> impl IntoView {
+    badge.map(|badge| {
+        view! {
+            <span class="ml-auto px-2 rounded-full text-xs font-normal bg-gray-200 text-gray-800">
+                {badge}
+            </span>
+        }
+    })
+}
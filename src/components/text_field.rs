@@ -1,4 +1,4 @@
-use crate::common::ValueType;
+use crate::common::{TextLimits, ValueType, check_email, format_currency, format_phone, parse_date};
 use leptos::prelude::*;
 use std::collections::HashMap;
 use leptos::html::Input;
@@ -22,7 +22,37 @@ pub enum TextFieldType {
     /// TextFieldType::Email(vec!["gmail.com".to_string(), "customdomain.org".to_string()]);
     /// TextFieldType::Email(vec!["*".to_string()]);
     /// ```
-    Email(Vec<String>)
+    Email(Vec<String>),
+    /// A text field that accepts US phone numbers, which are formatted as `000-000-0000` once the
+    /// field loses focus.
+    Phone,
+    /// A text field that accepts dollar amounts, which are stored as numbers with two decimal
+    /// places once the field loses focus.
+    Currency,
+    /// A date picker. Dates are stored using the `YYYY-MM-DD` format.
+    Date,
+}
+
+impl TextFieldType {
+    /// Gets the `type` attribute of the HTML input.
+    fn html_type(&self) -> &'static str {
+        match self {
+            TextFieldType::Email(_) => "email",
+            TextFieldType::Phone => "tel",
+            TextFieldType::Date => "date",
+            _ => "text",
+        }
+    }
+
+    /// Formats a finished value, such as a phone number, returning `None` if it can't be
+    /// formatted or doesn't need to be.
+    fn format(&self, value: &str) -> Option<String> {
+        match self {
+            TextFieldType::Phone => format_phone(value).ok(),
+            TextFieldType::Currency => format_currency(value).ok(),
+            _ => None,
+        }
+    }
 }
 
 /// Entry point for validating a text field.
@@ -32,27 +62,17 @@ fn validate(required: bool, value: &str, input_type: &TextFieldType) -> Validati
     } else if !required && value.is_empty() {
         ValidationState::Valid
     } else {
-        match input_type {
-            TextFieldType::Email(domains) => validate_email(value, domains),
-            TextFieldType::Number => validate_number(value),
-            TextFieldType::Text => ValidationState::Valid
-        }
-    }
-}
+        let result = match input_type {
+            TextFieldType::Email(domains) => check_email(value, domains),
+            TextFieldType::Number => return validate_number(value),
+            TextFieldType::Phone => format_phone(value).map(|_| ()),
+            TextFieldType::Currency => format_currency(value).map(|_| ()),
+            TextFieldType::Date => parse_date(value).map(|_| ()),
+            TextFieldType::Text => Ok(()),
+        };
 
-/// Validates an email address.
-fn validate_email(input: &str, valid_domains: &Vec<String>) -> ValidationState {
-    // Check for wildcard domain. We won't check domains if it's present.
-    let wild = valid_domains.contains(&"*".to_string());
-
-    input.split_once('@')
-        .and_then(|(_, domain)| {
-            (wild || valid_domains.contains(&domain.to_string()))
-                .then(|| ValidationState::Valid)
-                .or_else(|| Some(ValidationState::Invalid(
-                    format!("Email address must match one of the following: {}", valid_domains.join(", "))
-                )))
-        }).unwrap_or(ValidationState::Invalid("Invalid email address.".to_string()))
+        result.map_or_else(ValidationState::Invalid, |_| ValidationState::Valid)
+    }
 }
 
 /// Validates a number.
@@ -93,24 +113,36 @@ pub fn OutlinedTextField(
     let error = Signal::derive(move || 
         validate(required.get(), &display_value.get(), &input_type.get())
     );
+    let error = validator_context.with_field_error(data_member.get_value(), error);
     let show_errors = Signal::derive(move || {
         dirty.get() && matches!(error.get(), ValidationState::Invalid(_))
     });
 
-    let validator = RwSignal::new(InputState::new(data_member.get_value(), error.clone(), dirty.clone()));
+    validator_context.register(
+        InputState::new(data_member.get_value(), error.clone(), dirty.clone())
+            .with_label(label.clone())
+    );
 
-    validator_context.validators.update(|list| list.push(validator));
 
-    on_cleanup(move || {
-        validator_context.validators.update(|list| {
-            list.retain(|v| *v.get_untracked().input_name != *validator.get_untracked().input_name)
-        });
-    });
+    let pending = validator_context.is_pending(data_member.get_value());
 
     let on_blur = move |_| {
         // Check validity of input's current value. The value will be updated by on_input,
         // and it should be a raw input (not parsed into a ValueType).
         dirty.set(true);
+
+        // Values such as phone numbers are only formatted once they're finished, so that the
+        // text doesn't change while it's being typed.
+        let input_type = input_type.get_untracked();
+        if let Some(formatted) = input_type.format(&display_value.get_untracked()) {
+            data_map.update(|map| {
+                let value = match input_type {
+                    TextFieldType::Currency => ValueType::Number(Some(formatted)),
+                    _ => ValueType::String(Some(formatted)),
+                };
+                map.insert(data_member.get_value(), value);
+            });
+        }
     };
 
     // Syncs data from the map into the display_value, only when the map is actually changed.
@@ -137,7 +169,7 @@ pub fn OutlinedTextField(
         // or not.
         data_map.update(|map| {
             let into_map = match input_type.get() {
-                TextFieldType::Number => ValueType::Number(Some(to_parse)),
+                TextFieldType::Number | TextFieldType::Currency => ValueType::Number(Some(to_parse)),
                 _ => ValueType::String(Some(to_parse))
             };
 
@@ -146,7 +178,7 @@ pub fn OutlinedTextField(
     };
 
     view! {
-        <div class="flex flex-1">
+        <div class="flex flex-1" data-member=validator_context.path(data_member.get_value())>
             <label class="flex flex-col flex-1">
                 <span class="block ml-1.5 mb-0 font-bold">{label}</span>
                 <input
@@ -155,7 +187,7 @@ pub fn OutlinedTextField(
                     transition-all duration-150
                     border-red-700 bg-transparent
                     disabled:border-gray-600 disabled:pointer-events-none disabled:bg-gray-600/33"
-                    r#type="text"
+                    r#type=move || input_type.with(TextFieldType::html_type)
                     disabled=disabled
                     placeholder=placeholder
                     prop:name=name
@@ -163,6 +195,9 @@ pub fn OutlinedTextField(
                     on:input=on_input
                     on:blur=on_blur
                 />
+                <Show when=move || pending.get()>
+                    <div class="text-gray-600 text-sm italic mr-1.5 ml-1.5">"Checking..."</div>
+                </Show>
                 <Show when=move || show_errors.get()>
                     <div class="text-red-600 text-sm mr-1.5 ml-1.5">
                         {move || {
@@ -177,3 +212,104 @@ pub fn OutlinedTextField(
         </div>
     }
 }
+
+/// # Outlined Text Area
+///
+/// A multi-line version of [`OutlinedTextField`] for longer answers, such as essays. The text can
+/// be limited to a number of characters or words using `limits`, and the current counts are
+/// shown underneath the text area.
+///
+/// Example usage:
+/// ```ignore
+/// view! {
+///     <OutlinedTextArea
+///         label="Essay:"
+///         data_member="essay"
+///         data_map=data_map
+///         limits=TextLimits::words(250)
+///         required=true
+///     />
+/// }
+/// ```
+#[component]
+pub fn OutlinedTextArea(
+    #[prop(optional, into)] placeholder: String,
+    #[prop(into)] data_member: String,
+    #[prop()] data_map: RwSignal<HashMap<String, ValueType>>,
+    #[prop(optional, into)] limits: Signal<TextLimits>,
+    #[prop(optional, into)] disabled: Signal<bool>,
+    #[prop(optional, into)] label: String,
+    #[prop(optional, into)] required: Signal<bool>
+) -> impl IntoView {
+    let data_member = StoredValue::new(data_member);
+
+    let validator_context = use_validation_context()
+        .expect("FormValidSignal was not found");
+
+    let value = Memo::new(move |_| {
+        data_map.with(|map| {
+            map.get(&data_member.get_value())
+                .map(|v| v.to_string())
+                .unwrap_or_default()
+        })
+    });
+
+    let dirty = RwSignal::new(false);
+    let error = Signal::derive(move || {
+        let value = value.get();
+        if value.trim().is_empty() {
+            return validate(required.get(), "", &TextFieldType::Text);
+        }
+        limits.with(|limits| limits.check(&value))
+            .map_or_else(ValidationState::Invalid, |_| ValidationState::Valid)
+    });
+    let error = validator_context.with_field_error(data_member.get_value(), error);
+    let show_errors = Signal::derive(move || {
+        dirty.get() && matches!(error.get(), ValidationState::Invalid(_))
+    });
+
+    validator_context.register(
+        InputState::new(data_member.get_value(), error.clone(), dirty.clone())
+            .with_label(label.clone())
+    );
+
+
+    let usage = move || limits.with(|limits| value.with(|value| limits.describe_usage(value)));
+
+    let on_input = move |e| {
+        let text = event_target_value(&e);
+        data_map.update(|map| {
+            map.insert(data_member.get_value(), ValueType::String(Some(text)));
+        });
+    };
+
+    view! {
+        <div class="flex flex-1" data-member=validator_context.path(data_member.get_value())>
+            <label class="flex flex-col flex-1">
+                <span class="block ml-1.5 mb-0 font-bold">{label}</span>
+                <textarea
+                    class="border-2 m-1.5 p-1.5 mt-0 rounded-md bg-transparent relative flex-1 min-h-32
+                    transition-all duration-150
+                    border-red-700 bg-transparent
+                    disabled:border-gray-600 disabled:pointer-events-none disabled:bg-gray-600/33"
+                    disabled=disabled
+                    placeholder=placeholder
+                    prop:value=value
+                    on:input=on_input
+                    on:blur=move |_| dirty.set(true)
+                />
+                <div class="flex flex-row text-sm mr-1.5 ml-1.5">
+                    <Show when=move || show_errors.get()>
+                        <span class="text-red-600">
+                            {move || match error.get() {
+                                ValidationState::Invalid(msg) => msg,
+                                _ => String::new(),
+                            }}
+                        </span>
+                    </Show>
+                    <span class="ml-auto text-gray-600">{usage}</span>
+                </div>
+            </label>
+        </div>
+    }
+}
@@ -1,7 +1,16 @@
-﻿use leptos::logging::log;
+﻿use leptos::html::{Div, Form};
+use leptos::logging::log;
 use leptos::prelude::*;
-use leptos::web_sys::SubmitEvent;
+use leptos::task::spawn_local;
+use leptos::wasm_bindgen::JsCast;
+use leptos::web_sys::{HtmlElement, SubmitEvent};
+use crate::common::{FieldErrors, FormCheck, ValueType};
 use crate::components::{ActionButton, Header};
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
 
 /// Indicates the current state of an input.
 #[derive(Default, Debug, Clone)]
@@ -20,6 +29,11 @@ pub enum ValidationState {
 pub struct InputState {
     /// The name of the input. This is used to identify the input in the form.
     pub input_name: String,
+    /// The path of the list entry that contains the input, such as `sports[0].`, which is empty
+    /// for inputs that aren't within an entry. Set when the input is registered.
+    scope: Signal<String>,
+    /// The label of the input, which is shown in the form's error summary.
+    label: String,
     /// Indicates the current state of the input.
     error: Signal<ValidationState>,
     /// Indicates whether the form has requested the error state. This means that the input
@@ -36,14 +50,156 @@ impl InputState {
     ) -> Self {
         Self {
             input_name: name.into(),
+            scope: Signal::default(),
+            label: String::new(),
             error: error.into(),
             form_requested_validation: form_requested_errors
         }
     }
 
+    /// Sets the label that the form's error summary uses for this input. Inputs without a label
+    /// are listed using their name.
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = label.into();
+        self
+    }
+
     pub fn error(&self) -> Signal<ValidationState> {
         self.error.clone()
     }
+
+    /// Gets the input's path within the form's data, such as `sports[0].sport_name`. Server
+    /// errors use the same paths.
+    pub fn path(&self) -> String {
+        format!("{}{}", self.scope.get(), self.input_name)
+    }
+
+    /// Returns true if the input is invalid and is showing its error.
+    pub fn shows_error(&self) -> bool {
+        self.form_requested_validation.get()
+            && matches!(self.error.get(), ValidationState::Invalid(_))
+    }
+
+    /// Gets the label that the form's error summary uses for this input.
+    pub fn summary_label(&self) -> String {
+        let label = self.label.trim().trim_end_matches(':');
+        if label.is_empty() {
+            self.input_name.clone()
+        } else {
+            label.to_string()
+        }
+    }
+}
+
+/// The future returned by an [`AsyncValidator`]'s check.
+pub type AsyncCheckFuture = Pin<Box<dyn Future<Output = Result<(), String>>>>;
+
+/// # Async Validator
+///
+/// A check that needs to wait for something, such as a server function, before an input can be
+/// called valid. The check runs shortly after the input's value stops changing, and the input is
+/// pending until it finishes. Forms can't be submitted while any of their checks are pending.
+///
+/// The check is given the whole form's data and returns an error message for the input named
+/// `key`. Blank values aren't checked, since requiredness is handled by the input itself.
+///
+/// Example usage:
+/// ```ignore
+/// AsyncValidator::new("id_number", move |data| async move {
+///     let id_number = data.get("id_number").map(|v| v.to_string()).unwrap_or_default();
+///     match check_student_id(access_token, id_number).await {
+///         Ok(None) => Ok(()),
+///         Ok(Some(problem)) => Err(problem),
+///         Err(err) => Err(err.to_string()),
+///     }
+/// })
+/// ```
+#[derive(Clone)]
+pub struct AsyncValidator {
+    key: String,
+    check: Arc<dyn Fn(HashMap<String, ValueType>) -> AsyncCheckFuture + Send + Sync>,
+}
+
+impl AsyncValidator {
+    /// How long the input's value must stay the same before the check runs.
+    const DELAY: Duration = Duration::from_millis(400);
+
+    /// Creates a new [`AsyncValidator`] for the input named `key`.
+    pub fn new<F, Fut>(key: impl Into<String>, check: F) -> Self
+    where
+        F: Fn(HashMap<String, ValueType>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), String>> + 'static,
+    {
+        Self {
+            key: key.into(),
+            check: Arc::new(move |data| Box::pin(check(data))),
+        }
+    }
+
+    /// Runs the check whenever the input's value changes, keeping its result in `errors` and
+    /// marking the input as `pending` until the result arrives. Results for values that have
+    /// since changed are dropped.
+    fn watch(
+        self,
+        data_map: RwSignal<HashMap<String, ValueType>>,
+        errors: RwSignal<FieldErrors>,
+        pending: RwSignal<HashSet<String>>,
+    ) {
+        let AsyncValidator { key, check } = self;
+        let key = StoredValue::new(key);
+        let value = Memo::new(move |_| {
+            data_map.with(|map| key.with_value(|key| map.get(key).map(|v| v.to_string())))
+        });
+        let generation = StoredValue::new(0usize);
+
+        Effect::new(move || {
+            let value = value.get().unwrap_or_default();
+            generation.update_value(|generation| *generation += 1);
+            let current = generation.get_value();
+
+            errors.update(|errors| {
+                errors.remove(&key.get_value());
+            });
+            if value.trim().is_empty() {
+                pending.update(|pending| {
+                    pending.remove(&key.get_value());
+                });
+                return;
+            }
+            pending.update(|pending| {
+                pending.insert(key.get_value());
+            });
+
+            let check = check.clone();
+            set_timeout(
+                move || {
+                    if generation.try_get_value() != Some(current) {
+                        return;
+                    }
+                    let future = check(data_map.get_untracked());
+                    spawn_local(async move {
+                        let result = future.await;
+                        if generation.try_get_value() != Some(current) {
+                            return;
+                        }
+                        let Some(key) = key.try_get_value() else {
+                            return;
+                        };
+
+                        pending.update(|pending| {
+                            pending.remove(&key);
+                        });
+                        if let Err(message) = result {
+                            errors.update(|errors| {
+                                errors.insert(key, message);
+                            });
+                        }
+                    });
+                },
+                Self::DELAY,
+            );
+        });
+    }
 }
 
 /// A list of [`InputState`]s.
@@ -52,9 +208,114 @@ pub type ValidatorList = Vec<RwSignal<InputState>>;
 /// A struct that keeps track of all components that must be validated.
 /// All of our custom-made components must be placed within an area that contains context with
 /// this struct.
+///
+/// Inputs that are hidden by their conditions (see [`InputType::shown_when`]) are unmounted,
+/// which removes them from the registry, so hidden inputs never block a submission.
+///
+/// [`InputType::shown_when`]: crate::common::InputType::shown_when
 #[derive(Debug, Clone)]
 pub struct FormValidationRegistry {
-    pub validators: RwSignal<ValidatorList>
+    pub validators: RwSignal<ValidatorList>,
+    /// Errors that don't come from the inputs themselves, keyed by data member. These are errors
+    /// returned by the server for the last submission, along with the form's checks.
+    pub field_errors: Signal<FieldErrors>,
+    /// The names of inputs whose [`AsyncValidator`]s haven't finished yet.
+    pub pending: Signal<HashSet<String>>,
+    /// The path that's added to the names of inputs registered with this registry. Inputs within
+    /// a `MultiEntry` use a registry that's scoped to their entry, such as `sports[0].`.
+    pub scope: Signal<String>,
+}
+
+impl FormValidationRegistry {
+    /// Registers an input with the form, returning its state. The input is removed from the form
+    /// when it's unmounted.
+    pub fn register(&self, mut state: InputState) -> RwSignal<InputState> {
+        state.scope = self.scope;
+        let state = RwSignal::new(state);
+        let validators = self.validators;
+
+        validators.update(|list| list.push(state));
+        on_cleanup(move || {
+            validators.update(|list| list.retain(|v| *v != state));
+        });
+
+        state
+    }
+
+    /// Creates a registry for the inputs of a list entry. The entry's `scope`, such as
+    /// `sports[0].`, is added to the scope of this registry.
+    pub fn scoped(&self, scope: impl Into<Signal<String>>) -> Self {
+        let parent = self.scope;
+        let scope = scope.into();
+
+        Self {
+            scope: Signal::derive(move || format!("{}{}", parent.get(), scope.get())),
+            ..self.clone()
+        }
+    }
+
+    /// Gets the path of an input within the form's data, including the registry's scope.
+    pub fn path(&self, name: impl Into<String>) -> Signal<String> {
+        let name = name.into();
+        let scope = self.scope;
+
+        Signal::derive(move || format!("{}{}", scope.get(), name))
+    }
+
+    /// Combines an input's own validation with any error that the server returned for the same
+    /// data member. The input's own error is shown first, since it's more up to date.
+    pub fn with_field_error(
+        &self,
+        name: impl Into<String>,
+        error: impl Into<Signal<ValidationState>>,
+    ) -> Signal<ValidationState> {
+        let path = self.path(name);
+        let error = error.into();
+        let field_errors = self.field_errors;
+
+        Signal::derive(move || match error.get() {
+            ValidationState::Valid => field_errors
+                .with(|errors| path.with(|path| errors.get(path).cloned()))
+                .map(ValidationState::Invalid)
+                .unwrap_or_default(),
+            invalid => invalid,
+        })
+    }
+
+    /// Returns true while an input's [`AsyncValidator`] is running.
+    pub fn is_pending(&self, name: impl Into<String>) -> Signal<bool> {
+        let path = self.path(name);
+        let pending = self.pending;
+
+        Signal::derive(move || pending.with(|pending| path.with(|path| pending.contains(path))))
+    }
+}
+
+/// Focuses the input at the given path. Inputs mark their container with a `data-member`
+/// attribute that contains their path. If there's no input at the path, such as for an entry that
+/// was removed, the closest list that contains it is focused instead.
+fn focus_input(form_ref: NodeRef<Form>, path: &str) {
+    let Some(form) = form_ref.get_untracked() else {
+        return;
+    };
+    // Shorter paths are found by cutting the path at each `[` or `.`, from the end.
+    let paths = std::iter::once(path).chain(
+        path.rmatch_indices(['[', '.'])
+            .map(|(index, _)| &path[..index]),
+    );
+    let Some(container) = paths
+        .filter_map(|path| form.query_selector(&format!("[data-member=\"{path}\"]")).ok().flatten())
+        .next()
+    else {
+        return;
+    };
+
+    container.scroll_into_view();
+    if let Ok(Some(input)) = container.query_selector("input:not(.hidden), select, textarea")
+        && let Ok(input) = input.dyn_into::<HtmlElement>()
+    {
+        let _ = input.focus();
+    }
 }
 
 /// Gets the [`FormValidationRegistry`] context if it exists.
@@ -97,11 +358,19 @@ pub fn use_validation_context() -> Option<FormValidationRegistry> {
 ///         error.clone(),
 ///         form_requested_errors.clone()
 ///     );
-///     context.validators.update(|list| list.push(RwSignal::new(state)));
+///     // Inputs are removed from the form when they're unmounted
+///     let state = context.register(state);
 ///
 ///     /* Other input view logic */
 /// }
 /// ```
+///
+/// Checks that involve more than one input are given as [`FormCheck`]s, and checks that need the
+/// server as [`AsyncValidator`]s. Both need the form's `data_map`. When a submission is blocked,
+/// every error is listed at the top of the form, with links that focus the inputs.
+///
+/// Forms that autosave drafts can pass `draft_saved_at`, which keeps a "saved · unsaved" status
+/// above the submit button.
 #[component]
 pub fn ValidatedForm(
     children: Children,
@@ -113,12 +382,93 @@ pub fn ValidatedForm(
     #[prop(optional, into)] description: Signal<String>,
     /// A `Signal` that determines when the submit button should be disabled.
     #[prop(optional, into)] disabled: Signal<bool>,
+    /// A `Signal` that shows a notice above the submit button when the form has unsaved changes.
+    #[prop(optional, into)] unsaved_changes: Signal<bool>,
+    /// A callback that discards unsaved changes. If set, a button that runs it is shown next to
+    /// the unsaved changes notice.
+    #[prop(optional_no_strip, into)] on_discard: Option<Callback<()>>,
+    /// When the unsaved changes were last autosaved as a draft. If set, the unsaved changes
+    /// notice always shows whether the form is saved, along with when the draft was saved.
+    #[prop(optional_no_strip, into)] draft_saved_at: Option<Signal<Option<String>>>,
+    /// Errors returned by the server, keyed by data member. Each input shows the error for its own
+    /// data member, and any other errors are listed in the error summary.
+    #[prop(optional, into)] field_errors: Signal<FieldErrors>,
+    /// The data that the form's inputs edit, which is needed by `checks` and `async_validators`.
+    #[prop(optional)] data_map: Option<RwSignal<HashMap<String, ValueType>>>,
+    /// Checks over the whole form's data, which show their errors on the inputs that they name.
+    #[prop(optional, into)] checks: Vec<FormCheck>,
+    /// Checks that run in the background, such as checks that use a server function.
+    #[prop(optional)] async_validators: Vec<AsyncValidator>,
 ) -> impl IntoView {
     let validators = RwSignal::new(vec![]);
+    let data_map = data_map.unwrap_or_else(|| RwSignal::new(HashMap::new()));
+
+    let check_errors = Memo::new(move |_| {
+        let mut errors = FieldErrors::new();
+        data_map.with(|data| {
+            for check in &checks {
+                check.run_into(data, &mut errors);
+            }
+        });
+        errors
+    });
+
+    let async_errors = RwSignal::new(FieldErrors::new());
+    let pending = RwSignal::new(HashSet::new());
+    for validator in async_validators {
+        validator.watch(data_map, async_errors, pending);
+    }
+
+    // The form's own checks are more up to date than the server's errors, so they replace them.
+    let all_errors = Signal::derive(move || {
+        let mut errors = field_errors.get();
+        errors.extend(check_errors.get());
+        errors.extend(async_errors.get());
+        errors
+    });
 
     // Provide context to all children nested within this component. They will be able to access
     // this no matter how nested they are.
-    provide_context(FormValidationRegistry {validators});
+    provide_context(FormValidationRegistry {
+        validators,
+        field_errors: all_errors,
+        pending: pending.into(),
+        scope: Signal::default(),
+    });
+
+    // Every error in the form as (name, label, message), in the order that the inputs are shown.
+    // Errors that don't belong to any input, such as errors for fields within a list of entries,
+    // are listed last.
+    let summary = Memo::new(move |_| {
+        let mut names = HashSet::new();
+        let mut summary = validators.with(|list| {
+            list.iter()
+                .filter_map(|v| v.with(|validator| {
+                    names.insert(validator.path());
+                    match validator.error().get() {
+                        ValidationState::Invalid(msg) => Some((
+                            validator.path(),
+                            validator.summary_label(),
+                            msg,
+                        )),
+                        ValidationState::Valid => None,
+                    }
+                }))
+                .collect::<Vec<(String, String, String)>>()
+        });
+        let mut other_errors = all_errors.with(|errors| {
+            errors.iter()
+                .filter(|(key, _)| !names.contains(*key))
+                .map(|(key, msg)| (key.clone(), key.clone(), msg.clone()))
+                .collect::<Vec<(String, String, String)>>()
+        });
+        other_errors.sort();
+        summary.extend(other_errors);
+        summary
+    });
+    let attempted_submit = RwSignal::new(false);
+    let form_ref = NodeRef::<Form>::new();
+    let summary_ref = NodeRef::<Div>::new();
 
     let can_submit = Memo::new(move |_| {
         log!("Checking {} inputs...", validators.get().len());
@@ -130,6 +480,9 @@ pub fn ValidatedForm(
                         v.update(|validator| validator.form_requested_validation.set(true));
                         matches!(v.get().error().get(), ValidationState::Valid) && acc
                     })
+                    && check_errors.with(FieldErrors::is_empty)
+                    && async_errors.with(FieldErrors::is_empty)
+                    && pending.with(HashSet::is_empty)
                     // .all(|v| {
                     //     // Not sure how this will work reactively.
                     //     let result = matches!(v.get().error().get(), ValidationState::Valid);
@@ -141,9 +494,13 @@ pub fn ValidatedForm(
 
     let submit_success_event = move |e: SubmitEvent| {
         e.prevent_default();
+        attempted_submit.set(true);
 
         if !can_submit.get() {
             log!("Form failed to submit, there are errors in form inputs.");
+            if let Some(summary) = summary_ref.get_untracked() {
+                summary.scroll_into_view();
+            }
             return;
         }
 
@@ -151,10 +508,71 @@ pub fn ValidatedForm(
         on_submit.run(());
     };
 
+    let save_status = move || {
+        if !unsaved_changes.get() {
+            return "All changes saved.".to_string();
+        }
+        match draft_saved_at.and_then(|saved_at| saved_at.get()) {
+            Some(time) => format!("Unsaved changes · Draft saved at {time}."),
+            None => "You have unsaved changes.".to_string(),
+        }
+    };
+
     view! {
-        <form class="flex flex-col gap-2 py-7" novalidate on:submit=submit_success_event>
+        <form
+            class="flex flex-col gap-2 py-7"
+            novalidate
+            node_ref=form_ref
+            on:submit=submit_success_event
+        >
             <Header title=title description=description />
+            <div node_ref=summary_ref>
+                <Show when=move || attempted_submit.get() && !summary.get().is_empty()>
+                    <div
+                        class="flex flex-col gap-1 m-1.5 p-3 rounded-md border-2 border-red-700 bg-red-700/10"
+                        role="alert"
+                    >
+                        <span class="font-bold">"Please fix the following before submitting:"</span>
+                        <ul class="text-sm list-disc ml-6">
+                            {move || summary.get().into_iter().map(|(name, label, msg)| view! {
+                                <li>
+                                    <a
+                                        href="#"
+                                        class="underline cursor-pointer"
+                                        on:click=move |e| {
+                                            e.prevent_default();
+                                            focus_input(form_ref, &name);
+                                        }
+                                    >
+                                        {label}
+                                    </a>
+                                    ": "{msg}
+                                </li>
+                            }).collect_view()}
+                        </ul>
+                    </div>
+                </Show>
+            </div>
             {children()}
+            <Show when=move || pending.with(|pending| !pending.is_empty())>
+                <span class="text-sm italic text-gray-600">"Checking your answers..."</span>
+            </Show>
+            <Show when=move || unsaved_changes.get() || draft_saved_at.is_some()>
+                <div class="flex flex-row items-center gap-2 text-sm text-gray-600">
+                    <span class="italic">{save_status}</span>
+                    <Show when=move || unsaved_changes.get()>
+                        {on_discard.map(|on_discard| view! {
+                            <button
+                                type="button"
+                                class="underline cursor-pointer"
+                                on:click=move |_| on_discard.run(())
+                            >
+                                "Discard Changes"
+                            </button>
+                        })}
+                    </Show>
+                </div>
+            </Show>
             <ActionButton
                 disabled=disabled
                 on:click=move |_| {
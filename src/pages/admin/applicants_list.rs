@@ -1,15 +1,13 @@
 ﻿use crate::common::{
-    ComparisonData, ExpandableInfo, SchemaContainerStyle, SchemaHeaderStyle, SchemaNode,
-    SchemaType, ValueType,
+    self, ExpandableInfo, SchemaContainerStyle, SchemaHeaderStyle, SchemaNode, SchemaType,
 };
 use crate::components::{ActionButton, Banner, DataDisplay, Loading};
-use crate::pages::{StudentInformationDialog, UnauthenticatedPage};
-use crate::pages::api::get_comparison_info;
-use crate::pages::api::students::{GetStudentFiles, admin_get_all_input_files, get_student_data};
+use crate::pages::{ApplicantTable, StudentInformationDialog, UnauthenticatedPage};
+use crate::pages::api::ApplicantSummary;
+use crate::pages::api::students::{GetStudentFiles, get_student_data};
 use base64::Engine;
 use leptos::ev::{Event, MouseEvent};
 use leptos::html::Dialog;
-use leptos::logging::debug_log;
 use leptos::prelude::*;
 use leptos::wasm_bindgen::JsCast;
 use leptos::web_sys;
@@ -18,7 +16,6 @@ use leptos_icons::Icon;
 use leptos_oidc::{AuthLoaded, AuthSignal, Authenticated};
 use leptos_router::components::Outlet;
 use leptos_router::hooks::use_navigate;
-use std::collections::HashMap;
 use leptos_router::NavigateOptions;
 
 #[component]
@@ -186,7 +183,7 @@ fn ApplicantsScholarshipEntry(
 #[component]
 pub fn AdminApplicantsStudentList() -> impl IntoView {
     use crate::pages::api::get_scholarship_info;
-    use crate::pages::api::students::admin_get_completed_students;
+    use crate::pages::api::admin_get_eligible_students;
     use leptos_router::hooks::use_params;
     use leptos_router::params::Params;
 
@@ -217,21 +214,13 @@ pub fn AdminApplicantsStudentList() -> impl IntoView {
     async fn get_eligibility_info(
         token: String,
         scholarship_id: String,
-    ) -> Result<
-        (
-            HashMap<String, HashMap<String, ValueType>>,
-            ExpandableInfo,
-            Vec<ComparisonData>,
-        ),
-        ServerFnError,
-    > {
-        let (students, scholarships, requirements_list) = tokio::join!(
-            admin_get_completed_students(token),
-            get_scholarship_info(scholarship_id),
-            get_comparison_info()
+    ) -> Result<(Vec<ApplicantSummary>, ExpandableInfo), ServerFnError> {
+        let (students, scholarship) = tokio::join!(
+            admin_get_eligible_students(token, scholarship_id.clone()),
+            get_scholarship_info(scholarship_id)
         );
 
-        Ok((students?, scholarships?, requirements_list?))
+        Ok((students?, scholarship?))
     }
 
     let resource = Resource::new(
@@ -256,7 +245,7 @@ pub fn AdminApplicantsStudentList() -> impl IntoView {
                         .get()
                         .map(|result| {
                             match result {
-                                Ok((students, scholarship, requirements_list)) => {
+                                Ok((students, scholarship)) => {
                                     view! {
                                         <h1 class="text-2xl font-bold">"Eligible Students"</h1>
                                         <div class="text-lg">
@@ -272,10 +261,8 @@ pub fn AdminApplicantsStudentList() -> impl IntoView {
                                             </a>"."
                                         </div>
                                         <AdminApplicantsStudentListView
-                                            access_token=access_token
                                             students=students
                                             scholarship=scholarship
-                                            requirements=requirements_list
                                         />
                                     }
                                         .into_any()
@@ -298,13 +285,9 @@ pub fn AdminApplicantsStudentList() -> impl IntoView {
 
 #[component]
 fn AdminApplicantsStudentListView(
-    #[prop(into)] access_token: Signal<Option<String>>,
-    #[prop(into)] students: Signal<HashMap<String, HashMap<String, ValueType>>>,
+    #[prop(into)] students: Signal<Vec<ApplicantSummary>>,
     #[prop(into)] scholarship: Signal<ExpandableInfo>,
-    #[prop(into)] requirements: Signal<Vec<ComparisonData>>,
 ) -> impl IntoView {
-    let eligible_students = RwSignal::new(HashMap::new());
-    let error_msg = RwSignal::new(String::new());
     // let (current_student_id, set_current_student_id) = query_signal::<String>("student_id");
     let current_student_id = RwSignal::new(None);
     let navigate_student = Callback::new(move |id: String| {
@@ -324,196 +307,8 @@ fn AdminApplicantsStudentListView(
         current_student_id.set(None);
     });
 
-    let fafsa_required = Memo::new(move |_| {
-        let required = scholarship
-            .get()
-            .data
-            .get("fafsa_required")
-            .map(|v| v.as_string().ok().flatten())
-            .flatten()
-            .unwrap_or_default();
-
-        required == "Yes"
-    });
-
-    let essay_required = Memo::new(move |_| {
-        let prompt = scholarship
-            .get()
-            .data
-            .get("essay_prompt")
-            .map(|v| v.as_string().ok().flatten())
-            .flatten()
-            .unwrap_or_default();
-
-        !prompt.is_empty()
-    });
-
-    let fafsa_resource = Resource::new(
-        move || (fafsa_required.get(), access_token.get()),
-        move |(fafsa_required, access_token)| async move {
-            // Only get this information if the FAFSA is required. Otherwise, return a blank result.
-            let Some(access_token) = access_token else {
-                return Err(ServerFnError::new("Couldn't find access token"));
-            };
-            if !fafsa_required {
-                return Ok(HashMap::new());
-            }
-
-            admin_get_all_input_files(
-                access_token,
-                "financial_info".to_string(),
-                "fafsa".to_string(),
-            )
-                .await
-        },
-    );
-    let essay_resource = Resource::new(
-        move || (essay_required.get(), access_token.get()),
-        move |(essay_required, access_token)| async move {
-            // Only get this information if the scholarship requested it. Otherwise, return a blank
-            // result.
-            let Some(access_token) = access_token else {
-                return Err(ServerFnError::new("Couldn't find access token"));
-            };
-            if !essay_required {
-                return Ok(HashMap::new());
-            }
-
-            admin_get_all_input_files(
-                access_token,
-                "scholarship_essays".to_string(),
-                scholarship.get().subject,
-            )
-                .await
-        },
-    );
-
-    fn get_eligible_students(
-        students: HashMap<String, HashMap<String, ValueType>>,
-        scholarship: ExpandableInfo,
-        requirements: Vec<ComparisonData>,
-        essay_required: bool,
-        fafsa_required: bool,
-        fafsa_list: HashMap<String, Vec<String>>,
-        essay_list: HashMap<String, Vec<String>>,
-    ) -> Result<HashMap<String, HashMap<String, ValueType>>, Error> {
-        // Get the full map of requirements. Each key contains a list of IDs.
-        let scholarship_requirements = scholarship
-            .data
-            .get("requirements")
-            .map(|val| val.as_map().ok()?)
-            .flatten()
-            .unwrap_or_default();
-
-        // Get the lists of requirements. Maps into string IDs and discards invalid ValueTypes.
-        let requirement_lists = scholarship_requirements
-            .into_iter()
-            .filter_map(|(_, list_val)| {
-                let list = list_val.as_list().ok().flatten()?;
-                let string_list = list
-                    .into_iter()
-                    .filter_map(|v| v.as_string().ok().flatten())
-                    .collect::<Vec<String>>();
-
-                Some(string_list)
-            })
-            .collect::<Vec<Vec<String>>>();
-
-        // Resolve the requirement IDs. Discards all IDs that do not resolve.
-        let resolved_requirements = requirement_lists
-            .into_iter()
-            .map(|list| {
-                list.into_iter()
-                    .filter_map(|id| {
-                        requirements
-                            .iter()
-                            .cloned()
-                            .find(|requirement| requirement.id == id)
-                    })
-                    .collect::<Vec<ComparisonData>>()
-            })
-            .collect::<Vec<Vec<ComparisonData>>>();
-
-        // Check student eligibility. These are all students that have completed the demographics
-        // forms.
-        let eligible_students = students
-            .into_iter()
-            .filter_map(|(id, student)| {
-                // The student needs to pass one requirement from each list.
-                // The easiest ones to check first are whether the scholarship requires a student
-                // essay and/or their fafsa information. If the student does not have these things,
-                // they are skipped.
-
-                if fafsa_required && !fafsa_list.contains_key(&id) {
-                    // Get the student files related to their fafsa and continue. Return false if not there.
-                    debug_log!(
-                        "Student with id {id} failed - FAFSA is required but was not found."
-                    );
-                    return None;
-                }
-
-                if essay_required && !essay_list.contains_key(&id) {
-                    // Get the student's files related to their essays and continue. Return false if not there.
-                    debug_log!(
-                        "Student with id {id} failed - essay is required but was not found."
-                    );
-                    return None;
-                }
-
-                let result = resolved_requirements.iter().all(|list| {
-                    if list.is_empty() {
-                        return true;
-                    }
-                    list.iter().any(|requirement| {
-                        let result = requirement.compare(&student).unwrap_or(false);
-
-                        if !result {
-                            debug_log!(
-                                "Student with id {id} failed - requirement with id {} failed.",
-                                requirement.id
-                            );
-                        }
-
-                        result
-                    })
-                });
-
-                if result { Some((id, student)) } else { None }
-            })
-            .collect::<HashMap<String, HashMap<String, ValueType>>>();
-
-        // debug_log!("Total number of eligible students: {}", eligible_students.len());
-
-        Ok(eligible_students)
-    }
-
-    Effect::new(move || {
-        // We have to wait until we've gotten fafsa/essay requirements.
-        debug_log!("Waiting on essay and FAFSA resources...");
-        if let (Some(fafsa_list), Some(essay_list)) = (fafsa_resource.get(), essay_resource.get()) {
-            debug_log!(
-                "Running eligibility check on {} students",
-                students.get().len()
-            );
-            let eligible_res = get_eligible_students(
-                students.get(),
-                scholarship.get(),
-                requirements.get(),
-                essay_required.get(),
-                fafsa_required.get(),
-                fafsa_list.unwrap_or_default(),
-                essay_list.unwrap_or_default(),
-            );
-
-            match eligible_res {
-                Ok(list) => {
-                    debug_log!("Total number of eligible students: {}", list.len());
-                    eligible_students.set(list);
-                }
-                Err(e) => error_msg.set(format!("Failed to get eligible students list: {e}")),
-            }
-        }
-    });
+    let fafsa_required = Memo::new(move |_| scholarship.with(common::fafsa_required));
+    let essay_required = Memo::new(move |_| scholarship.with(common::essay_required));
 
     // This is actually just a display of all the information from the students list.
     // As defined in the ApplicantsStudentList component, this will already be wrapped in a
@@ -527,51 +322,6 @@ fn AdminApplicantsStudentListView(
             essay_required=essay_required
             fafsa_required=fafsa_required
         />
-        <Show when=move || !error_msg.get().is_empty()>
-            <div>"Error checking student eligibility: "{error_msg}</div>
-        </Show>
-        <div class="m-3 flex flex-col">
-            <div class="flex flex-row p-1 border-b-1 border-black">
-                <div class="pl-5 flex-1 text-lg font-bold text-left">"Last Name"</div>
-                <div class="pr-5 flex-1 text-lg font-bold text-left">"First Name"</div>
-            </div>
-            <For
-                each=move || eligible_students.get()
-                key=|(id, _)| id.clone()
-                children=move |(student_id, student)| {
-                    let student = StoredValue::new(student);
-                    let first_name = Memo::new(move |_| {
-                        student
-                            .get_value()
-                            .get("first_name")
-                            .map(|n| n.as_string().ok().flatten())
-                            .unwrap_or_default()
-                    });
-                    let last_name = Memo::new(move |_| {
-                        student
-                            .get_value()
-                            .get("last_name")
-                            .map(|n| n.as_string().ok().flatten())
-                            .unwrap_or_default()
-                    });
-                    let on_click = move |_| {
-                        navigate_student.run(student_id.clone());
-                    };
-                    // For now, we'll just display each student's first and last name.
-                    // Now I'll add a container with some links. These links will just be appended
-                    // to the end of the current path.
-
-                    view! {
-                        <div
-                            class="flex flex-row p-1 border-y-1 border-gray-200 cursor-pointer hover:bg-yellow-100 transition-all duration-200"
-                            on:click=on_click
-                        >
-                            <div class="pl-5 flex-1 text-lg text-left">{last_name}</div>
-                            <div class="pr-5 flex-1 text-lg text-left">{first_name}</div>
-                        </div>
-                    }
-                }
-            />
-        </div>
+        <ApplicantTable students=students on_select=navigate_student />
     }
 }
@@ -0,0 +1,326 @@
+use crate::common::{DateInfo, DatePurpose, DateRange, parse_local_input};
+use crate::components::{ActionButton, ConfirmDialog, Loading, Row};
+use crate::pages::api::{CreateDates, DeleteDate, UpdateDate, get_important_dates};
+use crate::utils::{browser_offset, to_browser_input};
+use chrono::{DateTime, FixedOffset};
+use leptos::prelude::*;
+use leptos_oidc::AuthSignal;
+
+static INPUT_CLASS: &str = "border-2 m-1.5 p-1.5 mt-0 rounded-md bg-transparent relative flex-1
+    transition-all duration-150 border-red-700";
+
+/// # Admin Dates Page
+///
+/// Lists the important dates that are shown to providers and students, and allows them to be
+/// created, edited and deleted. Dates with a purpose open and close parts of the site, so changes
+/// take effect as soon as they're saved.
+///
+/// Times are entered in the admin's local time zone and are stored with its offset.
+#[component]
+pub fn AdminDatesPage() -> impl IntoView {
+    let auth = expect_context::<AuthSignal>();
+    let access_token =
+        Memo::new(move |_| auth.with(|auth| auth.authenticated().map(|a| a.access_token())));
+
+    let dates = Resource::new(|| (), async |_| get_important_dates().await);
+    let create_action = ServerAction::<CreateDates>::new();
+    let update_action = ServerAction::<UpdateDate>::new();
+    let delete_action = ServerAction::<DeleteDate>::new();
+    let pending = Signal::derive(move || {
+        create_action.pending().get()
+            || update_action.pending().get()
+            || delete_action.pending().get()
+    });
+
+    Effect::watch(
+        move || {
+            (
+                create_action.version().get(),
+                update_action.version().get(),
+                delete_action.version().get(),
+            )
+        },
+        move |_, _, _| dates.refetch(),
+        false,
+    );
+
+    // The new date's editor is only cleared once the date is created, so nothing is lost if it
+    // can't be.
+    let created = RwSignal::new(0_usize);
+    Effect::watch(
+        move || create_action.value().get(),
+        move |value, _, _| {
+            if matches!(value, Some(Ok(()))) {
+                created.update(|count| *count += 1);
+            }
+        },
+        false,
+    );
+
+    let on_create = Callback::new(move |date: DateInfo| {
+        create_action.dispatch(CreateDates {
+            access_token: access_token.get_untracked().unwrap_or_default(),
+            dates: vec![date],
+        });
+    });
+    let on_update = Callback::new(move |date: DateInfo| {
+        update_action.dispatch(UpdateDate {
+            access_token: access_token.get_untracked().unwrap_or_default(),
+            date,
+        });
+    });
+
+    // Deleting asks first, since a date with a purpose may be all that keeps its forms closed.
+    let pending_delete = RwSignal::new(None::<DateInfo>);
+    let delete_message = Signal::derive(move || {
+        pending_delete.with(|date| {
+            date.as_ref().map(|date| match date.purpose {
+                Some(purpose) => format!(
+                    "{:?} will be deleted. If no other dates are for {}, they'll become \
+                    unscheduled, which counts as open and reopens them.",
+                    date.title,
+                    purpose.name()
+                ),
+                None => format!("{:?} will be deleted.", date.title),
+            })
+        })
+    });
+    let on_delete = Callback::new(move |date: DateInfo| pending_delete.set(Some(date)));
+    let on_confirm_delete = Callback::new(move |_| {
+        if let Some(date) = pending_delete.get_untracked() {
+            delete_action.dispatch(DeleteDate {
+                access_token: access_token.get_untracked().unwrap_or_default(),
+                id: date.id,
+            });
+        }
+        pending_delete.set(None);
+    });
+
+    let action_error = move || {
+        [
+            create_action.value().get(),
+            update_action.value().get(),
+            delete_action.value().get(),
+        ]
+        .into_iter()
+        .flatten()
+        .find_map(|result| result.err())
+        .map(|err| {
+            view! {
+                <pre class="font-mono text-red-700 whitespace-pre-wrap">
+                    {err.to_string().trim_start_matches("error running server function: ").to_string()}
+                </pre>
+            }
+        })
+    };
+
+    view! {
+        <div class="flex flex-col flex-1 gap-4 m-4">
+            <h1 class="text-2xl font-bold">"Important Dates"</h1>
+            <span>"Times are entered in your local time zone."</span>
+            {action_error}
+            <h2 class="text-xl font-bold">"New Date"</h2>
+            <DateEditor on_save=on_create created=created pending=pending />
+            <h2 class="text-xl font-bold">"Saved Dates"</h2>
+            <Transition fallback=Loading>
+                {move || {
+                    dates
+                        .get()
+                        .map(|result| match result {
+                            Ok(mut dates) if !dates.is_empty() => {
+                                dates.sort_by_key(DateInfo::get_start_date);
+                                dates
+                                    .into_iter()
+                                    .map(|date| {
+                                        view! {
+                                            <DateEditor
+                                                date=date
+                                                on_save=on_update
+                                                on_delete=on_delete
+                                                pending=pending
+                                            />
+                                        }
+                                    })
+                                    .collect_view()
+                                    .into_any()
+                            }
+                            Ok(_) => view! { <div>"There aren't any dates yet."</div> }.into_any(),
+                            Err(err) => {
+                                view! { <div>"Error while getting dates: "{err.to_string()}</div> }
+                                    .into_any()
+                            }
+                        })
+                }}
+            </Transition>
+            <ConfirmDialog
+                message=delete_message
+                on_confirm=on_confirm_delete
+                on_cancel=Callback::new(move |_| pending_delete.set(None))
+                disabled=pending
+            />
+        </div>
+    }
+}
+
+/// Parses one of the editor's time inputs in the browser's time zone.
+fn parse_input(input: &str) -> Option<DateTime<FixedOffset>> {
+    browser_offset(input).and_then(|offset| parse_local_input(input, offset))
+}
+
+/// Edits a single date. Without a date, the editor creates a new one with a new ID, and is cleared
+/// each time `created` changes.
+#[component]
+fn DateEditor(
+    #[prop(optional)] date: Option<DateInfo>,
+    on_save: Callback<DateInfo>,
+    /// Changes each time a new date is created.
+    #[prop(optional, into)]
+    created: Signal<usize>,
+    /// Asks to delete the saved date.
+    #[prop(optional)]
+    on_delete: Option<Callback<DateInfo>>,
+    pending: Signal<bool>,
+) -> impl IntoView {
+    let saved = StoredValue::new(date.clone());
+    let id = StoredValue::new(date.as_ref().map(|date| date.id.clone()));
+    let title = RwSignal::new(date.as_ref().map(|date| date.title.clone()).unwrap_or_default());
+    let description =
+        RwSignal::new(date.as_ref().map(|date| date.description.clone()).unwrap_or_default());
+    let purpose = RwSignal::new(
+        date.as_ref()
+            .and_then(|date| date.purpose)
+            .map(|purpose| format!("{purpose:?}"))
+            .unwrap_or_default(),
+    );
+    let has_end = RwSignal::new(
+        date.as_ref()
+            .is_none_or(|date| matches!(date.date, DateRange::Range(..))),
+    );
+    let start = RwSignal::new(String::new());
+    let end = RwSignal::new(String::new());
+    let error = RwSignal::new(None::<String>);
+
+    // The inputs are in the browser's time zone, which is only known once the page is hydrated.
+    let range = date.map(|date| date.date);
+    Effect::new(move || match &range {
+        Some(DateRange::Single(date)) => start.set(to_browser_input(date)),
+        Some(DateRange::Range(start_date, end_date)) => {
+            start.set(to_browser_input(start_date));
+            end.set(to_browser_input(end_date));
+        }
+        None => {}
+    });
+
+    let on_click_save = move |_| {
+        let Some(start_date) = parse_input(&start.get_untracked()) else {
+            error.set(Some("Enter a start time.".to_string()));
+            return;
+        };
+        let date = if has_end.get_untracked() {
+            let Some(end_date) = parse_input(&end.get_untracked()) else {
+                error.set(Some("Enter an end time.".to_string()));
+                return;
+            };
+            DateRange::Range(start_date, end_date)
+        } else {
+            DateRange::Single(start_date)
+        };
+
+        let info = DateInfo {
+            id: id.get_value().unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+            title: title.get_untracked().trim().to_string(),
+            date,
+            description: description.get_untracked().trim().to_string(),
+            purpose: purpose.with_untracked(|purpose| {
+                DatePurpose::ALL
+                    .into_iter()
+                    .find(|option| format!("{option:?}") == *purpose)
+            }),
+        };
+        if let Err(msg) = info.validate() {
+            error.set(Some(msg));
+            return;
+        }
+
+        error.set(None);
+        on_save.run(info);
+    };
+    let on_click_delete = move |_| {
+        if let (Some(on_delete), Some(date)) = (on_delete, saved.get_value()) {
+            on_delete.run(date);
+        }
+    };
+
+    Effect::watch(
+        move || created.get(),
+        move |_, _, _| {
+            title.set(String::new());
+            description.set(String::new());
+            purpose.set(String::new());
+            start.set(String::new());
+            end.set(String::new());
+        },
+        false,
+    );
+
+    view! {
+        <div class="flex flex-col gap-1 rounded-lg border-1 border-gray-300 p-2">
+            <Row>
+                <label class="flex flex-col flex-1">
+                    <span class="ml-1.5 mb-0 font-bold">"Title"</span>
+                    <input class=INPUT_CLASS r#type="text" bind:value=title />
+                </label>
+                <label class="flex flex-col flex-1">
+                    <span class="ml-1.5 mb-0 font-bold">"Purpose"</span>
+                    <select
+                        class=INPUT_CLASS
+                        prop:value=purpose
+                        on:change=move |e| purpose.set(event_target_value(&e))
+                    >
+                        <option value="">"None (only shown in the date list)"</option>
+                        {DatePurpose::ALL
+                            .into_iter()
+                            .map(|option| {
+                                view! { <option value=format!("{option:?}")>{option.name()}</option> }
+                            })
+                            .collect_view()}
+                    </select>
+                </label>
+            </Row>
+            <Row>
+                <label class="flex flex-col flex-1">
+                    <span class="ml-1.5 mb-0 font-bold">"Start"</span>
+                    <input class=INPUT_CLASS r#type="datetime-local" bind:value=start />
+                </label>
+                <Show when=move || has_end.get()>
+                    <label class="flex flex-col flex-1">
+                        <span class="ml-1.5 mb-0 font-bold">"End"</span>
+                        <input class=INPUT_CLASS r#type="datetime-local" bind:value=end />
+                    </label>
+                </Show>
+            </Row>
+            <label class="flex flex-row items-center gap-2 ml-1.5">
+                <input type="checkbox" bind:checked=has_end />
+                "Has an end time"
+            </label>
+            <label class="flex flex-col flex-1">
+                <span class="ml-1.5 mb-0 font-bold">"Description"</span>
+                <textarea class=INPUT_CLASS rows=2 bind:value=description />
+            </label>
+            {move || error.get().map(|msg| view! { <span class="ml-1.5 text-red-700">{msg}</span> })}
+            <Row>
+                <ActionButton on:click=on_click_save disabled=pending>
+                    {if on_delete.is_some() { "Save" } else { "Create" }}
+                </ActionButton>
+                {on_delete
+                    .map(|_| {
+                        view! {
+                            <ActionButton on:click=on_click_delete disabled=pending>
+                                "Delete"
+                            </ActionButton>
+                        }
+                    })}
+            </Row>
+        </div>
+    }
+}
@@ -0,0 +1,822 @@
+use crate::common::{
+    EntryLimits, FieldMigration, FormDefinition, FormSection, InputConditions, InputType,
+    TextLimits, ValueType, parent_path,
+};
+use crate::components::{ActionButton, DynamicForm, Loading, Panel, Row};
+use crate::pages::api::{
+    PublishForm, RetryFormMigrations, SaveFormDraft, get_form_draft, get_student_forms,
+};
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use leptos_oidc::AuthSignal;
+use std::collections::{HashMap, HashSet};
+
+/// The kinds of inputs that can be chosen in the form builder, named after their `InputType`.
+static INPUT_KINDS: [&str; 11] = [
+    "Text",
+    "TextArea",
+    "Number",
+    "Currency",
+    "Email",
+    "Phone",
+    "Date",
+    "Radio",
+    "Checkbox",
+    "Select",
+    "MultiEntry",
+];
+
+/// The value of the migration select that retires a removed field. Keys can't contain `#`, so this
+/// can't be confused with a field that it could be renamed to.
+static RETIRE_CHOICE: &str = "#retire";
+
+static INPUT_CLASS: &str = "border-2 m-1.5 p-1.5 mt-0 rounded-md bg-transparent relative flex-1
+    transition-all duration-150 border-red-700";
+
+/// The parts of an [`InputType`] that the form builder edits. Every variant is converted into
+/// the same set of parts, so that the kind of an input can be changed without losing its key and
+/// label. Conditions are kept as text, so that rules can be typed before they're complete.
+#[derive(Clone, Default)]
+struct FieldParts {
+    kind: String,
+    key: String,
+    label: String,
+    placeholder: String,
+    options: Vec<String>,
+    schema: Vec<InputType>,
+    entry_limits: EntryLimits,
+    domains: Vec<String>,
+    limits: TextLimits,
+    required: bool,
+    conditions: InputConditions,
+}
+
+impl From<&InputType> for FieldParts {
+    fn from(field: &InputType) -> Self {
+        let mut parts = FieldParts {
+            key: field.key().to_string(),
+            required: field.required(),
+            ..Default::default()
+        };
+        if let InputType::Conditional(_, conditions) = field {
+            parts.conditions = conditions.clone();
+        }
+
+        match field.input() {
+            InputType::Text(_, label, placeholder, _)
+            | InputType::Number(_, label, placeholder, _)
+            | InputType::Phone(_, label, placeholder, _)
+            | InputType::Currency(_, label, placeholder, _) => {
+                parts.label = label.clone();
+                parts.placeholder = placeholder.clone();
+            }
+            InputType::Radio(_, label, options, _)
+            | InputType::Checkbox(_, label, options, _)
+            | InputType::Select(_, label, options, _) => {
+                parts.label = label.clone();
+                parts.options = options.clone();
+            }
+            InputType::MultiEntry(_, label, schema, entry_limits, _) => {
+                parts.label = label.clone();
+                parts.schema = schema.clone();
+                parts.entry_limits = entry_limits.clone();
+            }
+            InputType::Date(_, label, _) => parts.label = label.clone(),
+            InputType::TextArea(_, label, placeholder, limits, _) => {
+                parts.label = label.clone();
+                parts.placeholder = placeholder.clone();
+                parts.limits = limits.clone();
+            }
+            InputType::Email(_, label, placeholder, domains, _) => {
+                parts.label = label.clone();
+                parts.placeholder = placeholder.clone();
+                parts.domains = domains.clone();
+            }
+            InputType::Conditional(..) => {}
+        }
+        parts.kind = match field.input() {
+            InputType::Text(..) => "Text",
+            InputType::Number(..) => "Number",
+            InputType::Radio(..) => "Radio",
+            InputType::Checkbox(..) => "Checkbox",
+            InputType::Select(..) => "Select",
+            InputType::MultiEntry(..) => "MultiEntry",
+            InputType::Date(..) => "Date",
+            InputType::TextArea(..) => "TextArea",
+            InputType::Email(..) => "Email",
+            InputType::Phone(..) => "Phone",
+            InputType::Currency(..) => "Currency",
+            InputType::Conditional(..) => unreachable!("input() removes conditions"),
+        }
+        .to_string();
+
+        parts
+    }
+}
+
+impl FieldParts {
+    /// Converts the parts back into an `InputType`. Unknown kinds become text inputs.
+    fn into_input(self) -> InputType {
+        let FieldParts {
+            kind,
+            key,
+            label,
+            placeholder,
+            options,
+            schema,
+            entry_limits,
+            domains,
+            limits,
+            required,
+            conditions,
+        } = self;
+
+        let input = match kind.as_str() {
+            "Number" => InputType::Number(key, label, placeholder, required),
+            "Radio" => InputType::Radio(key, label, options, required),
+            "Checkbox" => InputType::Checkbox(key, label, options, required),
+            "Select" => InputType::Select(key, label, options, required),
+            "MultiEntry" => InputType::MultiEntry(key, label, schema, entry_limits, required),
+            "Date" => InputType::Date(key, label, required),
+            "TextArea" => InputType::TextArea(key, label, placeholder, limits, required),
+            // New email questions accept any domain until some are entered.
+            "Email" if domains.is_empty() => {
+                InputType::Email(key, label, placeholder, vec!["*".to_string()], required)
+            }
+            "Email" => InputType::Email(key, label, placeholder, domains, required),
+            "Phone" => InputType::Phone(key, label, placeholder, required),
+            "Currency" => InputType::Currency(key, label, placeholder, required),
+            _ => InputType::Text(key, label, placeholder, required),
+        };
+
+        if conditions == InputConditions::default() {
+            input
+        } else {
+            InputType::Conditional(Box::new(input), conditions)
+        }
+    }
+}
+
+/// Removes blank options and email domains, which are left behind while they're being typed one
+/// per line.
+fn remove_blank_options(fields: &mut [InputType]) {
+    for field in fields {
+        match field {
+            InputType::Radio(_, _, options, _)
+            | InputType::Checkbox(_, _, options, _)
+            | InputType::Select(_, _, options, _)
+            | InputType::Email(_, _, _, options, _) => {
+                options.retain(|option| !option.trim().is_empty());
+            }
+            InputType::MultiEntry(_, _, schema, ..) => remove_blank_options(schema),
+            InputType::Conditional(input, _) => {
+                remove_blank_options(std::slice::from_mut(input.as_mut()))
+            }
+            _ => {}
+        }
+    }
+}
+
+/// # Admin Forms Page
+///
+/// Allows an administrator to build the student forms. A form is chosen from the list, or created
+/// using a new ID, and then its questions can be added, reordered, removed and edited, with a
+/// preview of the form underneath. Changes can be saved as a draft, which students don't see,
+/// and then published as a new version.
+///
+/// Publishing a version that removes questions requires a migration for each removed path, which
+/// either renames the question, moving students' stored answers to a new question in the same
+/// list, or retires it, keeping the stored answers without asking for them anymore.
+#[component]
+pub fn AdminFormsPage() -> impl IntoView {
+    let auth = expect_context::<AuthSignal>();
+    let access_token =
+        Memo::new(move |_| auth.with(|auth| auth.authenticated().map(|a| a.access_token())));
+
+    let forms = Resource::new(|| (), async |_| get_student_forms().await);
+    let save_action = ServerAction::<SaveFormDraft>::new();
+    let publish_action = ServerAction::<PublishForm>::new();
+    let retry_action = ServerAction::<RetryFormMigrations>::new();
+
+    // The form that's being edited, and its published version, if it has one.
+    let draft = RwSignal::new(FormDefinition::new("", "", ""));
+    let published = RwSignal::new(None::<FormDefinition>);
+    let loaded = RwSignal::new(false);
+    let migrations = RwSignal::new(HashMap::<String, FieldMigration>::new());
+    let new_id = RwSignal::new(String::new());
+    let preview_data = RwSignal::new(HashMap::<String, ValueType>::new());
+
+    let open_form = move |definition: FormDefinition| {
+        loaded.set(false);
+        migrations.set(HashMap::new());
+        preview_data.set(HashMap::new());
+        published.set(Some(definition.clone()));
+
+        let access_token = access_token.get_untracked().unwrap_or_default();
+        spawn_local(async move {
+            let form_draft = get_form_draft(access_token, definition.id.clone()).await;
+            draft.set(form_draft.ok().flatten().unwrap_or(definition));
+            loaded.set(true);
+        });
+    };
+
+    let on_click_new = move |_| {
+        let id = new_id.get_untracked().trim().to_lowercase();
+        if id.is_empty() {
+            return;
+        }
+
+        let existing = forms.get_untracked().and_then(|forms| {
+            forms
+                .ok()?
+                .into_iter()
+                .find(|form| form.id.eq_ignore_ascii_case(&id))
+        });
+        match existing {
+            Some(definition) => open_form(definition),
+            None => {
+                migrations.set(HashMap::new());
+                preview_data.set(HashMap::new());
+                published.set(None);
+                draft.set(FormDefinition::new(id, "", "").section(FormSection::default()));
+                loaded.set(true);
+            }
+        }
+    };
+
+    // Paths that the published version stores, but that the draft doesn't have a question for.
+    let removed_paths = Memo::new(move |_| {
+        let Some(published) = published.get() else {
+            return Vec::new();
+        };
+        draft.with(|draft| draft.removed_paths(&published))
+    });
+
+    // Paths that are new in the draft, which removed questions can be renamed to.
+    let added_paths = Memo::new(move |_| {
+        let stored = published.with(|published| {
+            published
+                .iter()
+                .flat_map(|published| {
+                    published
+                        .field_paths()
+                        .into_iter()
+                        .chain(published.retired.iter().cloned())
+                })
+                .collect::<HashSet<String>>()
+        });
+        draft.with(|draft| {
+            draft
+                .field_paths()
+                .into_iter()
+                .filter(|path| !path.is_empty() && !path.ends_with('.') && !stored.contains(path))
+                .collect::<Vec<String>>()
+        })
+    });
+
+    let cleaned_draft = move || {
+        let mut definition = draft.get_untracked();
+        for section in &mut definition.sections {
+            remove_blank_options(&mut section.fields);
+        }
+        definition
+    };
+
+    let on_click_save = move |_| {
+        save_action.dispatch(SaveFormDraft {
+            access_token: access_token.get_untracked().unwrap_or_default(),
+            definition: cleaned_draft(),
+        });
+    };
+
+    let on_click_publish = move |_| {
+        let removed = removed_paths.get_untracked();
+        publish_action.dispatch(PublishForm {
+            access_token: access_token.get_untracked().unwrap_or_default(),
+            definition: cleaned_draft(),
+            migrations: migrations.with_untracked(|migrations| {
+                removed
+                    .iter()
+                    .filter_map(|key| migrations.get(key).cloned())
+                    .collect()
+            }),
+        });
+    };
+
+    let on_click_retry = move |_| {
+        retry_action.dispatch(RetryFormMigrations {
+            access_token: access_token.get_untracked().unwrap_or_default(),
+            form_id: draft.with_untracked(|draft| draft.id.clone()),
+        });
+    };
+
+    // Once a version is published, it becomes the version that later changes are compared to.
+    let on_published = move |definition: FormDefinition| {
+        migrations.set(HashMap::new());
+        published.set(Some(definition.clone()));
+        draft.set(definition);
+        forms.refetch();
+    };
+    Effect::new(move || {
+        if let Some(Ok(definition)) = publish_action.value().get() {
+            on_published(definition);
+        }
+    });
+    Effect::new(move || {
+        if let Some(Ok(definition)) = retry_action.value().get() {
+            on_published(definition);
+        }
+    });
+
+    let status = move || {
+        if save_action.pending().get() {
+            return "Saving draft...".to_string();
+        }
+        if publish_action.pending().get() {
+            return "Publishing...".to_string();
+        }
+
+        match (publish_action.value().get(), save_action.value().get()) {
+            (Some(Err(err)), _) => format!("Couldn't publish the form: {err}"),
+            (Some(Ok(definition)), _) => format!("Published version {}.", definition.version),
+            (None, Some(Ok(()))) => "Saved draft.".to_string(),
+            (None, Some(Err(err))) => format!("Couldn't save the draft: {err}"),
+            (None, None) => String::new(),
+        }
+    };
+
+    let form_list = move || {
+        forms.get().map(|result| match result {
+            Ok(forms) => forms
+                .into_iter()
+                .map(|definition| {
+                    let text = format!("{} ({})", definition.tab_text, definition.id);
+                    view! {
+                        <ActionButton on:click=move |_| open_form(definition.clone())>
+                            {text}
+                        </ActionButton>
+                    }
+                })
+                .collect_view()
+                .into_any(),
+            Err(err) => view! { <span>{format!("Couldn't load forms: {err}")}</span> }.into_any(),
+        })
+    };
+
+    let retry_status = move || {
+        if retry_action.pending().get() {
+            return "Migrating...".to_string();
+        }
+        match retry_action.value().get() {
+            Some(Ok(definition)) => format!("Finished the migrations of version {}.", definition.version),
+            Some(Err(err)) => format!("Couldn't finish the migrations: {err}"),
+            None => String::new(),
+        }
+    };
+
+    let migration_list = move || {
+        removed_paths
+            .get()
+            .into_iter()
+            .map(|key| {
+                let from = key.clone();
+                let parent = parent_path(&key).to_string();
+                let on_change = move |e| {
+                    let choice = event_target_value(&e);
+                    migrations.update(|migrations| {
+                        if choice.is_empty() {
+                            migrations.remove(&from);
+                        } else if choice == RETIRE_CHOICE {
+                            migrations
+                                .insert(from.clone(), FieldMigration::Retire { key: from.clone() });
+                        } else {
+                            migrations.insert(
+                                from.clone(),
+                                FieldMigration::Rename {
+                                    from: from.clone(),
+                                    to: choice,
+                                },
+                            );
+                        }
+                    });
+                };
+
+                view! {
+                    <label class="flex flex-col flex-1">
+                        <span class="ml-1.5 mb-0 font-bold">{format!("{key:?} was removed:")}</span>
+                        <select class=INPUT_CLASS on:change=on_change>
+                            <option value="">"Choose a migration..."</option>
+                            <option value=RETIRE_CHOICE>"Retire it and keep stored answers"</option>
+                            {added_paths
+                                .get()
+                                .into_iter()
+                                .filter(|to| parent_path(to) == parent)
+                                .map(|to| {
+                                    let text = format!("Rename it to {to:?}");
+                                    view! { <option value=to>{text}</option> }
+                                })
+                                .collect_view()}
+                        </select>
+                    </label>
+                }
+            })
+            .collect_view()
+    };
+
+    view! {
+        <div class="mx-auto px-6">
+            <div class="flex flex-col gap-4">
+                <div class="self-centered text-lg mt-6">
+                    "Choose a student form to edit, or enter the ID of a new form."
+                </div>
+                <Transition fallback=Loading>
+                    <div class="flex flex-row flex-wrap">{form_list}</div>
+                </Transition>
+                <Row>
+                    <input
+                        class=INPUT_CLASS
+                        placeholder="new_form_id"
+                        prop:value=new_id
+                        on:input=move |e| new_id.set(event_target_value(&e))
+                    />
+                    <ActionButton on:click=on_click_new>"New Form"</ActionButton>
+                </Row>
+                <Show when=move || loaded.get()>
+                    <Panel>
+                        <span class="text-xl font-bold">
+                            {move || {
+                                let version = published.with(|published| published.as_ref().map(|p| p.version));
+                                match version {
+                                    Some(version) => format!("Editing {:?} (published version {version})", draft.with(|d| d.id.clone())),
+                                    None => format!("Creating {:?}", draft.with(|d| d.id.clone())),
+                                }
+                            }}
+                        </span>
+                        <Row>
+                            <BuilderTextField
+                                label="Title"
+                                value=Signal::derive(move || draft.with(|d| d.title.clone()))
+                                on_input=move |text| draft.update(|d| d.title = text)
+                            />
+                            <BuilderTextField
+                                label="Tab Text"
+                                value=Signal::derive(move || draft.with(|d| d.tab_text.clone()))
+                                on_input=move |text| draft.update(|d| d.tab_text = text)
+                            />
+                            <BuilderTextField
+                                label="Path"
+                                value=Signal::derive(move || draft.with(|d| d.path.clone()))
+                                on_input=move |text| draft.update(|d| d.path = text)
+                            />
+                        </Row>
+                        <BuilderTextField
+                            label="Description"
+                            value=Signal::derive(move || draft.with(|d| d.description.clone()))
+                            on_input=move |text| draft.update(|d| d.description = text)
+                        />
+                        <For
+                            each=move || 0..draft.with(|d| d.sections.len())
+                            key=|index| *index
+                            children=move |index| view! { <SectionEditor draft=draft index=index /> }
+                        />
+                        <ActionButton on:click=move |_| draft.update(|d| d.sections.push(FormSection::default()))>
+                            "Add Section"
+                        </ActionButton>
+                    </Panel>
+                    <Show when=move || !removed_paths.get().is_empty()>
+                        <Panel>
+                            <span class="text-xl font-bold">"Migrations"</span>
+                            <span>
+                                "Students may have answered these questions already. Choose what happens to their answers when this version is published."
+                            </span>
+                            {migration_list}
+                        </Panel>
+                    </Show>
+                    <Row>
+                        <ActionButton on:click=on_click_save disabled=save_action.pending()>
+                            "Save Draft"
+                        </ActionButton>
+                        <ActionButton on:click=on_click_publish disabled=publish_action.pending()>
+                            "Publish"
+                        </ActionButton>
+                    </Row>
+                    <div>{status}</div>
+                    <Show when=move || matches!(publish_action.value().get(), Some(Err(_)))>
+                        <Row>
+                            <ActionButton on:click=on_click_retry disabled=retry_action.pending()>
+                                "Retry Migrations"
+                            </ActionButton>
+                            <span>{retry_status}</span>
+                        </Row>
+                    </Show>
+                    <span class="text-xl font-bold">"Preview"</span>
+                    <DynamicForm
+                        definition=Signal::derive(move || draft.get())
+                        data_map=preview_data
+                        on_submit=Callback::new(|_| ())
+                    />
+                </Show>
+            </div>
+        </div>
+    }
+}
+
+/// A text input used by the form builder. Multi-line inputs are used for lists, with one item per
+/// line.
+#[component]
+fn BuilderTextField(
+    #[prop(into)] label: String,
+    #[prop(into)] value: Signal<String>,
+    #[prop(into)] on_input: Callback<String>,
+    #[prop(optional)] multiline: bool,
+) -> impl IntoView {
+    let on_input = move |e| on_input.run(event_target_value(&e));
+
+    view! {
+        <label class="flex flex-col flex-1">
+            <span class="block ml-1.5 mb-0 font-bold">{label}</span>
+            {if multiline {
+                view! { <textarea class=INPUT_CLASS rows=4 prop:value=value on:input=on_input /> }
+                    .into_any()
+            } else {
+                view! { <input class=INPUT_CLASS r#type="text" prop:value=value on:input=on_input /> }
+                    .into_any()
+            }}
+        </label>
+    }
+}
+
+/// Edits a single section of the form, including its questions.
+#[component]
+fn SectionEditor(draft: RwSignal<FormDefinition>, index: usize) -> impl IntoView {
+    let section = move || draft.with(|d| d.sections.get(index).cloned().unwrap_or_default());
+    let update = move |edit: &dyn Fn(&mut FormSection)| {
+        draft.update(|d| {
+            if let Some(section) = d.sections.get_mut(index) {
+                edit(section);
+            }
+        })
+    };
+
+    let move_section = move |offset: isize| {
+        draft.update(|d| {
+            let target = index.wrapping_add_signed(offset);
+            if target < d.sections.len() {
+                d.sections.swap(index, target);
+            }
+        })
+    };
+
+    let on_change_fields = move |fields: Vec<InputType>| update(&|s| s.fields = fields.clone());
+
+    view! {
+        <div class="flex flex-col gap-2 border-t-2 border-gray-600/20 pt-2">
+            <Row>
+                <BuilderTextField
+                    label=format!("Section {} Title", index + 1)
+                    value=Signal::derive(move || section().title)
+                    on_input=move |text: String| update(&|s| s.title = text.clone())
+                />
+                <BuilderTextField
+                    label="Section Description"
+                    value=Signal::derive(move || section().description)
+                    on_input=move |text: String| update(&|s| s.description = text.clone())
+                />
+            </Row>
+            <FieldListEditor
+                fields=Signal::derive(move || section().fields)
+                on_change=on_change_fields
+            />
+            <Row>
+                <ActionButton on:click=move |_| move_section(-1)>"Move Section Up"</ActionButton>
+                <ActionButton on:click=move |_| move_section(1)>"Move Section Down"</ActionButton>
+                <ActionButton on:click=move |_| draft.update(|d| {
+                    d.sections.remove(index);
+                })>"Remove Section"</ActionButton>
+            </Row>
+        </div>
+    }
+}
+
+/// Edits a list of questions, which is either a section of the form or the questions of each
+/// entry of a `MultiEntry` input.
+#[component]
+fn FieldListEditor(
+    #[prop(into)] fields: Signal<Vec<InputType>>,
+    #[prop(into)] on_change: Callback<Vec<InputType>>,
+) -> impl IntoView {
+    let on_click_add = move |_| {
+        let mut list = fields.get_untracked();
+        list.push(InputType::Text(
+            String::new(),
+            String::new(),
+            String::new(),
+            false,
+        ));
+        on_change.run(list);
+    };
+
+    view! {
+        <div class="flex flex-col gap-2 ml-4">
+            <For
+                each=move || 0..fields.with(Vec::len)
+                key=|index| *index
+                children=move |index| view! { <FieldEditor fields=fields on_change=on_change index=index /> }
+            />
+            <ActionButton on:click=on_click_add>"Add Question"</ActionButton>
+        </div>
+    }
+}
+
+/// Edits a single question: its kind, key, label, placeholder or options, and whether it's
+/// required. Questions can also be moved within their list or removed.
+#[component]
+fn FieldEditor(
+    fields: Signal<Vec<InputType>>,
+    on_change: Callback<Vec<InputType>>,
+    index: usize,
+) -> impl IntoView {
+    let parts = move || {
+        fields
+            .with(|fields| fields.get(index).map(FieldParts::from))
+            .unwrap_or_default()
+    };
+    let edit = move |edit: &dyn Fn(&mut FieldParts)| {
+        let mut list = fields.get_untracked();
+        if let Some(field) = list.get_mut(index) {
+            let mut field_parts = FieldParts::from(&*field);
+            edit(&mut field_parts);
+            *field = field_parts.into_input();
+            on_change.run(list);
+        }
+    };
+    let move_field = move |offset: isize| {
+        let mut list = fields.get_untracked();
+        let target = index.wrapping_add_signed(offset);
+        if target < list.len() {
+            list.swap(index, target);
+            on_change.run(list);
+        }
+    };
+    let on_click_remove = move |_| {
+        let mut list = fields.get_untracked();
+        list.remove(index);
+        on_change.run(list);
+    };
+
+    let on_change_schema = move |schema: Vec<InputType>| edit(&|p| p.schema = schema.clone());
+
+    let placeholder_field = move || {
+        view! {
+            <BuilderTextField
+                label="Placeholder"
+                value=Signal::derive(move || parts().placeholder)
+                on_input=move |text: String| edit(&|p| p.placeholder = text.clone())
+            />
+        }
+    };
+    let limit_text = |limit: Option<usize>| limit.map(|max| max.to_string()).unwrap_or_default();
+
+    // Only the kind decides which details are shown, so typing in them doesn't replace them.
+    let kind = Memo::new(move |_| parts().kind);
+    let details = move || match kind.get().as_str() {
+        "Radio" | "Checkbox" | "Select" => view! {
+            <BuilderTextField
+                label="Options (one per line)"
+                multiline=true
+                value=Signal::derive(move || parts().options.join("\n"))
+                on_input=move |text: String| {
+                    edit(&|p| p.options = text.split('\n').map(str::to_string).collect())
+                }
+            />
+        }
+        .into_any(),
+        "Date" => ().into_any(),
+        "Email" => view! {
+            <Row>
+                {placeholder_field}
+                <BuilderTextField
+                    label="Email Domains (one per line, * for any)"
+                    multiline=true
+                    value=Signal::derive(move || parts().domains.join("\n"))
+                    on_input=move |text: String| {
+                        edit(&|p| p.domains = text.split('\n').map(str::to_string).collect())
+                    }
+                />
+            </Row>
+        }
+        .into_any(),
+        "TextArea" => view! {
+            <Row>
+                {placeholder_field}
+                <BuilderTextField
+                    label="Max Characters (optional)"
+                    value=Signal::derive(move || limit_text(parts().limits.max_chars))
+                    on_input=move |text: String| {
+                        edit(&|p| p.limits.max_chars = text.trim().parse().ok())
+                    }
+                />
+                <BuilderTextField
+                    label="Max Words (optional)"
+                    value=Signal::derive(move || limit_text(parts().limits.max_words))
+                    on_input=move |text: String| {
+                        edit(&|p| p.limits.max_words = text.trim().parse().ok())
+                    }
+                />
+            </Row>
+        }
+        .into_any(),
+        "MultiEntry" => view! {
+            <Row>
+                <BuilderTextField
+                    label="Min Entries (optional)"
+                    value=Signal::derive(move || {
+                        limit_text(Some(parts().entry_limits.min).filter(|min| *min > 0))
+                    })
+                    on_input=move |text: String| {
+                        edit(&|p| p.entry_limits.min = text.trim().parse().unwrap_or_default())
+                    }
+                />
+                <BuilderTextField
+                    label="Max Entries (optional)"
+                    value=Signal::derive(move || limit_text(parts().entry_limits.max))
+                    on_input=move |text: String| {
+                        edit(&|p| p.entry_limits.max = text.trim().parse().ok())
+                    }
+                />
+            </Row>
+            <span class="ml-1.5 font-bold">"Questions in each entry:"</span>
+            <FieldListEditor
+                fields=Signal::derive(move || parts().schema)
+                on_change=on_change_schema
+            />
+        }
+        .into_any(),
+        _ => placeholder_field().into_any(),
+    };
+
+    view! {
+        <div class="flex flex-col gap-1 border-l-4 border-red-800/40 pl-2">
+            <Row>
+                <label class="flex flex-col flex-1">
+                    <span class="ml-1.5 mb-0 font-bold">"Kind"</span>
+                    <select
+                        class=INPUT_CLASS
+                        prop:value=kind
+                        on:change=move |e| {
+                            let kind = event_target_value(&e);
+                            edit(&|p| p.kind = kind.clone())
+                        }
+                    >
+                        {INPUT_KINDS
+                            .iter()
+                            .map(|kind| view! { <option value=*kind>{*kind}</option> })
+                            .collect_view()}
+                    </select>
+                </label>
+                <BuilderTextField
+                    label="Key"
+                    value=Signal::derive(move || parts().key)
+                    on_input=move |text: String| edit(&|p| p.key = text.clone())
+                />
+                <BuilderTextField
+                    label="Label"
+                    value=Signal::derive(move || parts().label)
+                    on_input=move |text: String| edit(&|p| p.label = text.clone())
+                />
+            </Row>
+            {details}
+            <Row>
+                <BuilderTextField
+                    label="Show When (optional rule)"
+                    value=Signal::derive(move || parts().conditions.shown_when)
+                    on_input=move |text: String| edit(&|p| p.conditions.shown_when = text.clone())
+                />
+                <BuilderTextField
+                    label="Required When (optional rule)"
+                    value=Signal::derive(move || parts().conditions.required_when)
+                    on_input=move |text: String| {
+                        edit(&|p| p.conditions.required_when = text.clone())
+                    }
+                />
+            </Row>
+            <Row>
+                <label class="flex flex-row flex-1 items-center gap-2 m-1.5">
+                    <input
+                        r#type="checkbox"
+                        prop:checked=move || parts().required
+                        on:change=move |e| {
+                            let required = event_target_checked(&e);
+                            edit(&|p| p.required = required)
+                        }
+                    />
+                    <span>"Required"</span>
+                </label>
+                <ActionButton on:click=move |_| move_field(-1)>"Up"</ActionButton>
+                <ActionButton on:click=move |_| move_field(1)>"Down"</ActionButton>
+                <ActionButton on:click=on_click_remove>"Remove"</ActionButton>
+            </Row>
+        </div>
+    }
+    .into_any()
+}
@@ -1,13 +1,19 @@
 ﻿mod shell;
 mod home_page;
+mod forms_page;
 mod provider_page;
 mod scholarship_page;
 mod utils_page;
+mod progress_page;
+mod dates_page;
 mod applicants_list;
 
 pub use shell::*;
 pub use home_page::*;
+pub use forms_page::*;
 pub use provider_page::*;
 pub use scholarship_page::*;
 pub use utils_page::*;
+pub use progress_page::*;
+pub use dates_page::*;
 pub use applicants_list::*;
@@ -0,0 +1,238 @@
+use crate::common::{DatePurpose, parse_local_input, to_local_input};
+use crate::components::{ActionButton, Loading};
+use crate::pages::api::{
+    AdminSetExtension, StudentCompletion, admin_get_completion_roster, admin_get_extensions,
+};
+use crate::utils::browser_offset;
+use chrono::{DateTime, FixedOffset};
+use leptos::prelude::*;
+use leptos_oidc::AuthSignal;
+use std::collections::HashMap;
+
+/// # Admin Progress Page
+///
+/// Lists every student's progress through the application, least complete first, so that
+/// students who are behind can be contacted before the deadline. Students who need more time can
+/// be given an extension, which lets them keep editing their forms after the window closes.
+#[component]
+pub fn AdminProgressPage() -> impl IntoView {
+    let auth = expect_context::<AuthSignal>();
+    let access_token =
+        Memo::new(move |_| auth.with(|auth| auth.authenticated().map(|a| a.access_token())));
+
+    let roster = Resource::new(
+        move || access_token.get(),
+        async move |access_token| {
+            let Some(access_token) = access_token else {
+                return Err(ServerFnError::new("Couldn't find access token"));
+            };
+            admin_get_completion_roster(access_token).await
+        },
+    );
+    let extensions = Resource::new(
+        move || access_token.get(),
+        async move |access_token| {
+            let Some(access_token) = access_token else {
+                return Err(ServerFnError::new("Couldn't find access token"));
+            };
+            admin_get_extensions(access_token, DatePurpose::StudentForms).await
+        },
+    );
+    let extension_action = ServerAction::<AdminSetExtension>::new();
+    let only_incomplete = RwSignal::new(true);
+
+    Effect::watch(
+        move || extension_action.value().get(),
+        move |value, _, _| {
+            if let Some(Ok(())) = value {
+                extensions.refetch();
+            }
+        },
+        false,
+    );
+
+    let on_set_extension = Callback::new(
+        move |(student_id, until): (String, Option<DateTime<FixedOffset>>)| {
+            extension_action.dispatch(AdminSetExtension {
+                access_token: access_token.get_untracked().unwrap_or_default(),
+                purpose: DatePurpose::StudentForms,
+                student_id,
+                until,
+            });
+        },
+    );
+    let extension_error = move || {
+        extension_action.value().get().and_then(|result| result.err()).map(|err| {
+            view! {
+                <div class="text-red-700">"Couldn't save the extension: "{err.to_string()}</div>
+            }
+        })
+    };
+
+    view! {
+        <div class="flex flex-col flex-1 gap-4 m-4">
+            <h1 class="text-2xl font-bold">"Application Progress"</h1>
+            <label class="flex flex-row items-center gap-2">
+                <input type="checkbox" bind:checked=only_incomplete />
+                "Only show incomplete applications"
+            </label>
+            {extension_error}
+            <Transition fallback=Loading>
+                {move || {
+                    roster
+                        .get()
+                        .map(|result| match result {
+                            Ok(roster) => {
+                                let roster = roster
+                                    .into_iter()
+                                    .filter(|student| {
+                                        !only_incomplete.get() || !student.completion.is_complete()
+                                    })
+                                    .collect::<Vec<StudentCompletion>>();
+                                let extensions = extensions
+                                    .get()
+                                    .and_then(|result| result.ok())
+                                    .unwrap_or_default();
+                                view! {
+                                    <ProgressTable
+                                        roster=roster
+                                        extensions=extensions
+                                        on_set_extension=on_set_extension
+                                        pending=extension_action.pending()
+                                    />
+                                }
+                                    .into_any()
+                            }
+                            Err(err) => {
+                                view! {
+                                    <div>"Error while getting application progress: "{err.to_string()}</div>
+                                }
+                                    .into_any()
+                            }
+                        })
+                }}
+            </Transition>
+        </div>
+    }
+}
+
+#[component]
+fn ProgressTable(
+    roster: Vec<StudentCompletion>,
+    extensions: HashMap<String, DateTime<FixedOffset>>,
+    on_set_extension: Callback<(String, Option<DateTime<FixedOffset>>)>,
+    #[prop(into)] pending: Signal<bool>,
+) -> impl IntoView {
+    if roster.is_empty() {
+        return view! { <div>"There aren't any students to show."</div> }.into_any();
+    }
+
+    view! {
+        <div>{format!("{} students", roster.len())}</div>
+        <table class="table-auto text-left">
+            <thead>
+                <tr class="border-b border-gray-300">
+                    <th class="p-2">"Student"</th>
+                    <th class="p-2">"Email"</th>
+                    <th class="p-2">"Progress"</th>
+                    <th class="p-2">"Forms Left"</th>
+                    <th class="p-2">"Extension"</th>
+                </tr>
+            </thead>
+            <tbody>
+                {roster
+                    .into_iter()
+                    .map(|student| {
+                        let name = if student.last_name.is_empty() && student.first_name.is_empty() {
+                            "No name entered".to_string()
+                        } else {
+                            format!("{}, {}", student.last_name, student.first_name)
+                        };
+                        let percent = student.completion.percent();
+                        let forms_left = student
+                            .completion
+                            .incomplete()
+                            .map(|form| form.title.clone())
+                            .collect::<Vec<String>>()
+                            .join(", ");
+
+                        view! {
+                            <tr class="border-b border-gray-200 align-top">
+                                <td class="p-2">{name}</td>
+                                <td class="p-2">
+                                    <a
+                                        class="text-blue-500 underline"
+                                        href=format!("mailto:{}", student.email)
+                                    >
+                                        {student.email.clone()}
+                                    </a>
+                                </td>
+                                <td class="p-2">
+                                    <div class="flex flex-row items-center gap-2">
+                                        <div class="w-24 h-2 rounded-full bg-gray-200">
+                                            <div
+                                                class="h-2 rounded-full bg-red-900"
+                                                style:width=format!("{percent}%")
+                                            />
+                                        </div>
+                                        {format!("{percent}%")}
+                                    </div>
+                                </td>
+                                <td class="p-2">{forms_left}</td>
+                                <td class="p-2">
+                                    <ExtensionCell
+                                        until=extensions.get(&student.student_id).copied()
+                                        student_id=student.student_id
+                                        on_set_extension=on_set_extension
+                                        pending=pending
+                                    />
+                                </td>
+                            </tr>
+                        }
+                    })
+                    .collect_view()}
+            </tbody>
+        </table>
+    }
+        .into_any()
+}
+
+/// The extension controls of a single student. Times are entered in the admin's local time.
+#[component]
+fn ExtensionCell(
+    student_id: String,
+    until: Option<DateTime<FixedOffset>>,
+    on_set_extension: Callback<(String, Option<DateTime<FixedOffset>>)>,
+    pending: Signal<bool>,
+) -> impl IntoView {
+    let input = RwSignal::new(until.as_ref().map(to_local_input).unwrap_or_default());
+    let student_id = StoredValue::new(student_id);
+
+    let on_save = move |_| {
+        let input = input.get_untracked();
+        let Some(until) = browser_offset(&input).and_then(|offset| parse_local_input(&input, offset))
+        else {
+            return;
+        };
+        on_set_extension.run((student_id.get_value(), Some(until)));
+    };
+    let on_remove = move |_| on_set_extension.run((student_id.get_value(), None));
+
+    view! {
+        <div class="flex flex-row flex-wrap items-center">
+            <input
+                class="p-2 rounded-lg border-1 border-gray-300"
+                type="datetime-local"
+                bind:value=input
+            />
+            <ActionButton on:click=on_save disabled=pending>
+                "Save"
+            </ActionButton>
+            <Show when=move || until.is_some()>
+                <ActionButton on:click=on_remove disabled=pending>
+                    "Remove"
+                </ActionButton>
+            </Show>
+        </div>
+    }
+}
@@ -24,8 +24,11 @@ pub fn AdminShell() -> impl IntoView {
                             TabInfo::new("Home", "home", None),
                             TabInfo::new("Providers", "providers", None),
                             TabInfo::new("Scholarships", "scholarships", None),
+                            TabInfo::new("Forms", "forms", None),
+                            TabInfo::new("Important Dates", "dates", None),
                             TabInfo::new("Utilities", "utilities", None),
                             TabInfo::new("Scholarship Applicants", "applicants", None),
+                            TabInfo::new("Application Progress", "progress", None),
                         ]
                     />
                 </Authenticated>
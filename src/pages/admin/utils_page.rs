@@ -1,78 +1,97 @@
-﻿use crate::common::{DateInfo, DateRange};
-use crate::components::ActionButton;
+﻿use crate::common::{Rule, ValueType};
+use crate::components::{ActionButton, ConfirmDialog, OutlinedTextField, RadioList, Row, ValidatedForm};
 use crate::pages::api::exports::get_scholarship_csv;
-use crate::pages::api::{CreateDates, CreateTestComparisons, exports::GetScholarshipCsv};
-use chrono::DateTime;
+use crate::pages::api::{
+    CreateTestComparisons, DeleteComparison, GetIntegrityReport, IntegrityIssue,
+    ImportComparisonRule, RebuildEligibilityIndex, RepairIntegrityIssue,
+    exports::GetScholarshipCsv,
+};
 use leptos::prelude::*;
+use leptos_oidc::AuthSignal;
+use std::collections::HashMap;
 use leptos::task::spawn_local;
 use leptos::wasm_bindgen::JsCast;
 use leptos::web_sys::{Blob, HtmlAnchorElement, Url, js_sys};
-use crate::pages::api::students::{get_student_info_json, get_student_pdf};
-
-fn get_important_dates() -> Vec<DateInfo> {
-    vec![
-        DateInfo {
-            id: uuid::Uuid::new_v4().to_string(),
-            title: "Provider Forms Open".to_string(),
-            date: DateRange::Range(
-                DateTime::parse_from_rfc3339("2026-01-20T07:15:00-05:00").unwrap(),
-                DateTime::parse_from_rfc3339("2026-03-03T23:59:00-05:00").unwrap(),
-            ),
-            description: "".to_string(),
-        },
-        DateInfo {
-            id: uuid::Uuid::new_v4().to_string(),
-            title: "Student Forms Open".to_string(),
-            date: DateRange::Range(
-                DateTime::parse_from_rfc3339("2026-03-03T07:15:00-05:00").unwrap(),
-                DateTime::parse_from_rfc3339("2026-03-27T14:05:00-05:00").unwrap(),
-            ),
-            description: "".to_string(),
-        },
-        DateInfo {
-            id: uuid::Uuid::new_v4().to_string(),
-            title: "Scholarship Decision Window".to_string(),
-            date: DateRange::Range(
-                DateTime::parse_from_rfc3339("2026-04-10T07:15:00-05:00").unwrap(),
-                DateTime::parse_from_rfc3339("2026-05-01T23:59:00-05:00").unwrap(),
-            ),
-            description: "Start date may change if available sooner.".to_string(),
-        },
-        DateInfo {
-            id: uuid::Uuid::new_v4().to_string(),
-            title: "PHS Scholarship Committee".to_string(),
-            date: DateRange::Single(
-                DateTime::parse_from_rfc3339("2026-05-18T00:00:00-05:00").unwrap(),
-            ),
-            description: "Time TBD".to_string(),
-        },
-        DateInfo {
-            id: uuid::Uuid::new_v4().to_string(),
-            title: "Scholarship and Awards Night".to_string(),
-            date: DateRange::Single(
-                DateTime::parse_from_rfc3339("2026-06-09T00:00:00-05:00").unwrap(),
-            ),
-            description: "Time TBD".to_string(),
-        },
-    ]
-}
+use crate::pages::api::students::{
+    AdminClaimStudentIds, AdminMigrateExtracurriculars, get_student_info_json, get_student_pdf,
+};
 
 #[component]
 pub fn AdminUtilsPage() -> impl IntoView {
     let create_comparisons = ServerAction::<CreateTestComparisons>::new();
-    let create_dates = ServerAction::<CreateDates>::new();
     let create_export = ServerAction::<GetScholarshipCsv>::new();
+    let rebuild_index = ServerAction::<RebuildEligibilityIndex>::new();
+    let migrate_extracurriculars = ServerAction::<AdminMigrateExtracurriculars>::new();
+    let claim_student_ids = ServerAction::<AdminClaimStudentIds>::new();
+
+    let auth = expect_context::<AuthSignal>();
+    let access_token =
+        Memo::new(move |_| auth.with(|auth| auth.authenticated().map(|a| a.access_token())));
 
     let on_click_comparisons = move |_| {
         create_comparisons.dispatch(CreateTestComparisons {});
     };
 
-    let on_click_dates = move |_| {
-        create_dates.dispatch(CreateDates {
-            dates: get_important_dates(),
+    let on_click_rebuild = move |_| {
+        rebuild_index.dispatch(RebuildEligibilityIndex {
+            access_token: access_token.get_untracked().unwrap_or_default(),
+        });
+    };
+
+    let rebuild_status = move || {
+        if rebuild_index.pending().get() {
+            return "Rebuilding eligibility index...".to_string();
+        }
+
+        match rebuild_index.value().get() {
+            Some(Ok(count)) => format!("Eligibility index rebuilt with {count} entries."),
+            Some(Err(err)) => format!("Couldn't rebuild eligibility index: {err}"),
+            None => String::new(),
+        }
+    };
+
+    let on_click_migrate = move |_| {
+        migrate_extracurriculars.dispatch(AdminMigrateExtracurriculars {
+            access_token: access_token.get_untracked().unwrap_or_default(),
+        });
+    };
+
+    let migrate_status = move || {
+        if migrate_extracurriculars.pending().get() {
+            return "Moving extracurriculars...".to_string();
+        }
+
+        match migrate_extracurriculars.value().get() {
+            Some(Ok(count)) => format!("Copied {count} extracurricular answers out of demographics."),
+            Some(Err(err)) => format!("Couldn't move extracurriculars: {err}"),
+            None => String::new(),
+        }
+    };
+
+    let on_click_claim = move |_| {
+        claim_student_ids.dispatch(AdminClaimStudentIds {
+            access_token: access_token.get_untracked().unwrap_or_default(),
         });
     };
 
+    let claim_status = move || {
+        if claim_student_ids.pending().get() {
+            return "Claiming student ID numbers...".to_string();
+        }
+
+        match claim_student_ids.value().get() {
+            Some(Ok(duplicates)) if duplicates.is_empty() => {
+                "Every student ID number is claimed.".to_string()
+            }
+            Some(Ok(duplicates)) => format!(
+                "These student ID numbers are used by more than one student: {}",
+                duplicates.join(", ")
+            ),
+            Some(Err(err)) => format!("Couldn't claim student ID numbers: {err}"),
+            None => String::new(),
+        }
+    };
+
     let on_click_export = move |_| {
         spawn_local(async move {
             let file_bytes = get_scholarship_csv().await.unwrap_or_default();
@@ -138,10 +157,321 @@ pub fn AdminUtilsPage() -> impl IntoView {
                     "This utility page provides buttons to initialize the corresponding lists of information."
                 </div>
                 <ActionButton on:click=on_click_comparisons>"Create Comparisons"</ActionButton>
-                <ActionButton on:click=on_click_dates>"Create Dates"</ActionButton>
                 <ActionButton on:click=on_click_export>"Scholarship Export"</ActionButton>
                 <ActionButton on:click=on_click_json>"Get student JSON"</ActionButton>
+                <ActionButton on:click=on_click_rebuild disabled=rebuild_index.pending()>
+                    "Rebuild Eligibility Index"
+                </ActionButton>
+                <div>{rebuild_status}</div>
+                <ActionButton on:click=on_click_migrate disabled=migrate_extracurriculars.pending()>
+                    "Move Extracurriculars Out of Demographics"
+                </ActionButton>
+                <div>{migrate_status}</div>
+                <ActionButton on:click=on_click_claim disabled=claim_student_ids.pending()>
+                    "Claim Student ID Numbers"
+                </ActionButton>
+                <div>{claim_status}</div>
+                <RuleImportForm />
+                <DeleteComparisonForm />
+                <IntegrityReport />
             </div>
         </div>
     }.into_any()
 }
+
+/// # Rule Import Form
+///
+/// Allows an administrator to create comparisons by writing a requirement rule, such as
+/// `gpa >= 3.0 and town in ["Middlebury", "Southbury"]`. Each condition in the rule is stored as
+/// its own comparison under the given category. Errors are displayed underneath the rule.
+#[component]
+fn RuleImportForm() -> impl IntoView {
+    let auth = expect_context::<AuthSignal>();
+    let access_token =
+        Memo::new(move |_| auth.with(|auth| auth.authenticated().map(|a| a.access_token())));
+
+    let form_data = RwSignal::new(HashMap::<String, ValueType>::new());
+    let import_action = ServerAction::<ImportComparisonRule>::new();
+
+    let get_text = move |key: &str| {
+        form_data.with(|data| {
+            data.get(key)
+                .and_then(|v| v.as_string().ok().flatten())
+                .unwrap_or_default()
+        })
+    };
+
+    let on_submit = move |_| {
+        import_action.dispatch(ImportComparisonRule {
+            access_token: access_token.get_untracked().unwrap_or_default(),
+            rule: get_text("rule"),
+            category: get_text("category"),
+        });
+    };
+
+    // Parse the rule as it's typed, so mistakes can be seen before submitting.
+    let preview_view = move || {
+        let rule = get_text("rule");
+        if rule.trim().is_empty() {
+            return None;
+        }
+
+        Some(match Rule::parse(&rule) {
+            Ok(parsed) => view! { <span>{format!("Students qualify when {}.", parsed.describe())}</span> }
+                .into_any(),
+            Err(err) => view! {
+                <pre class="font-mono text-red-700 whitespace-pre-wrap">{err.render(&rule)}</pre>
+            }
+            .into_any(),
+        })
+    };
+
+    let result_view = move || {
+        import_action.value().get().map(|result| match result {
+            Ok(comparisons) => view! {
+                <div class="flex flex-col gap-1">
+                    <span>{format!("Created {} comparisons:", comparisons.len())}</span>
+                    <ul class="list-disc pl-6">
+                        {comparisons
+                            .into_iter()
+                            .map(|comp| view! { <li>{format!("{}: {}", comp.id, comp.to_rule_text())}</li> })
+                            .collect_view()}
+                    </ul>
+                </div>
+            }.into_any(),
+            Err(err) => view! {
+                <pre class="font-mono text-red-700 whitespace-pre-wrap">
+                    {err.to_string().trim_start_matches("error running server function: ").to_string()}
+                </pre>
+            }.into_any(),
+        })
+    };
+
+    view! {
+        <ValidatedForm
+            on_submit=Callback::new(on_submit)
+            title="Import Comparison Rule"
+            description="Write a requirement such as gpa >= 3.0 and town in [\"Middlebury\", \"Southbury\"]. Each condition becomes a comparison."
+            disabled=import_action.pending()
+        >
+            <Row>
+                <OutlinedTextField
+                    label="Rule"
+                    placeholder="gpa >= 3.0"
+                    data_member="rule"
+                    data_map=form_data
+                    required=true
+                />
+            </Row>
+            <Row>
+                <OutlinedTextField
+                    label="Category"
+                    placeholder="GPA Limits"
+                    data_member="category"
+                    data_map=form_data
+                    required=true
+                />
+            </Row>
+        </ValidatedForm>
+        {preview_view}
+        {result_view}
+    }
+}
+
+// The deletion modes, as displayed in the DeleteComparisonForm component.
+static DELETE_BLOCK: &str = "Only delete if no scholarships require it";
+static DELETE_CASCADE: &str = "Also remove it from scholarships";
+
+/// # Delete Comparison Form
+///
+/// Allows an administrator to delete a comparison by ID. By default, comparisons that are still
+/// required by a scholarship can't be deleted, and the scholarships are listed instead. The
+/// comparison can also be removed from those scholarships before it's deleted.
+#[component]
+fn DeleteComparisonForm() -> impl IntoView {
+    let auth = expect_context::<AuthSignal>();
+    let access_token =
+        Memo::new(move |_| auth.with(|auth| auth.authenticated().map(|a| a.access_token())));
+
+    let form_data = RwSignal::new(HashMap::from([(
+        "mode".to_string(),
+        ValueType::String(Some(DELETE_BLOCK.to_string())),
+    )]));
+    let delete_action = ServerAction::<DeleteComparison>::new();
+
+    let get_text = move |key: &str| {
+        form_data.with(|data| {
+            data.get(key)
+                .and_then(|v| v.as_string().ok().flatten())
+                .unwrap_or_default()
+        })
+    };
+
+    let on_submit = move |_| {
+        delete_action.dispatch(DeleteComparison {
+            access_token: access_token.get_untracked().unwrap_or_default(),
+            comparison_id: get_text("comparison_id"),
+            cascade: get_text("mode") == DELETE_CASCADE,
+        });
+    };
+
+    let result_view = move || {
+        delete_action.value().get().map(|result| match result {
+            Ok(scholarships) if scholarships.is_empty() => view! {
+                <span>"Deleted the comparison."</span>
+            }.into_any(),
+            Ok(scholarships) => view! {
+                <span>
+                    {format!("Deleted the comparison and removed it from: {}", scholarships.join(", "))}
+                </span>
+            }.into_any(),
+            Err(err) => view! {
+                <pre class="font-mono text-red-700 whitespace-pre-wrap">
+                    {err.to_string().trim_start_matches("error running server function: ").to_string()}
+                </pre>
+            }.into_any(),
+        })
+    };
+
+    view! {
+        <ValidatedForm
+            on_submit=Callback::new(on_submit)
+            title="Delete Comparison"
+            description="Scholarships that require a deleted comparison would silently ignore it, so deletion is blocked unless it's also removed from them."
+            disabled=delete_action.pending()
+        >
+            <Row>
+                <OutlinedTextField
+                    label="Comparison ID"
+                    placeholder="gpa_at_least_3"
+                    data_member="comparison_id"
+                    data_map=form_data
+                    required=true
+                />
+            </Row>
+            <Row>
+                <RadioList
+                    label="If scholarships require this comparison:"
+                    data_member="mode"
+                    data_map=form_data
+                    items=vec![DELETE_BLOCK.to_string(), DELETE_CASCADE.to_string()]
+                />
+            </Row>
+        </ValidatedForm>
+        {result_view}
+    }
+}
+
+/// # Integrity Report
+///
+/// Lists references between tables that no longer resolve, such as scholarship requirements for
+/// deleted comparisons. Each issue has a button that repairs it, after which the report is run
+/// again.
+#[component]
+fn IntegrityReport() -> impl IntoView {
+    let auth = expect_context::<AuthSignal>();
+    let access_token =
+        Memo::new(move |_| auth.with(|auth| auth.authenticated().map(|a| a.access_token())));
+
+    let report_action = ServerAction::<GetIntegrityReport>::new();
+    let repair_action = ServerAction::<RepairIntegrityIssue>::new();
+    // Repairs that delete something are held here until they're confirmed.
+    let pending_repair = RwSignal::new(None::<IntegrityIssue>);
+
+    let repair = move |issue: IntegrityIssue| {
+        repair_action.dispatch(RepairIntegrityIssue {
+            access_token: access_token.get_untracked().unwrap_or_default(),
+            issue,
+        });
+    };
+    let on_confirm_repair = Callback::new(move |_| {
+        if let Some(issue) = pending_repair.get_untracked() {
+            repair(issue);
+        }
+        pending_repair.set(None);
+    });
+
+    let run_report = move || {
+        report_action.dispatch(GetIntegrityReport {
+            access_token: access_token.get_untracked().unwrap_or_default(),
+        });
+    };
+
+    Effect::watch(
+        move || repair_action.value().get(),
+        move |value, _, _| {
+            if let Some(Ok(())) = value {
+                run_report();
+            }
+        },
+        false,
+    );
+
+    let repair_error = move || {
+        repair_action.value().get().and_then(|result| result.err()).map(|err| view! {
+            <pre class="font-mono text-red-700 whitespace-pre-wrap">
+                {err.to_string().trim_start_matches("error running server function: ").to_string()}
+            </pre>
+        })
+    };
+
+    let report_view = move || {
+        if report_action.pending().get() {
+            return view! { <span>"Checking data integrity..."</span> }.into_any();
+        }
+
+        match report_action.value().get() {
+            Some(Ok(issues)) if issues.is_empty() => view! {
+                <span>"No integrity issues were found."</span>
+            }.into_any(),
+            Some(Ok(issues)) => view! {
+                <div class="flex flex-col gap-1">
+                    <span>{format!("Found {} integrity issues:", issues.len())}</span>
+                    {issues
+                        .into_iter()
+                        .map(|issue| {
+                            let description = issue.describe();
+                            let label = issue.repair_label();
+                            let on_click = move |_| {
+                                if issue.confirmation().is_some() {
+                                    pending_repair.set(Some(issue.clone()));
+                                } else {
+                                    repair(issue.clone());
+                                }
+                            };
+
+                            view! {
+                                <div class="flex flex-row items-center border-b-1 border-gray-200">
+                                    <span class="flex-3">{description}</span>
+                                    <ActionButton on:click=on_click disabled=repair_action.pending()>
+                                        {label}
+                                    </ActionButton>
+                                </div>
+                            }
+                        })
+                        .collect_view()}
+                </div>
+            }.into_any(),
+            Some(Err(err)) => view! {
+                <span>{format!("Couldn't check data integrity: {err}")}</span>
+            }.into_any(),
+            None => ().into_any(),
+        }
+    };
+
+    view! {
+        <ConfirmDialog
+            message=Signal::derive(move || {
+                pending_repair.with(|issue| issue.as_ref().and_then(IntegrityIssue::confirmation))
+            })
+            on_confirm=on_confirm_repair
+            on_cancel=Callback::new(move |_| pending_repair.set(None))
+            disabled=repair_action.pending()
+        />
+        <ActionButton on:click=move |_| run_report() disabled=report_action.pending()>
+            "Check Data Integrity"
+        </ActionButton>
+        {repair_error}
+        {report_view}
+    }
+}
@@ -425,6 +425,10 @@ pub async fn create_test_comparisons() -> Result<(), ServerFnError> {
     log!("Creating test comparisons");
 
     let comp_list = make_comp_list();
+    let comparison_ids = comp_list
+        .iter()
+        .map(|comparison| comparison.id.clone())
+        .collect::<Vec<String>>();
 
     for comparison in comp_list {
         if let Err(err) = client
@@ -440,6 +444,11 @@ pub async fn create_test_comparisons() -> Result<(), ServerFnError> {
         }
     }
 
+    // The comparisons may have replaced ones that scholarships already require.
+    if let Err(err) = super::reindex_comparisons(&comparison_ids).await {
+        leptos::logging::error!("Couldn't update eligibility index for comparisons: {}", err);
+    }
+
     Ok(())
 }
 
@@ -506,3 +515,150 @@ pub async fn get_comparisons_categorized()
         }
     }
 }
+
+/// Parses a requirement rule, such as `gpa >= 3.0 and town in ["Middlebury", "Southbury"]`, and
+/// stores each of its conditions as a comparison under the given category. Rules with `and` are
+/// split into one category per group, as described in
+/// [`Rule::into_comparisons`](crate::common::Rule::into_comparisons). Returns the created
+/// comparisons. Refer to [`Rule`](crate::common::Rule) for the rule syntax.
+///
+/// Parse errors are returned with the rule text and a marker underneath the problem. Existing
+/// comparisons are never overwritten, since scholarships may already require them, so the import
+/// fails without creating anything if any of the comparisons already exist.
+#[server]
+pub async fn import_comparison_rule(
+    access_token: String,
+    rule: String,
+    category: String,
+) -> Result<Vec<ComparisonData>, ServerFnError> {
+    use crate::common::Rule;
+    use crate::pages::api::tokens::validate_and_get_token_info;
+    use aws_sdk_dynamodb::types::AttributeValue;
+
+    let _ = validate_and_get_token_info(access_token, "us-east-1_rvCU4Xy4j", "us-east-1").await?;
+
+    let comparisons = match Rule::parse(&rule) {
+        Ok(parsed) => parsed.into_comparisons(category),
+        Err(err) => {
+            let msg = err.render(&rule);
+            leptos::logging::error!("{}", msg);
+            return Err(ServerFnError::new(msg));
+        }
+    };
+
+    let client = create_dynamo_client().await;
+
+    let mut conflicts = Vec::new();
+    for comparison in &comparisons {
+        match client
+            .get_item()
+            .table_name(COMPARISONS_TABLE)
+            .key("id", AttributeValue::S(comparison.id.clone()))
+            .send()
+            .await
+        {
+            Ok(output) if output.item.is_some() => conflicts.push(comparison.id.clone()),
+            Ok(_) => {}
+            Err(err) => {
+                let msg = err.message().unwrap_or("An unknown error occurred");
+                leptos::logging::error!("{}", msg);
+                return Err(ServerFnError::new(msg));
+            }
+        }
+    }
+    if !conflicts.is_empty() {
+        let msg = format!(
+            "These comparisons already exist, so nothing was imported: {}",
+            conflicts.join(", ")
+        );
+        leptos::logging::error!("{}", msg);
+        return Err(ServerFnError::new(msg));
+    }
+
+    for comparison in &comparisons {
+        if let Err(err) = client
+            .put_item()
+            .table_name(COMPARISONS_TABLE)
+            .set_item(Some(serde_dynamo::to_item(comparison)?))
+            .condition_expression("attribute_not_exists(id)")
+            .send()
+            .await
+        {
+            // Another import may have created the comparison since it was checked.
+            let msg = if err.code() == Some("ConditionalCheckFailedException") {
+                format!("Comparison {:?} already exists, so it wasn't imported.", comparison.id)
+            } else {
+                err.message().unwrap_or("An unknown error occurred").to_string()
+            };
+            leptos::logging::error!("{}", msg);
+            return Err(ServerFnError::new(msg));
+        }
+    }
+
+    log!("Imported {} comparisons from rule {:?}", comparisons.len(), rule);
+
+    Ok(comparisons)
+}
+
+/// Deletes a comparison. If any scholarships still require the comparison, the deletion is
+/// blocked unless `cascade` is set, in which case the comparison is first removed from those
+/// scholarships. Returns the IDs of the scholarships that require, or required, the comparison.
+/// Only available to administrators.
+#[server]
+pub async fn delete_comparison(
+    access_token: String,
+    comparison_id: String,
+    cascade: bool,
+) -> Result<Vec<String>, ServerFnError> {
+    use super::{create_scholarship_info, get_all_scholarship_info};
+    use crate::common::remove_requirement;
+    use crate::pages::api::tokens::validate_and_get_token_info;
+    use aws_sdk_dynamodb::types::AttributeValue;
+
+    let _ = validate_and_get_token_info(access_token, "us-east-1_rvCU4Xy4j", "us-east-1").await?;
+
+    let mut dependents = get_all_scholarship_info()
+        .await?
+        .into_iter()
+        .filter_map(|mut scholarship| {
+            remove_requirement(&mut scholarship, &comparison_id).then_some(scholarship)
+        })
+        .collect::<Vec<_>>();
+    dependents.sort_by(|a, b| a.subject.cmp(&b.subject));
+    let dependent_ids = dependents
+        .iter()
+        .map(|scholarship| scholarship.subject.clone())
+        .collect::<Vec<String>>();
+
+    if !dependents.is_empty() && !cascade {
+        let msg = format!(
+            "Comparison {comparison_id:?} is required by {} scholarships: {}",
+            dependent_ids.len(),
+            dependent_ids.join(", ")
+        );
+        leptos::logging::error!("{}", msg);
+        return Err(ServerFnError::new(msg));
+    }
+
+    for scholarship in dependents {
+        create_scholarship_info(scholarship).await?;
+    }
+
+    let client = create_dynamo_client().await;
+
+    if let Err(err) = client
+        .delete_item()
+        .table_name(COMPARISONS_TABLE)
+        .key("id", AttributeValue::S(comparison_id.clone()))
+        .send()
+        .await
+    {
+        let msg = err.message().unwrap_or("An unknown error occurred");
+        leptos::logging::error!("{}", msg);
+        return Err(ServerFnError::new(msg));
+    }
+
+    log!("Deleted comparison {:?} from {} scholarships", comparison_id, dependent_ids.len());
+
+    Ok(dependent_ids)
+}
@@ -0,0 +1,185 @@
+use crate::common::ApplicationCompletion;
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "ssr")]
+mod imports {
+    pub use super::super::MAIN_TABLE_NAME;
+    pub use super::super::load_form_definitions;
+    pub use super::super::students::StudentDemographics;
+    pub use crate::common::{FormDefinition, ValueMap, ValueType};
+    pub use crate::pages::api::tokens::validate_and_get_token_info;
+    pub use crate::utils::server::*;
+    pub use aws_sdk_dynamodb::error::ProvideErrorMetadata;
+    pub use aws_sdk_dynamodb::types::AttributeValue;
+    pub use leptos::logging::error;
+    pub use std::collections::HashMap;
+}
+
+#[cfg(feature = "ssr")]
+use imports::*;
+
+/// # Student Completion
+///
+/// A student's progress through the application, as shown on the admin roster. Students that
+/// haven't entered their name yet are still listed, since they're the furthest behind.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct StudentCompletion {
+    pub student_id: String,
+    pub first_name: String,
+    pub last_name: String,
+    pub email: String,
+    pub completion: ApplicationCompletion,
+}
+
+/// The items of a single student, with form data keyed by sort key and the sort keys of their
+/// file entries kept separately.
+#[cfg(feature = "ssr")]
+#[derive(Default)]
+struct StudentItems {
+    forms: HashMap<String, HashMap<String, ValueType>>,
+    files: Vec<String>,
+}
+
+#[cfg(feature = "ssr")]
+impl StudentItems {
+    fn add_item(&mut self, item: HashMap<String, AttributeValue>) {
+        let sort_key = item
+            .get("SK")
+            .and_then(|v| v.as_s().ok())
+            .cloned()
+            .unwrap_or_default();
+        if sort_key.starts_with("FILE#") {
+            self.files.push(sort_key);
+            return;
+        }
+
+        let data = item
+            .into_iter()
+            .filter(|(key, _)| key != "HK" && key != "SK")
+            .map(|(key, value)| (key, ValueType::from(&value)))
+            .collect();
+        self.forms.insert(sort_key, data);
+    }
+
+    fn completion(&self, definitions: &[FormDefinition]) -> ApplicationCompletion {
+        ApplicationCompletion::new(definitions, &self.forms, &self.files)
+    }
+}
+
+#[cfg(feature = "ssr")]
+fn dynamo_error(err: impl ProvideErrorMetadata) -> ServerFnError {
+    let msg = format!(
+        "Couldn't get student data from Dynamo: {}",
+        err.message().unwrap_or("Unknown error occurred")
+    );
+    error!("{}", msg);
+    ServerFnError::new(msg)
+}
+
+/// Gets how much of the application a student has finished, from the required fields of each
+/// form definition and the student's uploaded files. The student is taken from their access
+/// token.
+#[server]
+pub async fn get_application_completion(
+    access_token: String,
+) -> Result<ApplicationCompletion, ServerFnError> {
+    let subject = validate_and_get_token_info(access_token, "us-east-1_Lfjuy5zaM", "us-east-1")
+        .await?
+        .subject;
+
+    let client = create_dynamo_client().await;
+
+    let (definitions, items) = tokio::join!(
+        load_form_definitions(),
+        client
+            .query()
+            .table_name(MAIN_TABLE_NAME)
+            .key_condition_expression("HK = :hk")
+            .expression_attribute_values(":hk", AttributeValue::S(format!("STUDENT#{}", subject)))
+            .into_paginator()
+            .items()
+            .send()
+            .collect::<Result<Vec<_>, _>>()
+    );
+    let definitions = definitions?;
+
+    let mut student = StudentItems::default();
+    for item in items.map_err(dynamo_error)? {
+        student.add_item(item);
+    }
+
+    Ok(student.completion(&definitions))
+}
+
+/// Gets the application progress of every student, least complete first, so that students who
+/// are behind can be contacted before the deadline. Only available to administrators.
+#[server]
+pub async fn admin_get_completion_roster(
+    access_token: String,
+) -> Result<Vec<StudentCompletion>, ServerFnError> {
+    let _ = validate_and_get_token_info(access_token, "us-east-1_rvCU4Xy4j", "us-east-1").await?;
+
+    let client = create_dynamo_client().await;
+
+    let (definitions, items) = tokio::join!(
+        load_form_definitions(),
+        client
+            .scan()
+            .table_name(MAIN_TABLE_NAME)
+            .filter_expression("begins_with(HK, :prefix)")
+            .expression_attribute_values(":prefix", AttributeValue::S("STUDENT#".to_string()))
+            .into_paginator()
+            .items()
+            .send()
+            .collect::<Result<Vec<_>, _>>()
+    );
+    let definitions = definitions?;
+
+    let mut students = HashMap::<String, StudentItems>::new();
+    for item in items.map_err(dynamo_error)? {
+        let Some(student_id) = item
+            .get("HK")
+            .and_then(|v| v.as_s().ok())
+            .and_then(|hk| hk.strip_prefix("STUDENT#"))
+            .map(str::to_string)
+        else {
+            continue;
+        };
+
+        students.entry(student_id).or_default().add_item(item);
+    }
+
+    let mut roster = students
+        .into_iter()
+        .map(|(student_id, student)| {
+            let demographics = student
+                .forms
+                .get("DEMOGRAPHICS")
+                .and_then(|data| StudentDemographics::from_map(data).ok());
+
+            StudentCompletion {
+                student_id,
+                first_name: demographics
+                    .as_ref()
+                    .map(|info| info.first_name.clone())
+                    .unwrap_or_default(),
+                last_name: demographics
+                    .as_ref()
+                    .map(|info| info.last_name.clone())
+                    .unwrap_or_default(),
+                email: demographics.and_then(|info| info.email).unwrap_or_default(),
+                completion: student.completion(&definitions),
+            }
+        })
+        .collect::<Vec<StudentCompletion>>();
+    roster.sort_by(|a, b| {
+        a.completion
+            .percent()
+            .cmp(&b.completion.percent())
+            .then_with(|| a.last_name.cmp(&b.last_name))
+            .then_with(|| a.first_name.cmp(&b.first_name))
+    });
+
+    Ok(roster)
+}
@@ -1,13 +1,18 @@
-use crate::common::DateInfo;
+use crate::common::{DateInfo, DatePurpose, WindowState};
+use chrono::{DateTime, FixedOffset};
 use leptos::logging::{error, log};
 use leptos::prelude::*;
 use std::collections::HashMap;
 
 #[cfg(feature = "ssr")]
-use super::DATES_TABLE;
+use super::{DATES_TABLE, MAIN_TABLE_NAME};
+#[cfg(feature = "ssr")]
+use crate::pages::api::tokens::validate_and_get_token_info;
+#[cfg(feature = "ssr")]
+use aws_sdk_dynamodb::types::AttributeValue;
 
 #[cfg(feature = "ssr")]
-use crate::utils::server::create_dynamo_client;
+use crate::utils::server::{batch_write_requests, create_dynamo_client};
 #[cfg(feature = "ssr")]
 use aws_sdk_dynamodb::{
     Client as DynamoClient,
@@ -15,40 +20,95 @@ use aws_sdk_dynamodb::{
     types::{PutRequest, WriteRequest},
 };
 
+/// Creates important dates, which should each have a new ID. Dates are written in batches, so
+/// any number of them can be created at once. Only available to administrators.
 #[server]
-pub async fn create_dates(dates: Vec<DateInfo>) -> Result<(), ServerFnError> {
-    // Serialize and put dates into the database.
-    let client = create_dynamo_client().await;
+pub async fn create_dates(access_token: String, dates: Vec<DateInfo>) -> Result<(), ServerFnError> {
+    let _ = validate_and_get_token_info(access_token, "us-east-1_rvCU4Xy4j", "us-east-1").await?;
 
-    log!("Creating dates: {:?}", dates);
+    log!("Creating {} dates", dates.len());
 
     let requests = dates
-        .into_iter()
-        .filter_map(|date| {
-            let item = serde_dynamo::to_item(&date).unwrap_or_default();
+        .iter()
+        .map(|date| {
+            date.validate().map_err(ServerFnError::new)?;
+            let item = serde_dynamo::to_item(date).map_err(|err| {
+                let msg = format!("Couldn't serialize date {:?}: {}", date.title, err);
+                error!("{}", msg);
+                ServerFnError::new(msg)
+            })?;
+            let put_request = PutRequest::builder()
+                .set_item(Some(item))
+                .build()
+                .map_err(|err| ServerFnError::new(err.to_string()))?;
+
+            Ok(WriteRequest::builder().put_request(put_request).build())
+        })
+        .collect::<Result<Vec<WriteRequest>, ServerFnError>>()?;
+
+    let client = create_dynamo_client().await;
+    batch_write_requests(&client, DATES_TABLE, requests).await
+}
+
+/// Replaces an existing important date, keeping its ID. Only available to administrators.
+#[server]
+pub async fn update_date(access_token: String, date: DateInfo) -> Result<(), ServerFnError> {
+    let _ = validate_and_get_token_info(access_token, "us-east-1_rvCU4Xy4j", "us-east-1").await?;
 
-            let put_request = PutRequest::builder().set_item(Some(item)).build().ok()?;
+    date.validate().map_err(ServerFnError::new)?;
+    let item = serde_dynamo::to_item(&date).map_err(|err| {
+        let msg = format!("Couldn't serialize date {:?}: {}", date.title, err);
+        error!("{}", msg);
+        ServerFnError::new(msg)
+    })?;
 
-            Some(WriteRequest::builder().put_request(put_request).build())
+    let client = create_dynamo_client().await;
+    client
+        .put_item()
+        .table_name(DATES_TABLE)
+        .set_item(Some(item))
+        .condition_expression("attribute_exists(id)")
+        .send()
+        .await
+        .map(|_| ())
+        .map_err(|err| {
+            let msg = if err.code() == Some("ConditionalCheckFailedException") {
+                format!("The date {:?} no longer exists.", date.title)
+            } else {
+                format!(
+                    "Couldn't update date in Dynamo: {}",
+                    err.message().unwrap_or("Unknown error occurred")
+                )
+            };
+            error!("{}", msg);
+            ServerFnError::new(msg)
         })
-        .collect::<Vec<WriteRequest>>();
+}
 
-    let mut request_map = HashMap::new();
-    request_map.insert(DATES_TABLE.to_string(), requests);
+/// Deletes an important date. Only available to administrators.
+#[server]
+pub async fn delete_date(access_token: String, id: String) -> Result<(), ServerFnError> {
+    let _ = validate_and_get_token_info(access_token, "us-east-1_rvCU4Xy4j", "us-east-1").await?;
 
+    let client = create_dynamo_client().await;
     client
-        .batch_write_item()
-        .set_request_items(Some(request_map))
+        .delete_item()
+        .table_name(DATES_TABLE)
+        .key("id", AttributeValue::S(id))
         .send()
         .await
         .map(|_| ())
         .map_err(|err| {
-            let msg = err.message().unwrap_or("Unknown error occurred");
+            let msg = format!(
+                "Couldn't delete date from Dynamo: {}",
+                err.message().unwrap_or("Unknown error occurred")
+            );
             error!("{}", msg);
             ServerFnError::new(msg)
         })
 }
 
+/// Gets every important date, in no particular order.
 #[server]
 pub async fn get_important_dates() -> Result<Vec<DateInfo>, ServerFnError> {
     log!("Getting important dates list");
@@ -58,17 +118,16 @@ pub async fn get_important_dates() -> Result<Vec<DateInfo>, ServerFnError> {
     client
         .scan()
         .table_name(DATES_TABLE)
+        .into_paginator()
+        .items()
         .send()
+        .collect::<Result<Vec<_>, _>>()
         .await
-        .map(|output| {
-            if let Some(items) = output.items {
-                items
-                    .into_iter()
-                    .filter_map(|item| serde_dynamo::from_item::<_, DateInfo>(item).ok())
-                    .collect::<Vec<DateInfo>>()
-            } else {
-                Vec::new()
-            }
+        .map(|items| {
+            items
+                .into_iter()
+                .filter_map(|item| serde_dynamo::from_item::<_, DateInfo>(item).ok())
+                .collect::<Vec<DateInfo>>()
         })
         .map_err(|err| {
             let msg = err.message().unwrap_or("Unknown error occurred");
@@ -76,3 +135,187 @@ pub async fn get_important_dates() -> Result<Vec<DateInfo>, ServerFnError> {
             ServerFnError::new(msg)
         })
 }
+
+/// Gets the sort key of the extensions for a purpose. Extensions are stored in the main table
+/// under `EXTENSION#<student_id>`, apart from the student's data.
+#[cfg(feature = "ssr")]
+fn extension_sort_key(purpose: DatePurpose) -> String {
+    format!("EXTENSION#{purpose:?}")
+}
+
+/// Gets the end of a student's extension for a purpose, if they have one.
+#[cfg(feature = "ssr")]
+async fn get_extension(
+    client: &DynamoClient,
+    purpose: DatePurpose,
+    subject: &str,
+) -> Result<Option<DateTime<FixedOffset>>, ServerFnError> {
+    let item = client
+        .get_item()
+        .table_name(MAIN_TABLE_NAME)
+        .key("HK", AttributeValue::S(format!("EXTENSION#{subject}")))
+        .key("SK", AttributeValue::S(extension_sort_key(purpose)))
+        .send()
+        .await
+        .map_err(|err| {
+            let msg = format!(
+                "Couldn't get extension from Dynamo: {}",
+                err.message().unwrap_or("Unknown error occurred")
+            );
+            error!("{}", msg);
+            ServerFnError::new(msg)
+        })?
+        .item;
+
+    Ok(item
+        .as_ref()
+        .and_then(|item| item.get("until"))
+        .and_then(|until| until.as_s().ok())
+        .and_then(|until| DateTime::parse_from_rfc3339(until).ok()))
+}
+
+/// Gets whether the part of the site that a purpose controls is open. If a student is given,
+/// their extension is applied.
+#[cfg(feature = "ssr")]
+async fn form_window(
+    purpose: DatePurpose,
+    subject: Option<&str>,
+) -> Result<WindowState, ServerFnError> {
+    let now = chrono::Utc::now();
+    let dates = get_important_dates().await?;
+    let state = WindowState::for_purpose(&dates, purpose, now);
+
+    let Some(subject) = subject.filter(|_| !state.is_open()) else {
+        return Ok(state);
+    };
+    let client = create_dynamo_client().await;
+    let extension = get_extension(&client, purpose, subject).await?;
+
+    Ok(state.with_extension(extension, now))
+}
+
+/// Gets whether the part of the site that a purpose controls is open. If a student's access token
+/// is given, their extension is applied.
+#[server]
+pub async fn get_form_window(
+    purpose: DatePurpose,
+    access_token: Option<String>,
+) -> Result<WindowState, ServerFnError> {
+    let subject = match access_token {
+        Some(access_token) => Some(
+            validate_and_get_token_info(access_token, "us-east-1_Lfjuy5zaM", "us-east-1")
+                .await?
+                .subject,
+        ),
+        None => None,
+    };
+
+    form_window(purpose, subject.as_deref()).await
+}
+
+/// Returns an error if the part of the site that a purpose controls is closed, for use by server
+/// functions that write data. If a student is given, their extension is applied.
+///
+/// Once a write has passed this check and been saved, failures in its follow-up work, such as
+/// updating the eligibility index or releasing a student ID, are only logged. The index can be
+/// rebuilt by an admin.
+#[cfg(feature = "ssr")]
+pub async fn check_form_window(
+    purpose: DatePurpose,
+    subject: Option<&str>,
+) -> Result<(), ServerFnError> {
+    form_window(purpose, subject)
+        .await?
+        .check_open(purpose)
+        .map_err(|msg| {
+            log!("Rejected a write for {:?}: {}", purpose, msg);
+            ServerFnError::new(msg)
+        })
+}
+
+/// Gets the end of every student's extension for a purpose, keyed by student ID. Only available
+/// to administrators.
+#[server]
+pub async fn admin_get_extensions(
+    access_token: String,
+    purpose: DatePurpose,
+) -> Result<HashMap<String, DateTime<FixedOffset>>, ServerFnError> {
+    let _ = validate_and_get_token_info(access_token, "us-east-1_rvCU4Xy4j", "us-east-1").await?;
+
+    let client = create_dynamo_client().await;
+
+    let items = client
+        .scan()
+        .table_name(MAIN_TABLE_NAME)
+        .filter_expression("begins_with(HK, :prefix) AND SK = :sk")
+        .expression_attribute_values(":prefix", AttributeValue::S("EXTENSION#".to_string()))
+        .expression_attribute_values(":sk", AttributeValue::S(extension_sort_key(purpose)))
+        .into_paginator()
+        .items()
+        .send()
+        .collect::<Result<Vec<_>, _>>()
+        .await
+        .map_err(|err| {
+            let msg = format!(
+                "Couldn't get extensions from Dynamo: {}",
+                err.message().unwrap_or("Unknown error occurred")
+            );
+            error!("{}", msg);
+            ServerFnError::new(msg)
+        })?;
+
+    Ok(items
+        .iter()
+        .filter_map(|item| {
+            let student_id = item.get("HK")?.as_s().ok()?.strip_prefix("EXTENSION#")?;
+            let until = DateTime::parse_from_rfc3339(item.get("until")?.as_s().ok()?).ok()?;
+            Some((student_id.to_string(), until))
+        })
+        .collect())
+}
+
+/// Gives a student an extension for a purpose, which keeps its window open for them until the
+/// given time. Removes the extension if `until` is `None`. Only available to administrators.
+#[server]
+pub async fn admin_set_extension(
+    access_token: String,
+    purpose: DatePurpose,
+    student_id: String,
+    until: Option<DateTime<FixedOffset>>,
+) -> Result<(), ServerFnError> {
+    let _ = validate_and_get_token_info(access_token, "us-east-1_rvCU4Xy4j", "us-east-1").await?;
+
+    let client = create_dynamo_client().await;
+    let hash_key = AttributeValue::S(format!("EXTENSION#{student_id}"));
+    let sort_key = AttributeValue::S(extension_sort_key(purpose));
+
+    log!("Setting {:?} extension of {:?} to {:?}", purpose, student_id, until);
+
+    let result = match until {
+        Some(until) => client
+            .put_item()
+            .table_name(MAIN_TABLE_NAME)
+            .item("HK", hash_key)
+            .item("SK", sort_key)
+            .item("until", AttributeValue::S(until.to_rfc3339()))
+            .send()
+            .await
+            .map(|_| ())
+            .map_err(|err| err.message().unwrap_or("Unknown error occurred").to_string()),
+        None => client
+            .delete_item()
+            .table_name(MAIN_TABLE_NAME)
+            .key("HK", hash_key)
+            .key("SK", sort_key)
+            .send()
+            .await
+            .map(|_| ())
+            .map_err(|err| err.message().unwrap_or("Unknown error occurred").to_string()),
+    };
+
+    result.map_err(|err| {
+        let msg = format!("Couldn't save extension to Dynamo: {}", err);
+        error!("{}", msg);
+        ServerFnError::new(msg)
+    })
+}
@@ -0,0 +1,699 @@
+use crate::common::{EligibilityResult, ExpandableInfo, ScoreBreakdown, ValueType};
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[cfg(feature = "ssr")]
+mod imports {
+    pub use super::super::{ELIGIBILITY_TABLE, MAIN_TABLE_NAME};
+    pub use super::super::students::{StudentDemographics, get_student_data};
+    pub use super::super::{get_all_scholarship_info, get_comparison_info, get_scholarship_info};
+    pub use crate::common::{
+        ComparisonData, ESSAY_FORM, FAFSA_FORM, FAFSA_INPUT, ValueMap, ValuePath, evaluate_scholarship,
+        get_requirements, get_scoring_criteria,
+    };
+    pub use crate::pages::api::tokens::validate_and_get_token_info;
+    pub use crate::utils::server::*;
+    pub use aws_sdk_dynamodb::Client as DynamoClient;
+    pub use aws_sdk_dynamodb::error::ProvideErrorMetadata;
+    pub use aws_sdk_dynamodb::types::{AttributeValue, DeleteRequest, PutRequest, WriteRequest};
+    pub use leptos::logging::{debug_log, error, log};
+    pub use std::collections::HashSet;
+}
+
+#[cfg(feature = "ssr")]
+use imports::*;
+
+/// The information about an eligible student that is sent to applicant lists. Anything else
+/// about the student must be requested separately.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ApplicantSummary {
+    pub student_id: String,
+    pub first_name: String,
+    pub last_name: String,
+    /// The student's score from the scholarship's scoring criteria.
+    pub score: ScoreBreakdown,
+}
+
+/// The number of students that pass a single requirement category.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CategoryCount {
+    pub category: String,
+    pub passing: usize,
+}
+
+/// # Applicant Count Preview
+///
+/// The number of current students that would be eligible for a set of requirements. Only counts
+/// are included, so that providers can tune their requirements without seeing who the students
+/// are. Created using [`preview_applicant_counts`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct ApplicantCountPreview {
+    /// The number of students that have completed their forms.
+    pub total_students: usize,
+    /// The number of students that pass every requirement category.
+    pub requirements_met: usize,
+    /// The number of students that pass every requirement category and have uploaded their FAFSA.
+    pub fafsa_uploaded: usize,
+    /// The number of students that pass each category on its own, sorted by category key.
+    pub categories: Vec<CategoryCount>,
+}
+
+/// A student's form data, along with the sort keys of all of their uploaded files.
+#[cfg(feature = "ssr")]
+#[derive(Default)]
+struct StudentRecord {
+    data: HashMap<String, ValueType>,
+    files: Vec<String>,
+}
+
+#[cfg(feature = "ssr")]
+impl StudentRecord {
+    /// Adds an item from the main table to this record.
+    fn add_item(&mut self, item: HashMap<String, AttributeValue>) {
+        let mut data = item
+            .iter()
+            .map(|(k, v)| (k.clone(), ValueType::from(v)))
+            .collect::<HashMap<String, ValueType>>();
+        data.remove_path("HK");
+        let sort_key = data
+            .remove_path("SK")
+            .and_then(|v| v.as_string().ok().flatten())
+            .unwrap_or_default();
+
+        if sort_key.starts_with("FILE#") {
+            self.files.push(sort_key);
+        } else {
+            self.data.merge(data);
+        }
+    }
+
+    /// Reads the student's demographics form, if they've completed it.
+    fn demographics(&self) -> Option<StudentDemographics> {
+        StudentDemographics::from_map(&self.data).ok()
+    }
+
+    /// Students can only be eligible once they've completed the demographics form.
+    fn is_complete(&self) -> bool {
+        self.demographics().is_some()
+    }
+}
+
+/// # Eligibility Index Entry
+///
+/// The stored eligibility result for a single student and scholarship. The index table is keyed
+/// by `scholarship_id` and `student_id`, so that the eligible students of a scholarship can be
+/// read with a single query instead of checking every student on every page load.
+///
+/// Entries are updated whenever a student's data or files change, and whenever a scholarship is
+/// saved. The whole index can be rebuilt using [`rebuild_eligibility_index`].
+#[cfg(feature = "ssr")]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct EligibilityIndexEntry {
+    scholarship_id: String,
+    student_id: String,
+    first_name: String,
+    last_name: String,
+    /// Whether the student is eligible, including required files.
+    eligible: bool,
+    result: EligibilityResult,
+}
+
+#[cfg(feature = "ssr")]
+impl EligibilityIndexEntry {
+    fn new(
+        student_id: &str,
+        record: &StudentRecord,
+        scholarship: &ExpandableInfo,
+        comparisons: &[ComparisonData],
+    ) -> Self {
+        let result = evaluate_scholarship(&record.data, scholarship, comparisons, &record.files);
+        let demographics = record.demographics();
+
+        Self {
+            scholarship_id: scholarship.subject.clone(),
+            student_id: student_id.to_string(),
+            eligible: demographics.is_some() && result.is_eligible(),
+            first_name: demographics
+                .as_ref()
+                .map(|info| info.first_name.clone())
+                .unwrap_or_default(),
+            last_name: demographics
+                .map(|info| info.last_name)
+                .unwrap_or_default(),
+            result,
+        }
+    }
+
+    fn summary(self) -> ApplicantSummary {
+        ApplicantSummary {
+            student_id: self.student_id,
+            first_name: self.first_name,
+            last_name: self.last_name,
+            score: self.result.score,
+        }
+    }
+}
+
+//#region Index Maintenance
+
+#[cfg(feature = "ssr")]
+fn dynamo_error<E: ProvideErrorMetadata>(err: E) -> ServerFnError {
+    let msg = err.message().unwrap_or("Unknown error occurred").to_string();
+    error!("{}", msg);
+    ServerFnError::new(msg)
+}
+
+/// Gets the records of all students, keyed by the student's ID.
+#[cfg(feature = "ssr")]
+async fn get_student_records(
+    client: &DynamoClient,
+) -> Result<HashMap<String, StudentRecord>, ServerFnError> {
+    let items = client
+        .scan()
+        .table_name(MAIN_TABLE_NAME)
+        .into_paginator()
+        .items()
+        .send()
+        .collect::<Result<Vec<_>, _>>()
+        .await
+        .map_err(dynamo_error)?;
+
+    let mut records = HashMap::<String, StudentRecord>::new();
+
+    for item in items {
+        // Other items in the table, such as form drafts, aren't part of a student's record.
+        let Some(student_id) = item
+            .get("HK")
+            .and_then(|v| v.as_s().ok())
+            .and_then(|hk| hk.strip_prefix("STUDENT#"))
+            .map(str::to_string)
+        else {
+            continue;
+        };
+
+        records.entry(student_id).or_default().add_item(item);
+    }
+
+    Ok(records)
+}
+
+/// Gets the record of a single student.
+#[cfg(feature = "ssr")]
+async fn get_student_record(
+    client: &DynamoClient,
+    student_id: &str,
+) -> Result<StudentRecord, ServerFnError> {
+    let items = client
+        .query()
+        .table_name(MAIN_TABLE_NAME)
+        .key_condition_expression("HK = :hk")
+        .expression_attribute_values(":hk", AttributeValue::S(format!("STUDENT#{student_id}")))
+        .into_paginator()
+        .items()
+        .send()
+        .collect::<Result<Vec<_>, _>>()
+        .await
+        .map_err(dynamo_error)?;
+
+    let mut record = StudentRecord::default();
+    items.into_iter().for_each(|item| record.add_item(item));
+
+    Ok(record)
+}
+
+/// Gets the keys of all index entries. If a scholarship ID is given, only the entries for that
+/// scholarship are returned.
+#[cfg(feature = "ssr")]
+async fn get_index_keys(
+    client: &DynamoClient,
+    scholarship_id: Option<&str>,
+) -> Result<HashSet<(String, String)>, ServerFnError> {
+    let items = match scholarship_id {
+        Some(id) => client
+            .query()
+            .table_name(ELIGIBILITY_TABLE)
+            .key_condition_expression("scholarship_id = :id")
+            .expression_attribute_values(":id", AttributeValue::S(id.to_string()))
+            .projection_expression("scholarship_id, student_id")
+            .into_paginator()
+            .items()
+            .send()
+            .collect::<Result<Vec<_>, _>>()
+            .await
+            .map_err(dynamo_error)?,
+        None => client
+            .scan()
+            .table_name(ELIGIBILITY_TABLE)
+            .projection_expression("scholarship_id, student_id")
+            .into_paginator()
+            .items()
+            .send()
+            .collect::<Result<Vec<_>, _>>()
+            .await
+            .map_err(dynamo_error)?,
+    };
+
+    let get = |item: &HashMap<String, AttributeValue>, key: &str| {
+        item.get(key)
+            .and_then(|v| v.as_s().ok().cloned())
+            .unwrap_or_default()
+    };
+
+    Ok(items
+        .iter()
+        .map(|item| (get(item, "scholarship_id"), get(item, "student_id")))
+        .collect())
+}
+
+/// Writes the given entries to the index, and removes the given keys.
+#[cfg(feature = "ssr")]
+async fn write_index(
+    client: &DynamoClient,
+    entries: Vec<EligibilityIndexEntry>,
+    removed: impl IntoIterator<Item = (String, String)>,
+) -> Result<(), ServerFnError> {
+    let mut requests = Vec::new();
+
+    for entry in entries {
+        let put_request = PutRequest::builder()
+            .set_item(Some(serde_dynamo::to_item(&entry)?))
+            .build()?;
+        requests.push(WriteRequest::builder().put_request(put_request).build());
+    }
+
+    for (scholarship_id, student_id) in removed {
+        let delete_request = DeleteRequest::builder()
+            .key("scholarship_id", AttributeValue::S(scholarship_id))
+            .key("student_id", AttributeValue::S(student_id))
+            .build()?;
+        requests.push(WriteRequest::builder().delete_request(delete_request).build());
+    }
+
+    batch_write_requests(client, ELIGIBILITY_TABLE, requests).await
+}
+
+/// Recomputes a student's eligibility for every scholarship.
+#[cfg(feature = "ssr")]
+pub async fn reindex_student(student_id: &str) -> Result<(), ServerFnError> {
+    let (scholarships, comparisons) =
+        tokio::join!(get_all_scholarship_info(), get_comparison_info());
+
+    index_student(student_id, &scholarships?, &comparisons?).await
+}
+
+/// Recomputes a student's eligibility for every scholarship, using scholarships and comparisons
+/// that have already been loaded.
+#[cfg(feature = "ssr")]
+async fn index_student(
+    student_id: &str,
+    scholarships: &[ExpandableInfo],
+    comparisons: &[ComparisonData],
+) -> Result<(), ServerFnError> {
+    let client = create_dynamo_client().await;
+    let record = get_student_record(&client, student_id).await?;
+
+    let entries = scholarships
+        .iter()
+        .map(|scholarship| EligibilityIndexEntry::new(student_id, &record, scholarship, comparisons))
+        .collect::<Vec<EligibilityIndexEntry>>();
+
+    debug_log!(
+        "Reindexing student {:?} against {} scholarships",
+        student_id,
+        entries.len()
+    );
+
+    write_index(&client, entries, []).await
+}
+
+/// Recomputes a student's eligibility, but only if one of the changed fields is used by a
+/// comparison or scoring criterion, or decides whether the student has completed their forms.
+#[cfg(feature = "ssr")]
+pub async fn reindex_student_fields(
+    student_id: &str,
+    changed_fields: HashSet<String>,
+) -> Result<(), ServerFnError> {
+    let (comparisons, scholarships) = tokio::join!(get_comparison_info(), get_all_scholarship_info());
+    let (comparisons, scholarships) = (comparisons?, scholarships?);
+
+    let mut relevant_fields = HashSet::from(["first_name".to_string(), "last_name".to_string()]);
+    relevant_fields.extend(comparisons.iter().map(|comparison| comparison.member.clone()));
+    relevant_fields.extend(
+        scholarships
+            .iter()
+            .flat_map(get_scoring_criteria)
+            .flatten()
+            .flat_map(|criterion| criterion.members()),
+    );
+
+    if changed_fields.iter().any(|field| relevant_fields.contains(field)) {
+        index_student(student_id, &scholarships, &comparisons).await
+    } else {
+        debug_log!("No eligibility inputs changed for student {:?}", student_id);
+        Ok(())
+    }
+}
+
+/// Recomputes a student's eligibility after a file upload or removal. Only FAFSA and essay files
+/// affect eligibility.
+#[cfg(feature = "ssr")]
+pub async fn reindex_student_file(
+    student_id: &str,
+    form_id: &str,
+    input_name: &str,
+) -> Result<(), ServerFnError> {
+    if (form_id == FAFSA_FORM && input_name == FAFSA_INPUT) || form_id == ESSAY_FORM {
+        reindex_student(student_id).await
+    } else {
+        Ok(())
+    }
+}
+
+/// Recomputes every student's eligibility for a single scholarship.
+#[cfg(feature = "ssr")]
+pub async fn reindex_scholarship(scholarship: &ExpandableInfo) -> Result<(), ServerFnError> {
+    let client = create_dynamo_client().await;
+
+    let (records, comparisons) = tokio::join!(get_student_records(&client), get_comparison_info());
+    let (records, comparisons) = (records?, comparisons?);
+
+    let entries = records
+        .iter()
+        .map(|(id, record)| EligibilityIndexEntry::new(id, record, scholarship, &comparisons))
+        .collect::<Vec<EligibilityIndexEntry>>();
+
+    log!(
+        "Reindexing scholarship {:?} against {} students",
+        scholarship.subject,
+        entries.len()
+    );
+
+    write_index(&client, entries, []).await
+}
+
+/// Recomputes every student's eligibility for the scholarships that require any of the given
+/// comparisons, such as after the comparisons are overwritten.
+#[cfg(feature = "ssr")]
+pub async fn reindex_comparisons(comparison_ids: &[String]) -> Result<(), ServerFnError> {
+    let client = create_dynamo_client().await;
+
+    let (records, scholarships, comparisons) = tokio::join!(
+        get_student_records(&client),
+        get_all_scholarship_info(),
+        get_comparison_info()
+    );
+    let (records, scholarships, comparisons) = (records?, scholarships?, comparisons?);
+
+    let entries = scholarships
+        .iter()
+        .filter(|scholarship| {
+            get_requirements(scholarship)
+                .values()
+                .any(|requirement| requirement.ids.iter().any(|id| comparison_ids.contains(id)))
+        })
+        .flat_map(|scholarship| {
+            records.iter().map(|(id, record)| {
+                EligibilityIndexEntry::new(id, record, scholarship, &comparisons)
+            })
+        })
+        .collect::<Vec<EligibilityIndexEntry>>();
+
+    log!(
+        "Reindexing {} entries after changes to {} comparisons",
+        entries.len(),
+        comparison_ids.len()
+    );
+
+    write_index(&client, entries, []).await
+}
+
+/// Removes all index entries for a scholarship.
+#[cfg(feature = "ssr")]
+pub async fn remove_scholarship_from_index(
+    scholarship_id: &str,
+) -> Result<(), ServerFnError> {
+    let client = create_dynamo_client().await;
+    let keys = get_index_keys(&client, Some(scholarship_id)).await?;
+
+    write_index(&client, vec![], keys).await
+}
+
+/// Rebuilds the whole eligibility index from the current students, scholarships, and
+/// comparisons. Entries for students or scholarships that no longer exist are removed. Returns
+/// the number of entries in the rebuilt index. Only available to administrators.
+#[server]
+pub async fn rebuild_eligibility_index(access_token: String) -> Result<usize, ServerFnError> {
+    let _ = validate_and_get_token_info(access_token, "us-east-1_rvCU4Xy4j", "us-east-1").await?;
+
+    let client = create_dynamo_client().await;
+
+    let (records, scholarships, comparisons, existing) = tokio::join!(
+        get_student_records(&client),
+        get_all_scholarship_info(),
+        get_comparison_info(),
+        get_index_keys(&client, None)
+    );
+    let (records, scholarships, comparisons, mut existing) =
+        (records?, scholarships?, comparisons?, existing?);
+
+    let entries = scholarships
+        .iter()
+        .flat_map(|scholarship| {
+            records.iter().map(|(id, record)| {
+                EligibilityIndexEntry::new(id, record, scholarship, &comparisons)
+            })
+        })
+        .collect::<Vec<EligibilityIndexEntry>>();
+
+    for entry in &entries {
+        existing.remove(&(entry.scholarship_id.clone(), entry.student_id.clone()));
+    }
+
+    let count = entries.len();
+    log!(
+        "Rebuilding eligibility index with {} entries, removing {} stale entries",
+        count,
+        existing.len()
+    );
+
+    write_index(&client, entries, existing).await?;
+
+    Ok(count)
+}
+
+//#endregion
+//#region Applicant Lists
+
+/// Gets a summary of every student that is eligible for the scholarship from the index, sorted by
+/// last name. Eligibility is checked on the server, so that the data of ineligible students never
+/// leaves the server.
+#[cfg(feature = "ssr")]
+async fn get_eligible_students(
+    scholarship_id: String,
+) -> Result<Vec<ApplicantSummary>, ServerFnError> {
+    let client = create_dynamo_client().await;
+
+    let items = client
+        .query()
+        .table_name(ELIGIBILITY_TABLE)
+        .key_condition_expression("scholarship_id = :id")
+        .filter_expression("eligible = :eligible")
+        .expression_attribute_values(":id", AttributeValue::S(scholarship_id))
+        .expression_attribute_values(":eligible", AttributeValue::Bool(true))
+        .into_paginator()
+        .items()
+        .send()
+        .collect::<Result<Vec<_>, _>>()
+        .await
+        .map_err(dynamo_error)?;
+
+    let mut eligible = serde_dynamo::from_items::<_, EligibilityIndexEntry>(items)?
+        .into_iter()
+        .map(EligibilityIndexEntry::summary)
+        .collect::<Vec<ApplicantSummary>>();
+
+    eligible.sort_by(|a, b| (&a.last_name, &a.first_name).cmp(&(&b.last_name, &b.first_name)));
+
+    Ok(eligible)
+}
+
+/// Gets a scholarship, checking that the token belongs to the provider that owns it.
+#[cfg(feature = "ssr")]
+async fn get_owned_scholarship(
+    access_token: String,
+    scholarship_id: String,
+) -> Result<ExpandableInfo, ServerFnError> {
+    let claims =
+        validate_and_get_token_info(access_token, "us-east-1_Lfjuy5zaM", "us-east-1").await?;
+    if !claims.groups.contains(&"ScholarshipProviders".to_string()) {
+        return Err(ServerFnError::new(
+            "User is not in the ScholarshipProviders group",
+        ));
+    }
+
+    let scholarship = get_scholarship_info(scholarship_id).await?;
+    let provider_id = scholarship
+        .data
+        .get("provider_id")
+        .and_then(|v| v.as_string().ok().flatten())
+        .unwrap_or_default();
+
+    if provider_id != claims.subject {
+        let msg = "This scholarship does not belong to the current provider.";
+        error!("{} Scholarship: {:?}", msg, scholarship.subject);
+        return Err(ServerFnError::new(msg));
+    }
+
+    Ok(scholarship)
+}
+
+/// Gets all students that are eligible for the given scholarship. Only available to the
+/// scholarship provider that owns the scholarship.
+#[server]
+pub async fn provider_get_eligible_students(
+    access_token: String,
+    scholarship_id: String,
+) -> Result<Vec<ApplicantSummary>, ServerFnError> {
+    let scholarship = get_owned_scholarship(access_token, scholarship_id).await?;
+
+    get_eligible_students(scholarship.subject).await
+}
+
+/// Gets one of an applicant's forms, such as `demographics`. Providers can only get the forms of
+/// students that are eligible for a scholarship they own, while administrators can get any
+/// student's forms.
+#[server]
+pub async fn get_applicant_data(
+    access_token: String,
+    scholarship_id: String,
+    student_id: String,
+    data_type: String,
+) -> Result<HashMap<String, ValueType>, ServerFnError> {
+    let is_admin =
+        validate_and_get_token_info(access_token.clone(), "us-east-1_rvCU4Xy4j", "us-east-1")
+            .await
+            .is_ok();
+
+    if !is_admin {
+        let scholarship = get_owned_scholarship(access_token, scholarship_id).await?;
+        let client = create_dynamo_client().await;
+
+        let entry = client
+            .get_item()
+            .table_name(ELIGIBILITY_TABLE)
+            .key("scholarship_id", AttributeValue::S(scholarship.subject))
+            .key("student_id", AttributeValue::S(student_id.clone()))
+            .projection_expression("eligible")
+            .send()
+            .await
+            .map_err(dynamo_error)?
+            .item;
+
+        if entry.and_then(|entry| entry.get("eligible").cloned()) != Some(AttributeValue::Bool(true))
+        {
+            let msg = "This student is not eligible for the scholarship.";
+            error!("{} Student: {:?}", msg, student_id);
+            return Err(ServerFnError::new(msg));
+        }
+    }
+
+    get_student_data(student_id, data_type).await
+}
+
+/// Gets all students that are eligible for the given scholarship. Only available to
+/// administrators.
+#[server]
+pub async fn admin_get_eligible_students(
+    access_token: String,
+    scholarship_id: String,
+) -> Result<Vec<ApplicantSummary>, ServerFnError> {
+    let _ = validate_and_get_token_info(access_token, "us-east-1_rvCU4Xy4j", "us-east-1").await?;
+
+    get_eligible_students(scholarship_id).await
+}
+
+/// Counts the students that match an in-progress set of requirements, without saving them. The
+/// `requirements` use the same format as the `requirements` map stored in a scholarship. Essays
+/// aren't counted, as students can't write an essay for a scholarship until they're eligible.
+/// Only available to scholarship providers.
+#[server]
+pub async fn preview_applicant_counts(
+    access_token: String,
+    requirements: HashMap<String, ValueType>,
+) -> Result<ApplicantCountPreview, ServerFnError> {
+    let claims =
+        validate_and_get_token_info(access_token, "us-east-1_Lfjuy5zaM", "us-east-1").await?;
+    if !claims.groups.contains(&"ScholarshipProviders".to_string()) {
+        return Err(ServerFnError::new(
+            "User is not in the ScholarshipProviders group",
+        ));
+    }
+
+    let mut scholarship = ExpandableInfo::new("preview");
+    scholarship
+        .data
+        .insert("requirements".to_string(), ValueType::Map(Some(requirements)));
+
+    let client = create_dynamo_client().await;
+    let (records, comparisons) = tokio::join!(get_student_records(&client), get_comparison_info());
+    let (records, comparisons) = (records?, comparisons?);
+
+    let mut preview = ApplicantCountPreview::default();
+    let mut category_counts = HashMap::<String, usize>::new();
+
+    for record in records.values().filter(|record| record.is_complete()) {
+        let result = evaluate_scholarship(&record.data, &scholarship, &comparisons, &record.files);
+
+        preview.total_students += 1;
+        if result.requirements_met {
+            preview.requirements_met += 1;
+            if result.fafsa_found {
+                preview.fafsa_uploaded += 1;
+            }
+        }
+        for category in result.categories {
+            *category_counts.entry(category.category).or_default() += category.passed as usize;
+        }
+    }
+
+    preview.categories = category_counts
+        .into_iter()
+        .map(|(category, passing)| CategoryCount { category, passing })
+        .collect();
+    preview.categories.sort_by(|a, b| a.category.cmp(&b.category));
+
+    Ok(preview)
+}
+
+/// Checks the current student against every scholarship. The student is identified using the
+/// access token. Returns each scholarship along with the student's result for that scholarship.
+#[server]
+pub async fn get_student_eligibility(
+    access_token: String,
+) -> Result<Vec<(ExpandableInfo, EligibilityResult)>, ServerFnError> {
+    let claims =
+        validate_and_get_token_info(access_token, "us-east-1_Lfjuy5zaM", "us-east-1").await?;
+
+    let client = create_dynamo_client().await;
+
+    let (record, scholarships, comparisons) = tokio::join!(
+        get_student_record(&client, &claims.subject),
+        get_all_scholarship_info(),
+        get_comparison_info()
+    );
+    let (record, scholarships, comparisons) = (record?, scholarships?, comparisons?);
+
+    let results = scholarships
+        .into_iter()
+        .map(|scholarship| {
+            let result =
+                evaluate_scholarship(&record.data, &scholarship, &comparisons, &record.files);
+            (scholarship, result)
+        })
+        .collect();
+
+    Ok(results)
+}
+
+//#endregion
@@ -1,7 +1,8 @@
 #[cfg(feature = "ssr")]
 mod imports {
     pub use super::super::{COMPARISONS_TABLE, PROVIDER_CONTACT_TABLE, SCHOLARSHIPS_TABLE};
-    pub use crate::common::ValueType;
+    pub use crate::common::{RequirementCategory, ValueMap, ValuePath, ValueType};
+    pub use macros::ValueMap;
     pub use crate::utils::server::create_dynamo_client;
     pub use aws_sdk_dynamodb::Client;
     pub use aws_sdk_dynamodb::error::ProvideErrorMetadata;
@@ -31,6 +32,37 @@ pub async fn get_scholarship_csv() -> Result<Vec<u8>, ServerFnError> {
         residency_requirements: String,
     }
 
+    /// The parts of a scholarship that are exported.
+    #[derive(ValueMap, Default)]
+    struct ScholarshipListing {
+        #[value(default)]
+        name: String,
+        #[value(default)]
+        provider_id: String,
+        #[value(default)]
+        requirements: HashMap<String, ValueType>,
+    }
+
+    /// The parts of a provider's contact that are exported. Unlike
+    /// [`ProviderContact`](super::ProviderContact), missing fields are allowed, so that incomplete
+    /// contacts still export what they have.
+    #[derive(ValueMap, Default, Clone)]
+    struct ContactListing {
+        #[value(default)]
+        first_name: String,
+        #[value(default)]
+        last_name: String,
+        #[value(default)]
+        contact_email: String,
+    }
+
+    /// The parts of a comparison that are exported.
+    #[derive(ValueMap)]
+    struct ComparisonListing {
+        id: String,
+        display_text: String,
+    }
+
     // Get the scholarships and comparisons
     let client = create_dynamo_client().await;
 
@@ -78,6 +110,19 @@ pub async fn get_scholarship_csv() -> Result<Vec<u8>, ServerFnError> {
     let master_contacts = scan_table_hash_map(&client, PROVIDER_CONTACT_TABLE).await;
     let master_relations = scan_table_hash_map(&client, COMPARISONS_TABLE).await;
 
+    // Contacts are keyed by the provider's ID. Incomplete contacts export the fields they have.
+    let master_contacts = master_contacts
+        .into_iter()
+        .map(|contact| {
+            let subject = contact.get_str("subject").unwrap_or_default().to_string();
+            (subject, ContactListing::from_map(&contact).unwrap_or_default())
+        })
+        .collect::<HashMap<String, ContactListing>>();
+    let master_relations = master_relations
+        .iter()
+        .filter_map(|relation| ComparisonListing::from_map(relation).ok())
+        .collect::<Vec<ComparisonListing>>();
+
     // We now want to map the relations to the scholarships, as well as the contacts.
     // This should take the information from the current scholarship, then relate the IDs of the
     // requirements in the scholarship to the IDs of the relations in the master list.
@@ -86,69 +131,31 @@ pub async fn get_scholarship_csv() -> Result<Vec<u8>, ServerFnError> {
         .map(|scholarship| {
             // debug_log!("Scholarship: {:?}", scholarship);
             // We want the requirements IDs and the provider ID.
-            let requirements_map = scholarship
-                .data
-                .get("requirements")
-                .unwrap_or(&ValueType::Map(None))
-                .as_map()
-                .ok()
-                .flatten()
-                .unwrap_or_default();
-
-            let provider_id = scholarship
-                .data
-                .get("provider_id")
-                .map(|v| v.as_string().ok().flatten().unwrap_or_default())
-                .unwrap_or_default();
+            let scholarship = ScholarshipListing::from_expandable(&scholarship).unwrap_or_default();
 
             // Search for the provider ID in the master list.
+            debug_log!("Finding contact for provider {:?}", scholarship.provider_id);
             let contact_info = master_contacts
-                .iter()
-                .find(|contact| {
-                    let subject = contact
-                        .get("subject")
-                        .map(|v| v.as_string().ok().flatten().unwrap_or_default())
-                        .unwrap_or_default();
-
-                    debug_log!("Comparing {:?} and {:?}", subject, provider_id);
-
-                    subject == provider_id
-                })
+                .get(&scholarship.provider_id)
                 .cloned()
                 .unwrap_or_default();
 
             // Resolve the requirements - this should resolve as another category-separated map,
-            // but with requirement display text instead of IDs.
-            let resolved_requirements = requirements_map
+            // but with requirement display text instead of IDs. IDs that don't resolve are
+            // skipped, and are listed in the admin integrity report instead.
+            let resolved_requirements = scholarship
+                .requirements
                 .iter()
                 .map(|(category, selected_val)| {
-                    let selected_list = selected_val.as_list().ok().flatten().unwrap_or_default();
+                    let selected_list = RequirementCategory::from_value(selected_val).ids;
 
                     let resolved_names = selected_list
                         .into_iter()
-                        .map(|id_val| {
-                            let id = id_val.as_string().ok().flatten().unwrap_or_default();
-
-                            let resolved_relation = master_relations
+                        .filter_map(|id| {
+                            master_relations
                                 .iter()
-                                .find(|item| {
-                                    let comp_id = item
-                                        .get("id")
-                                        .map(|v| v.as_string().ok().flatten().unwrap_or_default())
-                                        .unwrap_or_default();
-
-                                    comp_id == id
-                                })
-                                .cloned()
-                                .map(|v| {
-                                    v.get("display_text").map(|text_opt| {
-                                        text_opt.as_string().ok().flatten().unwrap_or_default()
-                                    })
-                                })
-                                .flatten()
-                                .unwrap_or_default();
-
-                            resolved_relation
+                                .find(|relation| relation.id == id)
+                                .map(|relation| relation.display_text.clone())
                         })
                         .collect::<Vec<String>>();
 
@@ -157,26 +164,12 @@ pub async fn get_scholarship_csv() -> Result<Vec<u8>, ServerFnError> {
                 .collect::<IndexMap<String, Vec<String>>>();
             debug_log!("Resolved requirements: {:?}", resolved_requirements);
 
-            let contact_email = contact_info
-                .get("contact_email")
-                .map(|v| v.as_string().ok().flatten().unwrap_or_default())
-                .unwrap_or_default();
-
-            let first_name = contact_info
-                .get("first_name")
-                .map(|v| v.as_string().ok().flatten().unwrap_or_default())
-                .unwrap_or_default();
-
-            let last_name = contact_info
-                .get("last_name")
-                .map(|v| v.as_string().ok().flatten().unwrap_or_default())
-                .unwrap_or_default();
-
-            let scholarship_name = scholarship
-                .data
-                .get("name")
-                .map(|v| v.as_string().ok().flatten().unwrap_or_default())
-                .unwrap_or_default();
+            let ContactListing {
+                contact_email,
+                first_name,
+                last_name,
+            } = contact_info;
+            let scholarship_name = scholarship.name;
 
             debug_log!(
                 "{:?}, {:?}, {:?}, {:?}",
@@ -6,6 +6,8 @@ use server_fn::codec::{MultipartData, MultipartFormData};
 #[cfg(feature = "ssr")]
 mod imports {
     pub use super::super::{MAIN_TABLE_NAME, S3_BUCKET_NAME};
+    pub use crate::common::DatePurpose;
+    pub use crate::pages::api::{check_form_window, reindex_student_file};
     pub use crate::pages::api::tokens::validate_and_get_token_info;
     pub use crate::utils::server::create_aws_config;
     pub use aws_sdk_dynamodb::{
@@ -68,6 +70,7 @@ pub async fn upload_file(data: MultipartData) -> Result<String, ServerFnError> {
 
     let user_claims = validate_and_get_token_info(access_token, "us-east-1_Lfjuy5zaM", "us-east-1").await?;
     let subject = user_claims.subject;
+    check_form_window(DatePurpose::StudentForms, Some(&subject)).await?;
 
     let key = format!("{form_id}/{subject}/{input_name}/{file_name}");
 
@@ -145,6 +148,10 @@ pub async fn upload_file(data: MultipartData) -> Result<String, ServerFnError> {
         return Err(err);
     }
 
+    if let Err(err) = reindex_student_file(&subject, &form_id, &input_name).await {
+        error!("Couldn't update eligibility index for student {:?}: {}", subject, err);
+    }
+
     Ok(file_name)
 }
 
@@ -159,6 +166,7 @@ pub async fn delete_file(
 
     let user_claims = validate_and_get_token_info(access_token, "us-east-1_Lfjuy5zaM", "us-east-1").await?;
     let subject = user_claims.subject;
+    check_form_window(DatePurpose::StudentForms, Some(&subject)).await?;
 
     let entry_hk = format!("STUDENT#{subject}");
     let entry_sk = format!("FILE#{form_id}#{input_name}#{file_name}");
@@ -215,6 +223,10 @@ pub async fn delete_file(
         return Err(err);
     }
 
+    if let Err(err) = reindex_student_file(&subject, &form_id, &input_name).await {
+        error!("Couldn't update eligibility index for student {:?}: {}", subject, err);
+    }
+
     Ok(file_name)
 }
 
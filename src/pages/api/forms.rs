@@ -0,0 +1,577 @@
+use crate::common::{FieldMigration, FormDefinition};
+use leptos::prelude::*;
+use leptos::server_fn::codec::Json;
+
+#[cfg(feature = "ssr")]
+mod imports {
+    pub use super::super::{COMPARISONS_TABLE, FORMS_TABLE, MAIN_TABLE_NAME};
+    pub use crate::common::{get_form_definition, rename_scoring_field, student_form_definitions};
+    pub use crate::pages::api::{
+        create_scholarship_info, get_all_scholarship_info, get_comparison_info,
+        reindex_comparisons, reindex_student_fields,
+    };
+    pub use crate::pages::api::tokens::validate_and_get_token_info;
+    pub use crate::utils::server::*;
+    pub use aws_sdk_dynamodb::Client as DynamoClient;
+    pub use aws_sdk_dynamodb::error::ProvideErrorMetadata;
+    pub use aws_sdk_dynamodb::types::{AttributeValue, DeleteRequest, PutRequest, WriteRequest};
+    pub use leptos::logging::{debug_log, error, log};
+    pub use serde::{Deserialize, Serialize};
+    pub use std::collections::{HashMap, HashSet};
+}
+
+#[cfg(feature = "ssr")]
+use imports::*;
+
+/// # Form Record
+///
+/// A stored version of a student form. Every form is stored under `FORM#<id>`, with one item per
+/// sort key:
+/// * `PUBLISHED`: the version that students see.
+/// * `DRAFT`: an administrator's unpublished changes.
+/// * `VERSION#<n>`: every published version, along with the migrations it was published with.
+///
+/// Forms that were never published use their definition from the code.
+#[cfg(feature = "ssr")]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct FormRecord {
+    #[serde(rename = "HK")]
+    hk: String,
+    #[serde(rename = "SK")]
+    sk: String,
+    definition: FormDefinition,
+    #[serde(default)]
+    migrations: Vec<FieldMigration>,
+}
+
+#[cfg(feature = "ssr")]
+impl FormRecord {
+    fn new(
+        sk: impl Into<String>,
+        definition: FormDefinition,
+        migrations: Vec<FieldMigration>,
+    ) -> Self {
+        Self {
+            hk: form_key(&definition.id),
+            sk: sk.into(),
+            definition,
+            migrations,
+        }
+    }
+}
+
+#[cfg(feature = "ssr")]
+fn form_key(id: &str) -> String {
+    format!("FORM#{}", id.to_uppercase())
+}
+
+#[cfg(feature = "ssr")]
+fn version_key(version: u32) -> String {
+    format!("VERSION#{:04}", version)
+}
+
+#[cfg(feature = "ssr")]
+fn dynamo_error<E: ProvideErrorMetadata>(err: E) -> ServerFnError {
+    let msg = err
+        .message()
+        .unwrap_or("Unknown error occurred")
+        .to_string();
+    error!("{}", msg);
+    ServerFnError::new(msg)
+}
+
+/// Gets a stored version of a form, if there is one.
+#[cfg(feature = "ssr")]
+async fn get_form_record(
+    client: &DynamoClient,
+    id: &str,
+    sk: &str,
+) -> Result<Option<FormRecord>, ServerFnError> {
+    let output = client
+        .get_item()
+        .table_name(FORMS_TABLE)
+        .key("HK", AttributeValue::S(form_key(id)))
+        .key("SK", AttributeValue::S(sk.to_string()))
+        .send()
+        .await
+        .map_err(dynamo_error)?;
+
+    Ok(output.item.map(serde_dynamo::from_item).transpose()?)
+}
+
+/// Gets the published definitions of every student form. Forms keep the order of their
+/// definitions in the code, and forms that only exist in the table are added to the end.
+#[cfg(feature = "ssr")]
+pub async fn load_form_definitions() -> Result<Vec<FormDefinition>, ServerFnError> {
+    let client = create_dynamo_client().await;
+
+    let items = client
+        .scan()
+        .table_name(FORMS_TABLE)
+        .filter_expression("SK = :sk")
+        .expression_attribute_values(":sk", AttributeValue::S("PUBLISHED".to_string()))
+        .into_paginator()
+        .items()
+        .send()
+        .collect::<Result<Vec<_>, _>>()
+        .await
+        .map_err(dynamo_error)?;
+    let mut published = serde_dynamo::from_items::<_, FormRecord>(items)?
+        .into_iter()
+        .map(|record| record.definition)
+        .collect::<Vec<FormDefinition>>();
+    published.sort_by(|a, b| a.id.cmp(&b.id));
+
+    let mut definitions = student_form_definitions();
+    for definition in published {
+        match definitions
+            .iter_mut()
+            .find(|existing| existing.id.eq_ignore_ascii_case(&definition.id))
+        {
+            Some(existing) => *existing = definition,
+            None => definitions.push(definition),
+        }
+    }
+
+    Ok(definitions)
+}
+
+/// Gets the published definition of a student form, returning an error if the form doesn't
+/// exist.
+#[cfg(feature = "ssr")]
+pub async fn load_form_definition(id: &str) -> Result<FormDefinition, ServerFnError> {
+    let client = create_dynamo_client().await;
+
+    if let Some(record) = get_form_record(&client, id, "PUBLISHED").await? {
+        return Ok(record.definition);
+    }
+
+    get_form_definition(id).ok_or_else(|| {
+        let msg = format!("There is no form called {:?}.", id);
+        error!("{}", msg);
+        ServerFnError::new(msg)
+    })
+}
+
+/// The number of times a student's stored copy of a form is read and migrated before giving up,
+/// if it keeps changing while it's being migrated.
+#[cfg(feature = "ssr")]
+const MIGRATION_ATTEMPTS: usize = 3;
+
+/// Moves a stored value from one path to another in a student's item. Paths inside a `MultiEntry`
+/// move the value inside each of its entries. Values are never moved over an existing value, so
+/// migrations can be run again. Returns whether anything moved.
+#[cfg(feature = "ssr")]
+fn move_stored_value(item: &mut HashMap<String, AttributeValue>, from: &str, to: &str) -> bool {
+    let Some((list, from)) = from.split_once('.') else {
+        if item.contains_key(to) {
+            return false;
+        }
+        return match item.remove(from) {
+            Some(value) => {
+                item.insert(to.to_string(), value);
+                true
+            }
+            None => false,
+        };
+    };
+
+    // Renames stay in the same list, so `to` is inside the same list as `from`.
+    let (Some(to), Some(AttributeValue::L(entries))) = (
+        to.strip_prefix(list).and_then(|to| to.strip_prefix('.')),
+        item.get_mut(list),
+    ) else {
+        return false;
+    };
+
+    let mut moved = false;
+    for entry in entries {
+        if let AttributeValue::M(entry) = entry {
+            moved |= move_stored_value(entry, from, to);
+        }
+    }
+    moved
+}
+
+/// Applies renames to one student's stored copy of a form. The item is only written if it hasn't
+/// changed since it was read, and it's read again if it has. Returns the top-level keys that
+/// changed.
+#[cfg(feature = "ssr")]
+async fn migrate_student_item(
+    client: &DynamoClient,
+    mut item: HashMap<String, AttributeValue>,
+    renames: &[(&str, &str)],
+) -> Result<HashSet<String>, ServerFnError> {
+    let (Some(hk), Some(sk)) = (item.get("HK").cloned(), item.get("SK").cloned()) else {
+        return Ok(HashSet::new());
+    };
+
+    for _ in 0..MIGRATION_ATTEMPTS {
+        let mut migrated = item.clone();
+        for (from, to) in renames {
+            move_stored_value(&mut migrated, from, to);
+        }
+        let changed = item
+            .keys()
+            .chain(migrated.keys())
+            .filter(|key| item.get(*key) != migrated.get(*key))
+            .cloned()
+            .collect::<HashSet<String>>();
+        if changed.is_empty() {
+            return Ok(changed);
+        }
+
+        let mut request = client
+            .update_item()
+            .table_name(MAIN_TABLE_NAME)
+            .key("HK", hk.clone())
+            .key("SK", sk.clone());
+        let (mut sets, mut removes, mut conditions) = (Vec::new(), Vec::new(), Vec::new());
+        for (i, key) in changed.iter().enumerate() {
+            request = request.expression_attribute_names(format!("#k{i}"), key);
+            match migrated.get(key) {
+                Some(value) => {
+                    sets.push(format!("#k{i} = :new{i}"));
+                    request = request.expression_attribute_values(format!(":new{i}"), value.clone());
+                }
+                None => removes.push(format!("#k{i}")),
+            }
+            match item.get(key) {
+                Some(value) => {
+                    conditions.push(format!("#k{i} = :old{i}"));
+                    request = request.expression_attribute_values(format!(":old{i}"), value.clone());
+                }
+                None => conditions.push(format!("attribute_not_exists(#k{i})")),
+            }
+        }
+
+        let mut update_expression = Vec::new();
+        if !sets.is_empty() {
+            update_expression.push(format!("SET {}", sets.join(", ")));
+        }
+        if !removes.is_empty() {
+            update_expression.push(format!("REMOVE {}", removes.join(", ")));
+        }
+
+        let result = request
+            .update_expression(update_expression.join(" "))
+            .condition_expression(conditions.join(" AND "))
+            .send()
+            .await;
+
+        match result {
+            Ok(_) => return Ok(changed),
+            // The student saved the form while it was being migrated, so migrate their new copy.
+            Err(err) if err.code() == Some("ConditionalCheckFailedException") => {
+                let output = client
+                    .get_item()
+                    .table_name(MAIN_TABLE_NAME)
+                    .key("HK", hk.clone())
+                    .key("SK", sk.clone())
+                    .consistent_read(true)
+                    .send()
+                    .await
+                    .map_err(dynamo_error)?;
+                match output.item {
+                    Some(latest) => item = latest,
+                    None => return Ok(HashSet::new()),
+                }
+            }
+            Err(err) => return Err(dynamo_error(err)),
+        }
+    }
+
+    let msg = format!("{hk:?} kept changing while its stored values were being moved.");
+    error!("{}", msg);
+    Err(ServerFnError::new(msg))
+}
+
+/// Applies the renames that a version of a form was published with to everything that refers to
+/// the renamed paths: students' stored values, comparisons, and scholarships' scoring criteria.
+/// Students whose values moved are then reindexed. Anything that was already renamed is left
+/// alone, so this can be run again if it fails partway through.
+#[cfg(feature = "ssr")]
+async fn apply_migrations(
+    client: &DynamoClient,
+    form_id: &str,
+    migrations: &[FieldMigration],
+) -> Result<(), ServerFnError> {
+    let renames = migrations
+        .iter()
+        .filter_map(|migration| match migration {
+            FieldMigration::Rename { from, to } => Some((from.as_str(), to.as_str())),
+            FieldMigration::Retire { .. } => None,
+        })
+        .collect::<Vec<_>>();
+    if renames.is_empty() {
+        return Ok(());
+    }
+
+    let items = client
+        .scan()
+        .table_name(MAIN_TABLE_NAME)
+        .filter_expression("SK = :sk")
+        .expression_attribute_values(":sk", AttributeValue::S(form_id.to_uppercase()))
+        .into_paginator()
+        .items()
+        .send()
+        .collect::<Result<Vec<_>, _>>()
+        .await
+        .map_err(dynamo_error)?;
+
+    let mut moved = HashMap::new();
+    for item in items {
+        let student_id = item
+            .get("HK")
+            .and_then(|hk| hk.as_s().ok())
+            .and_then(|hk| hk.strip_prefix("STUDENT#"))
+            .map(str::to_string);
+        let changed = migrate_student_item(client, item, &renames).await?;
+        if let Some(student_id) = student_id
+            && !changed.is_empty()
+        {
+            moved.insert(student_id, changed);
+        }
+    }
+
+    let mut comparison_ids = Vec::new();
+    for mut comparison in get_comparison_info().await? {
+        let mut renamed = false;
+        for (from, to) in &renames {
+            renamed |= comparison.rename_field(from, to);
+        }
+        if !renamed {
+            continue;
+        }
+
+        client
+            .put_item()
+            .table_name(COMPARISONS_TABLE)
+            .set_item(Some(serde_dynamo::to_item(&comparison)?))
+            .send()
+            .await
+            .map_err(dynamo_error)?;
+        comparison_ids.push(comparison.id);
+    }
+
+    let mut scholarship_count = 0;
+    for mut scholarship in get_all_scholarship_info().await? {
+        let mut renamed = false;
+        for (from, to) in &renames {
+            renamed |= rename_scoring_field(&mut scholarship, from, to);
+        }
+        if renamed {
+            create_scholarship_info(scholarship).await?;
+            scholarship_count += 1;
+        }
+    }
+
+    log!(
+        "Migrated form {:?}: moved values for {} students, and renamed fields in {} comparisons \
+        and {} scholarships",
+        form_id,
+        moved.len(),
+        comparison_ids.len(),
+        scholarship_count
+    );
+
+    // Everything has been renamed, so a failure to update the index is only logged.
+    if !comparison_ids.is_empty()
+        && let Err(err) = reindex_comparisons(&comparison_ids).await
+    {
+        error!("Couldn't update eligibility index for comparisons: {}", err);
+    }
+    for (student_id, changed) in moved {
+        if let Err(err) = reindex_student_fields(&student_id, changed).await {
+            error!("Couldn't update eligibility index for student {:?}: {}", student_id, err);
+        }
+    }
+
+    Ok(())
+}
+
+/// Gets the definitions of every student form, in the order that they're shown to students.
+#[server]
+pub async fn get_student_forms() -> Result<Vec<FormDefinition>, ServerFnError> {
+    load_form_definitions().await
+}
+
+/// Gets the definition of a single student form using its ID, such as `demographics`.
+#[server]
+pub async fn get_student_form(id: String) -> Result<FormDefinition, ServerFnError> {
+    load_form_definition(&id).await
+}
+
+/// Gets the definition of the student form shown at a path, such as `additional/athletics`. The
+/// student pages are routed using this, so forms that are created or moved in the form builder
+/// get a page without any new routes.
+#[server]
+pub async fn get_student_form_by_path(path: String) -> Result<FormDefinition, ServerFnError> {
+    load_form_definitions()
+        .await?
+        .into_iter()
+        .find(|definition| definition.path == path)
+        .ok_or_else(|| {
+            let msg = format!("There is no form at {:?}.", path);
+            error!("{}", msg);
+            ServerFnError::new(msg)
+        })
+}
+
+/// Gets an administrator's unpublished changes to a form, if there are any.
+#[server]
+pub async fn get_form_draft(
+    access_token: String,
+    form_id: String,
+) -> Result<Option<FormDefinition>, ServerFnError> {
+    let _ = validate_and_get_token_info(access_token, "us-east-1_rvCU4Xy4j", "us-east-1").await?;
+
+    let client = create_dynamo_client().await;
+
+    Ok(get_form_record(&client, &form_id, "DRAFT")
+        .await?
+        .map(|record| record.definition))
+}
+
+/// Saves an administrator's unpublished changes to a form. Drafts aren't shown to students, so
+/// they don't need to be complete.
+#[server(SaveFormDraft, input = Json)]
+pub async fn save_form_draft(
+    access_token: String,
+    definition: FormDefinition,
+) -> Result<(), ServerFnError> {
+    let _ = validate_and_get_token_info(access_token, "us-east-1_rvCU4Xy4j", "us-east-1").await?;
+
+    if definition.id.trim().is_empty() {
+        return Err(ServerFnError::new("The form needs an ID."));
+    }
+
+    let client = create_dynamo_client().await;
+
+    debug_log!("Saving draft of form {:?}", definition.id);
+
+    client
+        .put_item()
+        .table_name(FORMS_TABLE)
+        .set_item(Some(serde_dynamo::to_item(FormRecord::new(
+            "DRAFT",
+            definition,
+            Vec::new(),
+        ))?))
+        .send()
+        .await
+        .map_err(dynamo_error)?;
+
+    Ok(())
+}
+
+/// Publishes a new version of a form, which students will see from then on, and discards its
+/// draft. Every field that the new version removes must be covered by one of the `migrations`.
+/// Once the new version is published, renamed fields have their stored values moved, along with
+/// the comparisons and scoring criteria that use them. Returns the published definition.
+#[server(PublishForm, input = Json)]
+pub async fn publish_form(
+    access_token: String,
+    definition: FormDefinition,
+    migrations: Vec<FieldMigration>,
+) -> Result<FormDefinition, ServerFnError> {
+    let _ = validate_and_get_token_info(access_token, "us-east-1_rvCU4Xy4j", "us-east-1").await?;
+
+    let client = create_dynamo_client().await;
+
+    // New forms are published over an empty definition, so that nothing needs a migration. Errors
+    // are returned rather than treated as a new form, which would skip the migration checks.
+    let previous = match get_form_record(&client, &definition.id, "PUBLISHED").await? {
+        Some(record) => record.definition,
+        None => get_form_definition(&definition.id)
+            .unwrap_or_else(|| FormDefinition::new(definition.id.clone(), "", "")),
+    };
+    let next = definition
+        .publish_over(&previous, &migrations)
+        .map_err(|err| {
+            let msg = format!("Couldn't publish form: {}", err);
+            error!("{}", msg);
+            ServerFnError::new(msg)
+        })?;
+
+    // Each path leads to a single form's page.
+    if let Some(other) = load_form_definitions().await?.into_iter().find(|other| {
+        other.path == next.path && !other.id.eq_ignore_ascii_case(&next.id)
+    }) {
+        let msg = format!(
+            "Couldn't publish form: {:?} is already the path of {:?}.",
+            next.path, other.id
+        );
+        error!("{}", msg);
+        return Err(ServerFnError::new(msg));
+    }
+
+    log!("Publishing version {} of form {:?}", next.version, next.id);
+
+    let mut requests = Vec::new();
+    for record in [
+        FormRecord::new(version_key(next.version), next.clone(), migrations.clone()),
+        FormRecord::new("PUBLISHED", next.clone(), Vec::new()),
+    ] {
+        let put_request = PutRequest::builder()
+            .set_item(Some(serde_dynamo::to_item(record)?))
+            .build()?;
+        requests.push(WriteRequest::builder().put_request(put_request).build());
+    }
+    let delete_request = DeleteRequest::builder()
+        .key("HK", AttributeValue::S(form_key(&next.id)))
+        .key("SK", AttributeValue::S("DRAFT".to_string()))
+        .build()?;
+    requests.push(
+        WriteRequest::builder()
+            .delete_request(delete_request)
+            .build(),
+    );
+
+    batch_write_requests(&client, FORMS_TABLE, requests).await?;
+
+    // Students' values are only moved once the new version is published, so that nothing is
+    // saved under the old paths afterwards.
+    apply_migrations(&client, &next.id, &migrations)
+        .await
+        .map_err(|err| {
+            let msg = format!(
+                "Version {} was published, but its migrations couldn't finish: {}. Retry the \
+                migrations to finish them.",
+                next.version, err
+            );
+            error!("{}", msg);
+            ServerFnError::new(msg)
+        })?;
+
+    Ok(next)
+}
+
+/// Applies the migrations of a form's published version again, such as after a publish whose
+/// migrations couldn't finish. Returns the published definition.
+#[server(RetryFormMigrations)]
+pub async fn retry_form_migrations(
+    access_token: String,
+    form_id: String,
+) -> Result<FormDefinition, ServerFnError> {
+    let _ = validate_and_get_token_info(access_token, "us-east-1_rvCU4Xy4j", "us-east-1").await?;
+
+    let client = create_dynamo_client().await;
+
+    let not_published = || {
+        let msg = format!("{:?} doesn't have a published version to migrate.", form_id);
+        error!("{}", msg);
+        ServerFnError::new(msg)
+    };
+    let published = get_form_record(&client, &form_id, "PUBLISHED")
+        .await?
+        .ok_or_else(not_published)?
+        .definition;
+    let record = get_form_record(&client, &form_id, &version_key(published.version))
+        .await?
+        .ok_or_else(not_published)?;
+
+    apply_migrations(&client, &published.id, &record.migrations).await?;
+
+    Ok(published)
+}
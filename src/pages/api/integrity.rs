@@ -0,0 +1,347 @@
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "ssr")]
+mod imports {
+    pub use super::super::{
+        MAIN_TABLE_NAME, PROVIDER_CONTACT_TABLE, S3_BUCKET_NAME, SCHOLARSHIPS_TABLE,
+    };
+    pub use super::super::{
+        create_scholarship_info, get_all_scholarship_info, get_comparison_info,
+        get_provider_contact, get_scholarship_info,
+    };
+    pub use crate::common::{get_requirements, remove_requirement};
+    pub use crate::pages::api::tokens::validate_and_get_token_info;
+    pub use crate::pages::api::{reindex_student_file, remove_scholarship_from_index};
+    pub use crate::utils::server::{create_aws_config, create_dynamo_client};
+    pub use aws_sdk_dynamodb::error::ProvideErrorMetadata;
+    pub use aws_sdk_dynamodb::types::AttributeValue;
+    pub use leptos::logging::{error, log};
+    pub use std::collections::HashSet;
+}
+
+#[cfg(feature = "ssr")]
+use imports::*;
+
+/// # Integrity Issue
+///
+/// A reference between tables that no longer resolves. Issues are found using
+/// [`get_integrity_report`], and each one can be fixed using [`repair_integrity_issue`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum IntegrityIssue {
+    /// A scholarship requirement refers to a comparison that doesn't exist. These requirements
+    /// are ignored when checking eligibility. Repaired by removing the requirement.
+    DanglingRequirement {
+        scholarship_id: String,
+        scholarship_name: String,
+        category: String,
+        comparison_id: String,
+    },
+    /// A scholarship's provider has no contact information, so the scholarship can't be exported
+    /// or reached. Repaired by deleting the scholarship, which must be confirmed first, since the
+    /// provider may still add their contact information.
+    MissingProviderContact {
+        scholarship_id: String,
+        scholarship_name: String,
+        provider_id: String,
+    },
+    /// A student's file entry refers to an object that isn't in S3. Repaired by deleting the file
+    /// entry, so that the student can upload the file again.
+    MissingFileObject {
+        student_id: String,
+        sort_key: String,
+        file_key: String,
+    },
+}
+
+impl IntegrityIssue {
+    /// Describes the issue for display in the integrity report.
+    pub fn describe(&self) -> String {
+        match self {
+            IntegrityIssue::DanglingRequirement {
+                scholarship_name,
+                category,
+                comparison_id,
+                ..
+            } => format!(
+                "{scholarship_name:?} requires comparison {comparison_id:?} in category {category:?}, which doesn't exist."
+            ),
+            IntegrityIssue::MissingProviderContact {
+                scholarship_name,
+                provider_id,
+                ..
+            } => format!(
+                "{scholarship_name:?} belongs to provider {provider_id:?}, who has no contact information."
+            ),
+            IntegrityIssue::MissingFileObject {
+                student_id,
+                file_key,
+                ..
+            } => format!(
+                "Student {student_id:?} has a file entry for {file_key:?}, which isn't in storage."
+            ),
+        }
+    }
+
+    /// The warning to confirm before repairing the issue, if the repair deletes something that
+    /// can't be recovered.
+    pub fn confirmation(&self) -> Option<String> {
+        match self {
+            IntegrityIssue::MissingProviderContact {
+                scholarship_name, ..
+            } => Some(format!(
+                "This permanently deletes {scholarship_name:?}. Consider contacting the provider to add their contact information instead."
+            )),
+            _ => None,
+        }
+    }
+
+    /// The label of the button that repairs the issue.
+    pub fn repair_label(&self) -> &'static str {
+        match self {
+            IntegrityIssue::DanglingRequirement { .. } => "Remove Requirement",
+            IntegrityIssue::MissingProviderContact { .. } => "Delete Scholarship",
+            IntegrityIssue::MissingFileObject { .. } => "Delete File Entry",
+        }
+    }
+}
+
+#[cfg(feature = "ssr")]
+fn dynamo_error<E: ProvideErrorMetadata>(err: E) -> ServerFnError {
+    let msg = err.message().unwrap_or("Unknown error occurred").to_string();
+    error!("{}", msg);
+    ServerFnError::new(msg)
+}
+
+/// Gets the keys of every object in the S3 bucket.
+#[cfg(feature = "ssr")]
+async fn get_object_keys() -> Result<HashSet<String>, ServerFnError> {
+    use aws_sdk_s3::error::ProvideErrorMetadata as _;
+
+    let s3_client = aws_sdk_s3::Client::new(&create_aws_config().await);
+
+    s3_client
+        .list_objects_v2()
+        .bucket(S3_BUCKET_NAME)
+        .into_paginator()
+        .send()
+        .collect::<Result<Vec<_>, _>>()
+        .await
+        .map(|pages| {
+            pages
+                .into_iter()
+                .flat_map(|page| page.contents.unwrap_or_default())
+                .filter_map(|object| object.key)
+                .collect()
+        })
+        .map_err(|err| {
+            let msg = format!(
+                "Couldn't list files in S3: {}",
+                err.message().unwrap_or("Unknown error occurred")
+            );
+            error!("{}", msg);
+            ServerFnError::new(msg)
+        })
+}
+
+#[cfg(feature = "ssr")]
+fn get_string(item: &std::collections::HashMap<String, AttributeValue>, key: &str) -> String {
+    item.get(key)
+        .and_then(|v| v.as_s().ok())
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// Finds every reference between tables that no longer resolves. Only available to
+/// administrators.
+#[server]
+pub async fn get_integrity_report(access_token: String) -> Result<Vec<IntegrityIssue>, ServerFnError> {
+    let _ = validate_and_get_token_info(access_token, "us-east-1_rvCU4Xy4j", "us-east-1").await?;
+
+    let client = create_dynamo_client().await;
+
+    let contacts = client
+        .scan()
+        .table_name(PROVIDER_CONTACT_TABLE)
+        .projection_expression("subject")
+        .into_paginator()
+        .items()
+        .send()
+        .collect::<Result<Vec<_>, _>>();
+    let files = client
+        .scan()
+        .table_name(MAIN_TABLE_NAME)
+        .filter_expression("begins_with(SK, :prefix)")
+        .expression_attribute_values(":prefix", AttributeValue::S("FILE#".to_string()))
+        .into_paginator()
+        .items()
+        .send()
+        .collect::<Result<Vec<_>, _>>();
+
+    let (scholarships, comparisons, contacts, files, object_keys) = tokio::join!(
+        get_all_scholarship_info(),
+        get_comparison_info(),
+        contacts,
+        files,
+        get_object_keys()
+    );
+    let (scholarships, comparisons, object_keys) = (scholarships?, comparisons?, object_keys?);
+    let contacts = contacts
+        .map_err(dynamo_error)?
+        .iter()
+        .map(|item| get_string(item, "subject"))
+        .collect::<HashSet<String>>();
+    let files = files.map_err(dynamo_error)?;
+
+    let comparison_ids = comparisons
+        .into_iter()
+        .map(|comparison| comparison.id)
+        .collect::<HashSet<String>>();
+
+    let mut issues = Vec::new();
+
+    for scholarship in &scholarships {
+        let get_data = |key: &str| {
+            scholarship
+                .data
+                .get(key)
+                .and_then(|v| v.as_string().ok().flatten())
+                .unwrap_or_default()
+        };
+        let scholarship_name = get_data("name");
+
+        let mut requirements = get_requirements(scholarship).into_iter().collect::<Vec<_>>();
+        requirements.sort_by(|a, b| a.0.cmp(&b.0));
+        for (category, requirement) in requirements {
+            for comparison_id in requirement.ids {
+                if !comparison_ids.contains(&comparison_id) {
+                    issues.push(IntegrityIssue::DanglingRequirement {
+                        scholarship_id: scholarship.subject.clone(),
+                        scholarship_name: scholarship_name.clone(),
+                        category: category.clone(),
+                        comparison_id,
+                    });
+                }
+            }
+        }
+
+        let provider_id = get_data("provider_id");
+        if !contacts.contains(&provider_id) {
+            issues.push(IntegrityIssue::MissingProviderContact {
+                scholarship_id: scholarship.subject.clone(),
+                scholarship_name,
+                provider_id,
+            });
+        }
+    }
+
+    for file in files {
+        let file_key = get_string(&file, "file_key");
+        if !object_keys.contains(&file_key) {
+            issues.push(IntegrityIssue::MissingFileObject {
+                student_id: get_string(&file, "HK").replace("STUDENT#", ""),
+                sort_key: get_string(&file, "SK"),
+                file_key,
+            });
+        }
+    }
+
+    log!("Integrity report found {} issues", issues.len());
+
+    Ok(issues)
+}
+
+/// Repairs an issue from the integrity report. The issue is checked again before it's repaired,
+/// so that a stale report can't delete anything that has since been fixed. Only available to
+/// administrators.
+#[server]
+pub async fn repair_integrity_issue(
+    access_token: String,
+    issue: IntegrityIssue,
+) -> Result<(), ServerFnError> {
+    let _ = validate_and_get_token_info(access_token, "us-east-1_rvCU4Xy4j", "us-east-1").await?;
+
+    match issue {
+        IntegrityIssue::DanglingRequirement {
+            scholarship_id,
+            comparison_id,
+            ..
+        } => {
+            if get_comparison_info()
+                .await?
+                .iter()
+                .any(|comparison| comparison.id == comparison_id)
+            {
+                return Err(ServerFnError::new("This comparison exists again."));
+            }
+
+            let mut scholarship = get_scholarship_info(scholarship_id).await?;
+            if remove_requirement(&mut scholarship, &comparison_id) {
+                create_scholarship_info(scholarship).await?;
+            }
+        }
+        IntegrityIssue::MissingProviderContact {
+            scholarship_id,
+            provider_id,
+            ..
+        } => {
+            if !get_provider_contact(provider_id).await?.is_empty() {
+                return Err(ServerFnError::new(
+                    "This provider has added their contact information.",
+                ));
+            }
+
+            create_dynamo_client()
+                .await
+                .delete_item()
+                .table_name(SCHOLARSHIPS_TABLE)
+                .key("subject", AttributeValue::S(scholarship_id.clone()))
+                .send()
+                .await
+                .map_err(dynamo_error)?;
+
+            if let Err(err) = remove_scholarship_from_index(&scholarship_id).await {
+                error!("Couldn't remove scholarship {:?} from eligibility index: {}", scholarship_id, err);
+            }
+        }
+        IntegrityIssue::MissingFileObject {
+            student_id,
+            sort_key,
+            file_key,
+        } => {
+            let s3_client = aws_sdk_s3::Client::new(&create_aws_config().await);
+            if s3_client
+                .head_object()
+                .bucket(S3_BUCKET_NAME)
+                .key(&file_key)
+                .send()
+                .await
+                .is_ok()
+            {
+                return Err(ServerFnError::new("This file exists in storage again."));
+            }
+
+            create_dynamo_client()
+                .await
+                .delete_item()
+                .table_name(MAIN_TABLE_NAME)
+                .key("HK", AttributeValue::S(format!("STUDENT#{student_id}")))
+                .key("SK", AttributeValue::S(sort_key.clone()))
+                .send()
+                .await
+                .map_err(dynamo_error)?;
+
+            // File entries are stored as `FILE#form#input#file_name`.
+            let mut parts = sort_key.splitn(4, '#').skip(1);
+            let (form_id, input_name) = (
+                parts.next().unwrap_or_default(),
+                parts.next().unwrap_or_default(),
+            );
+            if let Err(err) = reindex_student_file(&student_id, form_id, input_name).await {
+                error!("Couldn't update eligibility index for student {:?}: {}", student_id, err);
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -1,8 +1,12 @@
 mod admin;
 mod comparisons;
+mod completion;
 mod dates;
+mod eligibility;
 pub mod exports;
 pub mod files;
+mod forms;
+mod integrity;
 mod providers;
 mod scholarships;
 pub mod students;
@@ -10,7 +14,11 @@ pub(crate) mod tokens;
 
 pub use admin::*;
 pub use comparisons::*;
+pub use completion::*;
 pub use dates::*;
+pub use eligibility::*;
+pub use forms::*;
+pub use integrity::*;
 pub use providers::*;
 pub use scholarships::*;
 
@@ -26,6 +34,15 @@ pub static SCHOLARSHIPS_TABLE: &str = "leptos-scholarships";
 #[cfg(feature = "ssr")]
 pub static PROVIDER_CONTACT_TABLE: &str = "leptos-provider-contacts";
 
+/// Stores the eligibility of each student for each scholarship. Keyed by `scholarship_id` and
+/// `student_id`.
+#[cfg(feature = "ssr")]
+pub static ELIGIBILITY_TABLE: &str = "leptos-eligibility";
+
+/// Stores the published versions and drafts of student forms. Keyed by `HK` and `SK`.
+#[cfg(feature = "ssr")]
+pub static FORMS_TABLE: &str = "leptos-forms";
+
 #[cfg(feature = "ssr")]
 pub static MAIN_TABLE_NAME: &str = "scholarships-main";
 
@@ -2,14 +2,29 @@
 use leptos::logging::{debug_log, error};
 use leptos::prelude::*;
 use leptos::server_fn::codec::Json;
+use macros::ValueMap;
 use std::collections::HashMap;
 
+#[cfg(feature = "ssr")]
+use crate::common::ValueMap;
 #[cfg(feature = "ssr")]
 use crate::utils::server::{create_dynamo_client, into_attr_map};
 
 #[cfg(feature = "ssr")]
 static PROVIDER_CONTACT_TABLE: &str = "leptos-provider-contacts";
 
+/// # Provider Contact
+///
+/// The contact information that a provider enters on the contact page. Every field is required.
+#[derive(ValueMap, Debug, Clone, Default, PartialEq)]
+pub struct ProviderContact {
+    pub first_name: String,
+    pub last_name: String,
+    pub contact_email: String,
+    pub phone_number: String,
+    pub address: String,
+}
+
 #[server]
 pub async fn get_provider_contact(id: String) -> Result<HashMap<String, ValueType>, ServerFnError> {
     use aws_sdk_dynamodb::error::ProvideErrorMetadata;
@@ -57,9 +72,15 @@ pub async fn put_provider_contact(
 ) -> Result<(), ServerFnError> {
     use aws_sdk_dynamodb::error::ProvideErrorMetadata;
 
+    let contact = ProviderContact::from_map(&contact_info).map_err(|err| {
+        let msg = format!("Invalid contact information: {err}");
+        error!("{}", msg);
+        ServerFnError::new(msg)
+    })?;
+
     let client = create_dynamo_client().await;
 
-    let mut item = into_attr_map(contact_info);
+    let mut item = into_attr_map(contact.to_map());
     item.insert(
         "subject".to_owned(),
         aws_sdk_dynamodb::types::AttributeValue::S(id),
@@ -1,9 +1,16 @@
 #[cfg(feature = "ssr")]
 mod imports {
     pub use super::super::SCHOLARSHIPS_TABLE;
-    pub use crate::common::ValueType;
+    pub use crate::pages::api::{
+        check_form_window, reindex_scholarship, remove_scholarship_from_index,
+    };
+    pub use crate::common::{DatePurpose, ValueEncoding, ValuePatch, ValueType};
     pub use crate::utils::server::create_dynamo_client;
-    pub use aws_sdk_dynamodb::{error::ProvideErrorMetadata, types::AttributeValue};
+    pub use aws_sdk_dynamodb::{
+        error::ProvideErrorMetadata,
+        types::{AttributeValue, ReturnValue},
+    };
+    pub use std::collections::HashMap;
     pub use leptos::logging::{debug_log, error, log};
     pub use uuid::Uuid;
 }
@@ -48,7 +55,9 @@ pub async fn get_scholarship_info(id: String) -> Result<ExpandableInfo, ServerFn
     }
 }
 
-#[server(CreateScholarshipInfo, input = Json)]
+/// Saves a scholarship and updates its eligibility index. This has no permission checks of its
+/// own, so it's only for server functions that have already made them.
+#[cfg(feature = "ssr")]
 pub async fn create_scholarship_info(info: ExpandableInfo) -> Result<(), ServerFnError> {
     use aws_sdk_dynamodb::error::ProvideErrorMetadata;
     use imports::*;
@@ -67,7 +76,85 @@ pub async fn create_scholarship_info(info: ExpandableInfo) -> Result<(), ServerF
         .send()
         .await
     {
-        Ok(_) => Ok(()),
+        Ok(_) => {
+            if let Err(err) = reindex_scholarship(&info).await {
+                error!("Couldn't update eligibility index for scholarship {:?}: {}", info.subject, err);
+            }
+            Ok(())
+        }
+        Err(err) => {
+            let msg = err.message().unwrap_or("An unknown error occurred");
+            error!("{}", msg);
+            Err(ServerFnError::new(msg))
+        }
+    }
+}
+
+/// Changes part of a provider's scholarship. Each path in `set` is written with its value and each
+/// path in `remove` is deleted, while every other field is left alone. Like
+/// [`delete_provider_scholarship`], this only succeeds if the scholarship belongs to the provider.
+#[server(PatchScholarshipInfo, input = Json)]
+pub async fn patch_scholarship_info(
+    provider_id: String,
+    scholarship_id: String,
+    set: std::collections::HashMap<String, crate::common::ValueType>,
+    remove: Vec<String>,
+) -> Result<(), ServerFnError> {
+    use imports::*;
+
+    check_form_window(DatePurpose::ProviderForms, None).await?;
+
+    let patch = ValuePatch { set, remove };
+    if patch.is_empty() {
+        return Ok(());
+    }
+
+    // Scholarships are stored using serde, so nested maps and lists are tagged with their type.
+    let update = patch
+        .to_update_expression(ValueEncoding::Tagged, &["subject", "provider_id"])
+        .map_err(|err| {
+            let msg = format!("Couldn't update scholarship: {}", err);
+            error!("{}", msg);
+            ServerFnError::new(msg)
+        })?;
+
+    let mut values = update
+        .values
+        .into_iter()
+        .map(|(placeholder, value)| Ok((placeholder, serde_dynamo::to_attribute_value(value)?)))
+        .collect::<Result<HashMap<String, AttributeValue>, serde_dynamo::Error>>()?;
+    values.insert(
+        ":provider_id".to_string(),
+        serde_dynamo::to_attribute_value(ValueType::String(Some(provider_id)))?,
+    );
+
+    let client = create_dynamo_client().await;
+
+    log!(
+        "Updating scholarship with ID {:?} using expression {:?}",
+        scholarship_id,
+        update.expression
+    );
+
+    match client
+        .update_item()
+        .table_name(SCHOLARSHIPS_TABLE)
+        .key("subject", AttributeValue::S(scholarship_id.clone()))
+        .update_expression(update.expression)
+        .set_expression_attribute_names(Some(update.names))
+        .set_expression_attribute_values(Some(values))
+        .condition_expression("provider_id = :provider_id")
+        .return_values(ReturnValue::AllNew)
+        .send()
+        .await
+    {
+        Ok(output) => {
+            let info: ExpandableInfo = serde_dynamo::from_item(output.attributes.unwrap_or_default())?;
+            if let Err(err) = reindex_scholarship(&info).await {
+                error!("Couldn't update eligibility index for scholarship {:?}: {}", scholarship_id, err);
+            }
+            Ok(())
+        }
         Err(err) => {
             let msg = err.message().unwrap_or("An unknown error occurred");
             error!("{}", msg);
@@ -143,6 +230,8 @@ pub async fn get_provider_scholarships(
 pub async fn register_scholarship(provider_id: String) -> Result<String, ServerFnError> {
     use imports::*;
 
+    check_form_window(DatePurpose::ProviderForms, None).await?;
+
     let client = create_dynamo_client().await;
 
     debug_log!(
@@ -200,6 +289,8 @@ pub async fn delete_provider_scholarship(
 ) -> Result<(), ServerFnError> {
     use imports::*;
 
+    check_form_window(DatePurpose::ProviderForms, None).await?;
+
     let client = create_dynamo_client().await;
 
     debug_log!(
@@ -213,7 +304,7 @@ pub async fn delete_provider_scholarship(
     match client
         .delete_item()
         .table_name(SCHOLARSHIPS_TABLE)
-        .key("subject", AttributeValue::S(scholarship_id))
+        .key("subject", AttributeValue::S(scholarship_id.clone()))
         .expression_attribute_values(
             ":provider_id",
             serde_dynamo::to_attribute_value(ValueType::String(Some(provider_id)))?,
@@ -222,7 +313,12 @@ pub async fn delete_provider_scholarship(
         .send()
         .await
     {
-        Ok(_) => Ok(()),
+        Ok(_) => {
+            if let Err(err) = remove_scholarship_from_index(&scholarship_id).await {
+                error!("Couldn't remove scholarship {:?} from eligibility index: {}", scholarship_id, err);
+            }
+            Ok(())
+        }
         Err(err) => {
             let msg = err.message().unwrap_or("Unknown error occurred");
             error!("{}", msg);
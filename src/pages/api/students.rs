@@ -1,5 +1,7 @@
 use leptos::prelude::*;
 use leptos::server_fn::codec::Json;
+use macros::ValueMap;
+use crate::common::SubmissionResult;
 use std::collections::HashMap;
 use std::io::Write;
 use std::process::Stdio;
@@ -7,30 +9,75 @@ use std::process::Stdio;
 #[cfg(feature = "ssr")]
 mod imports {
     pub use super::super::MAIN_TABLE_NAME;
-    pub use crate::common::ValueType;
+    pub use crate::common::{
+        DatePurpose, ValueEncoding, ValueMap, ValuePatch, ValuePath, ValueType,
+    };
+    pub use crate::pages::api::check_form_window;
+    pub use crate::pages::api::load_form_definition;
     pub use crate::pages::api::tokens::validate_and_get_token_info;
     pub use crate::utils::server::*;
     pub use aws_sdk_dynamodb::error::ProvideErrorMetadata;
-    pub use aws_sdk_dynamodb::types::AttributeValue;
+    pub use crate::pages::api::reindex_student_fields;
+    pub use aws_sdk_dynamodb::types::{AttributeValue, ReturnValue};
     pub use leptos::logging::{debug_log, error};
     pub use leptos::serde_json;
-    pub use std::collections::HashMap;
+    pub use std::collections::{HashMap, HashSet};
     pub use std::process::Command;
     pub use zip::write::SimpleFileOptions;
 }
 
 static PDF_TEMPLATE: &str = include_str!("../../../pdf_template.typ");
 
+/// # Student Demographics
+///
+/// The student's demographics form, which is stored under `DEMOGRAPHICS`. A student's forms only
+/// count as complete once their name has been entered here. The other fields are optional so
+/// that older entries can still be read.
+#[derive(ValueMap, Debug, Clone, PartialEq)]
+pub struct StudentDemographics {
+    pub first_name: String,
+    pub last_name: String,
+    #[value(rename = "dob")]
+    pub date_of_birth: Option<String>,
+    pub gender: Option<String>,
+    pub id_number: Option<String>,
+    pub email: Option<String>,
+    pub phone_number: Option<String>,
+    pub street_address: Option<String>,
+    pub town: Option<String>,
+}
+
+/// Replaces one of a student's forms. The student is taken from the access token, and the data is
+/// validated against the form's definition first. Any problems are returned as field errors
+/// without saving anything.
 #[server(input = Json)]
 pub async fn put_student_data(
-    subject: String,
+    access_token: String,
     data_type: String,
     data_map: HashMap<String, crate::common::ValueType>,
-) -> Result<(), ServerFnError> {
+) -> Result<SubmissionResult, ServerFnError> {
     use imports::*;
 
+    let subject = validate_and_get_token_info(access_token, "us-east-1_Lfjuy5zaM", "us-east-1")
+        .await?
+        .subject;
+    check_form_window(DatePurpose::StudentForms, Some(&subject)).await?;
+    if let Err(errors) = load_form_definition(&data_type).await?.validate(&data_map) {
+        debug_log!("Rejected {:?} submission: {:?}", data_type, errors);
+        return Ok(Err(errors));
+    }
+
     let client = create_dynamo_client().await;
 
+    let new_claim = match claim_demographics_id(&client, &subject, &data_type, &data_map).await? {
+        Ok(new_claim) => new_claim,
+        Err(errors) => {
+            debug_log!("Rejected {:?} submission: {:?}", data_type, errors);
+            return Ok(Err(errors));
+        }
+    };
+
+    let id_number = claimable_id_number(&data_map);
     let mut data_map_attr = into_attr_map(data_map);
     data_map_attr.insert(
         "HK".into(),
@@ -40,21 +87,150 @@ pub async fn put_student_data(
 
     debug_log!("Inserting this item: {:?}", data_map_attr);
 
-    client
+    let result = client
         .put_item()
         .table_name(MAIN_TABLE_NAME)
-        .set_item(Some(data_map_attr))
+        .set_item(Some(data_map_attr.clone()))
+        .return_values(ReturnValue::AllOld)
         .send()
-        .await
-        .map(|_| ())
-        .map_err(|err| {
+        .await;
+    let previous_item = match result {
+        Ok(output) => output.attributes.unwrap_or_default(),
+        Err(err) => {
+            release_unsaved_claim(&client, &subject, new_claim).await;
             let msg = format!(
                 "Couldn't put item into Dynamo: {}",
                 err.message().unwrap_or("Unknown error occurred")
             );
             error!("{}", msg);
+            return Err(ServerFnError::new(msg));
+        }
+    };
+
+    // A changed student ID number frees the old one for other students.
+    let previous_id = claimable_id_number(
+        &previous_item.iter().map(|(k, v)| (k.clone(), ValueType::from(v))).collect(),
+    );
+    if let Some(previous_id) = previous_id
+        && data_type.eq_ignore_ascii_case("demographics")
+        && Some(&previous_id) != id_number.as_ref()
+        && let Err(err) = release_student_id(&client, &subject, &previous_id).await
+    {
+        error!("Couldn't release student ID for student {:?}: {}", subject, err);
+    }
+
+    // Update the eligibility index using only the fields that actually changed.
+    let changed_fields = data_map_attr
+        .keys()
+        .chain(previous_item.keys())
+        .filter(|key| data_map_attr.get(*key) != previous_item.get(*key))
+        .cloned()
+        .collect::<HashSet<String>>();
+
+    if let Err(err) = reindex_student_fields(&subject, changed_fields).await {
+        error!("Couldn't update eligibility index for student {:?}: {}", subject, err);
+    }
+
+    Ok(Ok(()))
+}
+
+/// Changes part of one of a student's forms. Each path in `set` is written with its value and each
+/// path in `remove` is deleted, while every other field is left alone. This lets a form save only
+/// the fields that were edited, so that edits to different fields don't overwrite each other.
+///
+/// The patched form is validated against its definition before anything is saved. Fields that are
+/// no longer part of the definition are only checked if the patch changes them. The subject is
+/// taken from the student's access token.
+#[server(input = Json)]
+pub async fn patch_student_data(
+    access_token: String,
+    data_type: String,
+    set: HashMap<String, crate::common::ValueType>,
+    remove: Vec<String>,
+) -> Result<SubmissionResult, ServerFnError> {
+    use imports::*;
+
+    let subject = validate_and_get_token_info(access_token, "us-east-1_Lfjuy5zaM", "us-east-1")
+        .await?
+        .subject;
+
+    check_form_window(DatePurpose::StudentForms, Some(&subject)).await?;
+    let patch = ValuePatch { set, remove };
+    if patch.is_empty() {
+        return Ok(Ok(()));
+    }
+
+    let definition = load_form_definition(&data_type).await?;
+    let changed_fields = patch.fields();
+    let mut patched = get_student_data(subject.clone(), data_type.clone()).await?;
+    let previous_id = claimable_id_number(&patched);
+    patched.retain(|key, _| definition.field(key).is_some() || changed_fields.contains(key));
+    patch.apply(&mut patched).map_err(|err| {
+        let msg = format!("Couldn't update student data: {}", err);
+        error!("{}", msg);
+        ServerFnError::new(msg)
+    })?;
+
+    if let Err(errors) = definition.validate(&patched) {
+        debug_log!("Rejected {:?} patch: {:?}", data_type, errors);
+        return Ok(Err(errors));
+    }
+
+    let update = patch
+        .to_update_expression(ValueEncoding::Attributes, &["HK", "SK"])
+        .map_err(|err| {
+            let msg = format!("Couldn't update student data: {}", err);
+            error!("{}", msg);
             ServerFnError::new(msg)
-        })
+        })?;
+
+    let client = create_dynamo_client().await;
+
+    let new_claim = match claim_demographics_id(&client, &subject, &data_type, &patched).await? {
+        Ok(new_claim) => new_claim,
+        Err(errors) => {
+            debug_log!("Rejected {:?} patch: {:?}", data_type, errors);
+            return Ok(Err(errors));
+        }
+    };
+
+    debug_log!("Updating student data with this expression: {:?}", update);
+
+    let result = client
+        .update_item()
+        .table_name(MAIN_TABLE_NAME)
+        .key("HK", AttributeValue::S(format!("STUDENT#{}", subject)))
+        .key("SK", AttributeValue::S(data_type.to_uppercase()))
+        .update_expression(update.expression)
+        .set_expression_attribute_names(Some(update.names))
+        .set_expression_attribute_values(
+            (!update.values.is_empty()).then(|| into_attr_map(update.values)),
+        )
+        .send()
+        .await;
+    if let Err(err) = result {
+        release_unsaved_claim(&client, &subject, new_claim).await;
+        let msg = format!(
+            "Couldn't update item in Dynamo: {}",
+            err.message().unwrap_or("Unknown error occurred")
+        );
+        error!("{}", msg);
+        return Err(ServerFnError::new(msg));
+    }
+
+    if let Some(previous_id) = previous_id
+        && data_type.eq_ignore_ascii_case("demographics")
+        && Some(&previous_id) != claimable_id_number(&patched).as_ref()
+        && let Err(err) = release_student_id(&client, &subject, &previous_id).await
+    {
+        error!("Couldn't release student ID for student {:?}: {}", subject, err);
+    }
+
+    if let Err(err) = reindex_student_fields(&subject, changed_fields).await {
+        error!("Couldn't update eligibility index for student {:?}: {}", subject, err);
+    }
+
+    Ok(Ok(()))
 }
 
 #[server]
@@ -98,6 +274,371 @@ pub async fn get_student_data(
         })
 }
 
+/// The message shown when a student ID number already belongs to another student.
+#[cfg(feature = "ssr")]
+const DUPLICATE_ID_MESSAGE: &str = "This student ID number is already used by another student. \
+    Contact your school counselor if this is your number.";
+
+/// Reads the student ID number from a demographics form. Numbers that can't be parsed are left to
+/// the form's own validation, so they're never claimed.
+#[cfg(feature = "ssr")]
+fn claimable_id_number(data: &HashMap<String, crate::common::ValueType>) -> Option<String> {
+    data.get("id_number")
+        .and_then(|value| value.to_string().trim().parse::<u64>().ok())
+        .map(|number| number.to_string())
+}
+
+/// The result of claiming a student ID number.
+#[cfg(feature = "ssr")]
+#[derive(Debug, PartialEq)]
+enum IdClaim {
+    /// The number wasn't claimed before, and now belongs to the student.
+    New,
+    /// The number already belonged to the student.
+    Kept,
+    /// The number belongs to another student.
+    Taken,
+}
+
+/// Claims a student ID number for a student, so that no other student can use it. Each number is
+/// claimed by an item under `STUDENT_ID#<number>` that names the student it belongs to.
+#[cfg(feature = "ssr")]
+async fn claim_student_id(
+    client: &aws_sdk_dynamodb::Client,
+    subject: &str,
+    id_number: &str,
+) -> Result<IdClaim, ServerFnError> {
+    use imports::*;
+
+    let result = client
+        .put_item()
+        .table_name(MAIN_TABLE_NAME)
+        .item("HK", AttributeValue::S(format!("STUDENT_ID#{}", id_number)))
+        .item("SK", AttributeValue::S("STUDENT_ID".to_string()))
+        .item("student", AttributeValue::S(subject.to_string()))
+        .condition_expression("attribute_not_exists(HK) OR student = :student")
+        .expression_attribute_values(":student", AttributeValue::S(subject.to_string()))
+        .return_values(ReturnValue::AllOld)
+        .send()
+        .await;
+
+    match result {
+        Ok(output) if output.attributes.is_some() => Ok(IdClaim::Kept),
+        Ok(_) => Ok(IdClaim::New),
+        Err(err) if err.code() == Some("ConditionalCheckFailedException") => Ok(IdClaim::Taken),
+        Err(err) => {
+            let msg = format!(
+                "Couldn't claim student ID in Dynamo: {}",
+                err.message().unwrap_or("Unknown error occurred")
+            );
+            error!("{}", msg);
+            Err(ServerFnError::new(msg))
+        }
+    }
+}
+
+/// Releases a student's claim on a student ID number they no longer use. Claims that belong to
+/// other students are left alone.
+#[cfg(feature = "ssr")]
+async fn release_student_id(
+    client: &aws_sdk_dynamodb::Client,
+    subject: &str,
+    id_number: &str,
+) -> Result<(), ServerFnError> {
+    use imports::*;
+
+    let result = client
+        .delete_item()
+        .table_name(MAIN_TABLE_NAME)
+        .key("HK", AttributeValue::S(format!("STUDENT_ID#{}", id_number)))
+        .key("SK", AttributeValue::S("STUDENT_ID".to_string()))
+        .condition_expression("student = :student")
+        .expression_attribute_values(":student", AttributeValue::S(subject.to_string()))
+        .send()
+        .await;
+
+    match result {
+        Ok(_) => Ok(()),
+        Err(err) if err.code() == Some("ConditionalCheckFailedException") => Ok(()),
+        Err(err) => {
+            let msg = format!(
+                "Couldn't release student ID in Dynamo: {}",
+                err.message().unwrap_or("Unknown error occurred")
+            );
+            error!("{}", msg);
+            Err(ServerFnError::new(msg))
+        }
+    }
+}
+
+/// Claims the student ID number in a demographics form before it's saved, returning field errors
+/// if the number belongs to another student. Other forms don't have ID numbers, so they pass.
+/// Returns the number if it was newly claimed, so that the claim can be released with
+/// [`release_unsaved_claim`] if the form can't be saved.
+#[cfg(feature = "ssr")]
+async fn claim_demographics_id(
+    client: &aws_sdk_dynamodb::Client,
+    subject: &str,
+    data_type: &str,
+    data: &HashMap<String, crate::common::ValueType>,
+) -> Result<Result<Option<String>, crate::common::FieldErrors>, ServerFnError> {
+    if !data_type.eq_ignore_ascii_case("demographics") {
+        return Ok(Ok(None));
+    }
+
+    let Some(id_number) = claimable_id_number(data) else {
+        return Ok(Ok(None));
+    };
+    match claim_student_id(client, subject, &id_number).await? {
+        IdClaim::New => Ok(Ok(Some(id_number))),
+        IdClaim::Kept => Ok(Ok(None)),
+        IdClaim::Taken => Ok(Err(HashMap::from([(
+            "id_number".to_string(),
+            DUPLICATE_ID_MESSAGE.to_string(),
+        )]))),
+    }
+}
+
+/// Releases a student ID number that was claimed for a form that couldn't be saved, so that the
+/// number isn't held by a student who never saved it. Failures are only logged, since the save's
+/// own error is the one returned.
+#[cfg(feature = "ssr")]
+async fn release_unsaved_claim(
+    client: &aws_sdk_dynamodb::Client,
+    subject: &str,
+    new_claim: Option<String>,
+) {
+    use imports::*;
+
+    if let Some(id_number) = new_claim
+        && let Err(err) = release_student_id(client, subject, &id_number).await
+    {
+        error!("Couldn't release student ID for student {:?}: {}", subject, err);
+    }
+}
+
+/// Checks that a student ID number isn't already claimed by another student. Returns a message
+/// describing the problem, or `None` if the number can be used. Saving the demographics form
+/// checks this again, so this is only to warn the student early.
+#[server]
+pub async fn check_student_id(
+    access_token: String,
+    id_number: String,
+) -> Result<Option<String>, ServerFnError> {
+    use imports::*;
+
+    let subject = validate_and_get_token_info(access_token, "us-east-1_Lfjuy5zaM", "us-east-1")
+        .await?
+        .subject;
+
+    // Numbers that can't be parsed are reported by the input itself.
+    let Ok(id_number) = id_number.trim().parse::<u64>() else {
+        return Ok(None);
+    };
+
+    let client = create_dynamo_client().await;
+
+    let claim = client
+        .get_item()
+        .table_name(MAIN_TABLE_NAME)
+        .key("HK", AttributeValue::S(format!("STUDENT_ID#{}", id_number)))
+        .key("SK", AttributeValue::S("STUDENT_ID".to_string()))
+        .send()
+        .await
+        .map_err(|err| {
+            let msg = format!(
+                "Couldn't check student ID in Dynamo: {}",
+                err.message().unwrap_or("Unknown error occurred")
+            );
+            error!("{}", msg);
+            ServerFnError::new(msg)
+        })?
+        .item;
+
+    let owner = claim
+        .as_ref()
+        .and_then(|claim| claim.get("student"))
+        .and_then(|student| student.as_s().ok());
+
+    Ok(owner
+        .is_some_and(|owner| *owner != subject)
+        .then(|| DUPLICATE_ID_MESSAGE.to_string()))
+}
+
+/// Claims the student ID number of every saved demographics form, for forms that were saved
+/// before numbers were claimed. Returns the numbers that are used by more than one student, which
+/// are left claimed by whichever student was found first. Only available to administrators.
+#[server]
+pub async fn admin_claim_student_ids(access_token: String) -> Result<Vec<String>, ServerFnError> {
+    use imports::*;
+
+    let _ = validate_and_get_token_info(access_token, "us-east-1_rvCU4Xy4j", "us-east-1").await?;
+
+    let client = create_dynamo_client().await;
+
+    let items = client
+        .scan()
+        .table_name(MAIN_TABLE_NAME)
+        .filter_expression("SK = :sk AND attribute_exists(id_number)")
+        .expression_attribute_values(":sk", AttributeValue::S("DEMOGRAPHICS".to_string()))
+        .projection_expression("HK, id_number")
+        .into_paginator()
+        .items()
+        .send()
+        .collect::<Result<Vec<_>, _>>()
+        .await
+        .map_err(|err| {
+            let msg = format!(
+                "Couldn't get student IDs from Dynamo: {}",
+                err.message().unwrap_or("Unknown error occurred")
+            );
+            error!("{}", msg);
+            ServerFnError::new(msg)
+        })?;
+
+    let mut duplicates = Vec::new();
+    for item in items {
+        let Some(subject) = item
+            .get("HK")
+            .and_then(|hk| hk.as_s().ok())
+            .and_then(|hk| hk.strip_prefix("STUDENT#"))
+        else {
+            continue;
+        };
+        let data = item
+            .iter()
+            .map(|(key, value)| (key.clone(), ValueType::from(value)))
+            .collect::<HashMap<_, _>>();
+        let Some(id_number) = claimable_id_number(&data) else {
+            continue;
+        };
+
+        if claim_student_id(&client, subject, &id_number).await? == IdClaim::Taken {
+            duplicates.push(id_number);
+        }
+    }
+
+    duplicates.sort();
+    duplicates.dedup();
+    Ok(duplicates)
+}
+
+/// Saves a draft of one of a student's forms. Drafts are stored apart from the student's data,
+/// under `DRAFT#<subject>`, so that they're never mistaken for submitted answers. The subject is
+/// taken from the student's access token.
+#[server(input = Json)]
+pub async fn save_student_draft(
+    access_token: String,
+    data_type: String,
+    draft: crate::common::FormDraft,
+) -> Result<(), ServerFnError> {
+    use imports::*;
+
+    let subject = validate_and_get_token_info(access_token, "us-east-1_Lfjuy5zaM", "us-east-1")
+        .await?
+        .subject;
+
+    let draft_json = serde_json::to_string(&draft).map_err(|err| {
+        let msg = format!("Couldn't serialize draft: {}", err);
+        error!("{}", msg);
+        ServerFnError::new(msg)
+    })?;
+
+    let client = create_dynamo_client().await;
+
+    client
+        .put_item()
+        .table_name(MAIN_TABLE_NAME)
+        .item("HK", AttributeValue::S(format!("DRAFT#{}", subject)))
+        .item("SK", AttributeValue::S(format!("DRAFT#{}", data_type.to_uppercase())))
+        .item("draft", AttributeValue::S(draft_json))
+        .item("saved_at", AttributeValue::N(draft.saved_at.to_string()))
+        .send()
+        .await
+        .map_err(|err| {
+            let msg = format!(
+                "Couldn't put draft into Dynamo: {}",
+                err.message().unwrap_or("Unknown error occurred")
+            );
+            error!("{}", msg);
+            ServerFnError::new(msg)
+        })?;
+
+    Ok(())
+}
+
+/// Gets the draft of one of a student's forms, if there is one.
+#[server]
+pub async fn get_student_draft(
+    access_token: String,
+    data_type: String,
+) -> Result<Option<crate::common::FormDraft>, ServerFnError> {
+    use imports::*;
+
+    let subject = validate_and_get_token_info(access_token, "us-east-1_Lfjuy5zaM", "us-east-1")
+        .await?
+        .subject;
+
+    let client = create_dynamo_client().await;
+
+    let item = client
+        .get_item()
+        .table_name(MAIN_TABLE_NAME)
+        .key("HK", AttributeValue::S(format!("DRAFT#{}", subject)))
+        .key("SK", AttributeValue::S(format!("DRAFT#{}", data_type.to_uppercase())))
+        .send()
+        .await
+        .map_err(|err| {
+            let msg = format!(
+                "Couldn't get draft from Dynamo: {}",
+                err.message().unwrap_or("Unknown error occurred")
+            );
+            error!("{}", msg);
+            ServerFnError::new(msg)
+        })?
+        .item;
+
+    // A draft that can't be read is treated as missing, since it can't be restored anyway.
+    Ok(item
+        .as_ref()
+        .and_then(|item| item.get("draft"))
+        .and_then(|draft| draft.as_s().ok())
+        .and_then(|draft| serde_json::from_str(draft).ok()))
+}
+
+/// Deletes the draft of one of a student's forms, such as once it has been submitted.
+#[server]
+pub async fn delete_student_draft(
+    access_token: String,
+    data_type: String,
+) -> Result<(), ServerFnError> {
+    use imports::*;
+
+    let subject = validate_and_get_token_info(access_token, "us-east-1_Lfjuy5zaM", "us-east-1")
+        .await?
+        .subject;
+
+    let client = create_dynamo_client().await;
+
+    client
+        .delete_item()
+        .table_name(MAIN_TABLE_NAME)
+        .key("HK", AttributeValue::S(format!("DRAFT#{}", subject)))
+        .key("SK", AttributeValue::S(format!("DRAFT#{}", data_type.to_uppercase())))
+        .send()
+        .await
+        .map_err(|err| {
+            let msg = format!(
+                "Couldn't delete draft from Dynamo: {}",
+                err.message().unwrap_or("Unknown error occurred")
+            );
+            error!("{}", msg);
+            ServerFnError::new(msg)
+        })?;
+
+    Ok(())
+}
+
 /// Gets and flattens all of a student's information from the database, regardless of their defined
 /// sort key.
 ///
@@ -141,21 +682,79 @@ pub async fn get_all_student_data(
         })
 }
 
+/// Copies the extracurricular answers that were saved under `DEMOGRAPHICS`, back when the
+/// extracurriculars page used the demographics data type, into `EXTRACURRICULARS`. Answers that
+/// have since been saved to `EXTRACURRICULARS` are kept, so this can safely be run more than once.
+/// Returns the number of answers that were copied. Only available to administrators.
 #[server]
-pub async fn provider_get_completed_students(
-    access_token: String,
-) -> Result<HashMap<String, HashMap<String, crate::common::ValueType>>, ServerFnError> {
+pub async fn admin_migrate_extracurriculars(access_token: String) -> Result<usize, ServerFnError> {
     use imports::*;
 
-    let claims =
-        validate_and_get_token_info(access_token, "us-east-1_Lfjuy5zaM", "us-east-1").await?;
-    if !claims.groups.contains(&"ScholarshipProviders".to_string()) {
-        return Err(ServerFnError::new(
-            "User is not in the ScholarshipProviders group",
-        ));
+    const FIELDS: [&str; 2] = ["service_hours", "extracurricular"];
+
+    let _ = validate_and_get_token_info(access_token, "us-east-1_rvCU4Xy4j", "us-east-1").await?;
+
+    let dynamo_error = |err: &dyn ProvideErrorMetadata| {
+        let msg = format!(
+            "Couldn't migrate extracurriculars in Dynamo: {}",
+            err.message().unwrap_or("Unknown error occurred")
+        );
+        error!("{}", msg);
+        ServerFnError::new(msg)
+    };
+
+    let client = create_dynamo_client().await;
+
+    let items = client
+        .scan()
+        .table_name(MAIN_TABLE_NAME)
+        .filter_expression(
+            "SK = :sk AND (attribute_exists(#service_hours) OR attribute_exists(#extracurricular))",
+        )
+        .expression_attribute_names("#service_hours", FIELDS[0])
+        .expression_attribute_names("#extracurricular", FIELDS[1])
+        .expression_attribute_values(":sk", AttributeValue::S("DEMOGRAPHICS".to_string()))
+        .into_paginator()
+        .items()
+        .send()
+        .collect::<Result<Vec<_>, _>>()
+        .await
+        .map_err(|err| dynamo_error(&err))?;
+
+    let mut copied = 0;
+    for item in items {
+        let Some(hash_key) = item.get("HK") else {
+            continue;
+        };
+
+        for field in FIELDS {
+            let Some(value) = item.get(field) else {
+                continue;
+            };
+
+            let result = client
+                .update_item()
+                .table_name(MAIN_TABLE_NAME)
+                .key("HK", hash_key.clone())
+                .key("SK", AttributeValue::S("EXTRACURRICULARS".to_string()))
+                .update_expression("SET #field = :value")
+                .condition_expression("attribute_not_exists(#field)")
+                .expression_attribute_names("#field", field)
+                .expression_attribute_values(":value", value.clone())
+                .send()
+                .await;
+
+            match result {
+                Ok(_) => copied += 1,
+                Err(err) if err.code() == Some("ConditionalCheckFailedException") => {}
+                Err(err) => return Err(dynamo_error(&err)),
+            }
+        }
     }
 
-    get_completed_students().await
+    debug_log!("Copied {} extracurricular answers out of demographics", copied);
+
+    Ok(copied)
 }
 
 #[server]
@@ -219,19 +818,15 @@ async fn get_completed_students()
         output
             .entry(student_id)
             .and_modify(|v| {
-                v.extend(form_info_convert.clone());
+                v.merge(form_info_convert.clone());
             })
             .or_insert(form_info_convert);
     });
 
-    // Don't love this, but it does verify that the student has completed the demographics form
+    // Only include students that have completed the demographics form.
     let output = output
         .into_iter()
-        .filter_map(|(k, student_info)| {
-            student_info
-                .get("first_name")
-                .and_then(|_| Some((k, student_info.clone())))
-        })
+        .filter(|(_, student_info)| StudentDemographics::from_map(student_info).is_ok())
         .collect();
 
     Ok(output)
@@ -512,23 +1107,6 @@ pub async fn admin_get_all_input_files(
     get_all_input_files(form_name, input_name).await
 }
 
-#[server]
-pub async fn provider_get_all_input_files(
-    access_token: String,
-    form_name: String,
-    input_name: String,
-) -> Result<HashMap<String, Vec<String>>, ServerFnError> {
-    use imports::*;
-
-    let claims =
-        validate_and_get_token_info(access_token, "us-east-1_Lfjuy5zaM", "us-east-1").await?;
-    if !claims.groups.contains(&"ScholarshipProviders".to_string()) {
-        return Err(ServerFnError::new("Access denied: user is not a provider"));
-    }
-
-    get_all_input_files(form_name, input_name).await
-}
-
 #[server]
 pub async fn get_student_pdf(student_id: String) -> Result<(String, Vec<u8>), ServerFnError> {
     use imports::*;
@@ -636,7 +1214,7 @@ pub async fn get_student_info_json(student_id: String) -> Result<String, ServerF
         .query()
         .table_name(MAIN_TABLE_NAME)
         .expression_attribute_values(":hk", AttributeValue::S(format!("STUDENT#{student_id}")))
-        .expression_attribute_values(":sk", AttributeValue::S("DEMOGRAPHICS".to_string()))
+        .expression_attribute_values(":sk", AttributeValue::S("EXTRACURRICULARS".to_string()))
         .key_condition_expression("HK = :hk AND SK = :sk")
         .send()
         .await
@@ -4,10 +4,10 @@ use aws_sdk_dynamodb::{Client, error::ProvideErrorMetadata, types::AttributeValu
 #[cfg(feature = "ssr")]
 use serde_dynamo::{from_item, to_item};
 
-#[cfg(feature = "ssr")]
-use crate::common::ValueType;
 
 use crate::common::ExpandableInfo;
+#[cfg(feature = "ssr")]
+use crate::common::ValueMap;
 use crate::components::{ActionButton, Banner, DashboardButton, OutlinedTextField, Panel, Row, Select, TextFieldType, Toast, ToastContext, ToastList, ValidatedForm};
 use chrono::{FixedOffset, TimeZone};
 use leptos::Params;
@@ -21,6 +21,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use leptos_router::components::Outlet;
 use leptos_animate::animate;
+use macros::ValueMap;
 use crate::common::animations::pop_in_out;
 // Loaner page creates a single page that contains the borrowing/returning buttons
 // and a panel for a form. At first, it will show a fallback view. At the borrowing
@@ -32,7 +33,7 @@ struct LoanerParams {
     form_name: Option<String>,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, ValueMap)]
 struct LoanerReturnOutput {
     /// The subject ID of the student entry.
     subject: String,
@@ -41,6 +42,7 @@ struct LoanerReturnOutput {
     /// The last name of the student that is borrowing.
     last_name: String,
     /// The date that the student borrowed the loaner.
+    #[value(rename = "date_taken")]
     date: String,
 }
 
@@ -64,33 +66,13 @@ async fn get_loaners_return_list() -> Result<Vec<LoanerReturnOutput>, ServerFnEr
             if let Some(items) = output.items {
                 Ok(items
                     .into_iter()
-                    .map(|item| {
-                        let expandable: ExpandableInfo = from_item(item).unwrap();
-                        // This set of function calls is ridiculous, we need to make a better way to do this.
-                        LoanerReturnOutput {
-                            first_name: expandable
-                                .data
-                                .get("first_name")
-                                .unwrap()
-                                .as_string()
-                                .unwrap_or_default()
-                                .unwrap_or_default(),
-                            last_name: expandable
-                                .data
-                                .get("last_name")
-                                .unwrap()
-                                .as_string()
-                                .unwrap_or_default()
-                                .unwrap_or_default(),
-                            date: expandable
-                                .data
-                                .get("date_taken")
-                                .unwrap()
-                                .as_string()
-                                .unwrap_or_default()
-                                .unwrap_or_default(),
-                            subject: expandable.subject,
-                        }
+                    .filter_map(|item| {
+                        let expandable: ExpandableInfo = from_item(item).ok()?;
+                        LoanerReturnOutput::from_expandable(&expandable)
+                            .inspect_err(|err| {
+                                log!("Skipping loaner entry {:?}: {}", expandable.subject, err)
+                            })
+                            .ok()
                     })
                     .collect())
             } else {
@@ -126,20 +108,36 @@ async fn check_in_loaner(student: LoanerReturnOutput) -> Result<(), ServerFnErro
     }
 }
 
+/// The information a student enters when borrowing a loaner, along with the time it was taken.
+#[cfg(feature = "ssr")]
+#[derive(Debug, Clone, ValueMap)]
+struct LoanerBorrowEntry {
+    first_name: String,
+    last_name: String,
+    email: String,
+    collateral: String,
+    loan: String,
+    /// Set by the server when the entry is created.
+    #[value(default)]
+    date_taken: String,
+}
+
 #[server]
 async fn create_borrow_entry(input: ExpandableInfo) -> Result<(), ServerFnError> {
-    let mut input = input;
     let dbclient =
         Client::new(&aws_config::load_defaults(aws_config::BehaviorVersion::latest()).await);
 
+    let mut entry = LoanerBorrowEntry::from_expandable(&input).map_err(|err| {
+        log!("Invalid borrow entry: {}", err);
+        ServerFnError::new(format!("Invalid borrow entry: {err}"))
+    })?;
+
     let current_time = chrono::Utc::now().format("%H:%M, %m/%d/%Y").to_string();
     log!("Creating entry with timestamp {}", current_time);
 
-    // Add the timestamp to the data map as a string and then push it to the database.
-    input.data.insert(
-        "date_taken".to_string(),
-        ValueType::String(Some(current_time.clone())),
-    );
+    // Add the timestamp to the entry and then push it to the database.
+    entry.date_taken = current_time;
+    let mut input = entry.to_expandable(input.subject);
 
     loop {
         let item: serde_dynamo::Item = to_item(&input)?;
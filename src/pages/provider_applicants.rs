@@ -1,13 +1,11 @@
 ﻿use crate::common::{
-    ComparisonData, ExpandableInfo, SchemaContainerStyle, SchemaHeaderStyle, SchemaNode,
-    SchemaType, ValueType,
+    self, ExpandableInfo, SchemaContainerStyle, SchemaHeaderStyle, SchemaNode, SchemaType,
+    ValueType,
 };
 use crate::components::{ActionButton, Banner, DataDisplay, Loading};
 use crate::pages::UnauthenticatedPage;
-use crate::pages::api::get_comparison_info;
-use crate::pages::api::students::{
-    GetStudentFiles, GetStudentPdf, get_student_data, provider_get_all_input_files,
-};
+use crate::pages::api::{ApplicantSummary, get_applicant_data};
+use crate::pages::api::students::{GetStudentFiles, GetStudentPdf};
 use base64::Engine;
 use leptos::ev::{Event, MouseEvent};
 use leptos::html::Dialog;
@@ -20,7 +18,6 @@ use leptos_icons::Icon;
 use leptos_oidc::{AuthLoaded, AuthSignal, Authenticated};
 use leptos_router::components::Outlet;
 use leptos_router::hooks::use_navigate;
-use std::collections::HashMap;
 
 #[component]
 pub fn ApplicantsPageFallback() -> impl IntoView {
@@ -208,7 +205,7 @@ fn ApplicantsScholarshipEntry(
 #[component]
 pub fn ApplicantsStudentList() -> impl IntoView {
     use crate::pages::api::get_scholarship_info;
-    use crate::pages::api::students::provider_get_completed_students;
+    use crate::pages::api::provider_get_eligible_students;
     use leptos_router::hooks::use_params;
     use leptos_router::params::Params;
 
@@ -239,21 +236,13 @@ pub fn ApplicantsStudentList() -> impl IntoView {
     async fn get_eligibility_info(
         token: String,
         scholarship_id: String,
-    ) -> Result<
-        (
-            HashMap<String, HashMap<String, ValueType>>,
-            ExpandableInfo,
-            Vec<ComparisonData>,
-        ),
-        ServerFnError,
-    > {
-        let (students, scholarships, requirements_list) = tokio::join!(
-            provider_get_completed_students(token),
-            get_scholarship_info(scholarship_id),
-            get_comparison_info()
+    ) -> Result<(Vec<ApplicantSummary>, ExpandableInfo), ServerFnError> {
+        let (students, scholarship) = tokio::join!(
+            provider_get_eligible_students(token, scholarship_id.clone()),
+            get_scholarship_info(scholarship_id)
         );
 
-        Ok((students?, scholarships?, requirements_list?))
+        Ok((students?, scholarship?))
     }
 
     let resource = Resource::new(
@@ -278,7 +267,7 @@ pub fn ApplicantsStudentList() -> impl IntoView {
                         .get()
                         .map(|result| {
                             match result {
-                                Ok((students, scholarship, requirements_list)) => {
+                                Ok((students, scholarship)) => {
                                     view! {
                                         <h1 class="text-2xl font-bold">"Eligible Students"</h1>
                                         <div class="text-lg">
@@ -294,10 +283,8 @@ pub fn ApplicantsStudentList() -> impl IntoView {
                                             </a>"."
                                         </div>
                                         <ApplicantsStudentListView
-                                            access_token=access_token
                                             students=students
                                             scholarship=scholarship
-                                            requirements=requirements_list
                                         />
                                     }
                                         .into_any()
@@ -320,13 +307,9 @@ pub fn ApplicantsStudentList() -> impl IntoView {
 
 #[component]
 fn ApplicantsStudentListView(
-    #[prop(into)] access_token: Signal<Option<String>>,
-    #[prop(into)] students: Signal<HashMap<String, HashMap<String, ValueType>>>,
+    #[prop(into)] students: Signal<Vec<ApplicantSummary>>,
     #[prop(into)] scholarship: Signal<ExpandableInfo>,
-    #[prop(into)] requirements: Signal<Vec<ComparisonData>>,
 ) -> impl IntoView {
-    let eligible_students = RwSignal::new(HashMap::new());
-    let error_msg = RwSignal::new(String::new());
     // let (current_student_id, set_current_student_id) = query_signal::<String>("student_id");
     let current_student_id = RwSignal::new(None);
     let navigate_student = Callback::new(move |id: String| {
@@ -346,196 +329,8 @@ fn ApplicantsStudentListView(
         current_student_id.set(None);
     });
 
-    let fafsa_required = Memo::new(move |_| {
-        let required = scholarship
-            .get()
-            .data
-            .get("fafsa_required")
-            .map(|v| v.as_string().ok().flatten())
-            .flatten()
-            .unwrap_or_default();
-
-        required == "Yes"
-    });
-
-    let essay_required = Memo::new(move |_| {
-        let prompt = scholarship
-            .get()
-            .data
-            .get("essay_prompt")
-            .map(|v| v.as_string().ok().flatten())
-            .flatten()
-            .unwrap_or_default();
-
-        !prompt.is_empty()
-    });
-
-    let fafsa_resource = Resource::new(
-        move || (fafsa_required.get(), access_token.get()),
-        move |(fafsa_required, access_token)| async move {
-            // Only get this information if the FAFSA is required. Otherwise, return a blank result.
-            let Some(access_token) = access_token else {
-                return Err(ServerFnError::new("Couldn't find access token"));
-            };
-            if !fafsa_required {
-                return Ok(HashMap::new());
-            }
-
-            provider_get_all_input_files(
-                access_token,
-                "financial_info".to_string(),
-                "fafsa".to_string(),
-            )
-            .await
-        },
-    );
-    let essay_resource = Resource::new(
-        move || (essay_required.get(), access_token.get()),
-        move |(essay_required, access_token)| async move {
-            // Only get this information if the scholarship requested it. Otherwise, return a blank
-            // result.
-            let Some(access_token) = access_token else {
-                return Err(ServerFnError::new("Couldn't find access token"));
-            };
-            if !essay_required {
-                return Ok(HashMap::new());
-            }
-
-            provider_get_all_input_files(
-                access_token,
-                "scholarship_essays".to_string(),
-                scholarship.get().subject,
-            )
-            .await
-        },
-    );
-
-    fn get_eligible_students(
-        students: HashMap<String, HashMap<String, ValueType>>,
-        scholarship: ExpandableInfo,
-        requirements: Vec<ComparisonData>,
-        essay_required: bool,
-        fafsa_required: bool,
-        fafsa_list: HashMap<String, Vec<String>>,
-        essay_list: HashMap<String, Vec<String>>,
-    ) -> Result<HashMap<String, HashMap<String, ValueType>>, Error> {
-        // Get the full map of requirements. Each key contains a list of IDs.
-        let scholarship_requirements = scholarship
-            .data
-            .get("requirements")
-            .map(|val| val.as_map().ok()?)
-            .flatten()
-            .unwrap_or_default();
-
-        // Get the lists of requirements. Maps into string IDs and discards invalid ValueTypes.
-        let requirement_lists = scholarship_requirements
-            .into_iter()
-            .filter_map(|(_, list_val)| {
-                let list = list_val.as_list().ok().flatten()?;
-                let string_list = list
-                    .into_iter()
-                    .filter_map(|v| v.as_string().ok().flatten())
-                    .collect::<Vec<String>>();
-
-                Some(string_list)
-            })
-            .collect::<Vec<Vec<String>>>();
-
-        // Resolve the requirement IDs. Discards all IDs that do not resolve.
-        let resolved_requirements = requirement_lists
-            .into_iter()
-            .map(|list| {
-                list.into_iter()
-                    .filter_map(|id| {
-                        requirements
-                            .iter()
-                            .cloned()
-                            .find(|requirement| requirement.id == id)
-                    })
-                    .collect::<Vec<ComparisonData>>()
-            })
-            .collect::<Vec<Vec<ComparisonData>>>();
-
-        // Check student eligibility. These are all students that have completed the demographics
-        // forms.
-        let eligible_students = students
-            .into_iter()
-            .filter_map(|(id, student)| {
-                // The student needs to pass one requirement from each list.
-                // The easiest ones to check first are whether the scholarship requires a student
-                // essay and/or their fafsa information. If the student does not have these things,
-                // they are skipped.
-
-                if fafsa_required && !fafsa_list.contains_key(&id) {
-                    // Get the student files related to their fafsa and continue. Return false if not there.
-                    debug_log!(
-                        "Student with id {id} failed - FAFSA is required but was not found."
-                    );
-                    return None;
-                }
-
-                if essay_required && !essay_list.contains_key(&id) {
-                    // Get the student's files related to their essays and continue. Return false if not there.
-                    debug_log!(
-                        "Student with id {id} failed - essay is required but was not found."
-                    );
-                    return None;
-                }
-
-                let result = resolved_requirements.iter().all(|list| {
-                    if list.is_empty() {
-                        return true;
-                    }
-                    list.iter().any(|requirement| {
-                        let result = requirement.compare(&student).unwrap_or(false);
-
-                        if !result {
-                            debug_log!(
-                                "Student with id {id} failed - requirement with id {} failed.",
-                                requirement.id
-                            );
-                        }
-
-                        result
-                    })
-                });
-
-                if result { Some((id, student)) } else { None }
-            })
-            .collect::<HashMap<String, HashMap<String, ValueType>>>();
-
-        // debug_log!("Total number of eligible students: {}", eligible_students.len());
-
-        Ok(eligible_students)
-    }
-
-    Effect::new(move || {
-        // We have to wait until we've gotten fafsa/essay requirements.
-        debug_log!("Waiting on essay and FAFSA resources...");
-        if let (Some(fafsa_list), Some(essay_list)) = (fafsa_resource.get(), essay_resource.get()) {
-            debug_log!(
-                "Running eligibility check on {} students",
-                students.get().len()
-            );
-            let eligible_res = get_eligible_students(
-                students.get(),
-                scholarship.get(),
-                requirements.get(),
-                essay_required.get(),
-                fafsa_required.get(),
-                fafsa_list.unwrap_or_default(),
-                essay_list.unwrap_or_default(),
-            );
-
-            match eligible_res {
-                Ok(list) => {
-                    debug_log!("Total number of eligible students: {}", list.len());
-                    eligible_students.set(list);
-                }
-                Err(e) => error_msg.set(format!("Failed to get eligible students list: {e}")),
-            }
-        }
-    });
+    let fafsa_required = Memo::new(move |_| scholarship.with(common::fafsa_required));
+    let essay_required = Memo::new(move |_| scholarship.with(common::essay_required));
 
     // This is actually just a display of all the information from the students list.
     // As defined in the ApplicantsStudentList component, this will already be wrapped in a
@@ -549,47 +344,112 @@ fn ApplicantsStudentListView(
             essay_required=essay_required
             fafsa_required=fafsa_required
         />
-        <Show when=move || !error_msg.get().is_empty()>
-            <div>"Error checking student eligibility: "{error_msg}</div>
-        </Show>
+        <ApplicantTable students=students on_select=navigate_student />
+    }
+}
+
+/// The columns that an [`ApplicantTable`] can be sorted by.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ApplicantSortKey {
+    LastName,
+    FirstName,
+    Score,
+}
+
+/// # Applicant Table
+///
+/// Displays a list of applicants with their names and scores. Clicking a column header sorts the
+/// table by that column, and clicking it again reverses the order. Applicants are sorted by score
+/// by default, with the highest scores first. Hovering over a score shows its breakdown.
+#[component]
+pub fn ApplicantTable(
+    #[prop(into)] students: Signal<Vec<ApplicantSummary>>,
+    /// Called with the student's ID when a row is clicked.
+    #[prop(into)]
+    on_select: Callback<String>,
+) -> impl IntoView {
+    let sort_key = RwSignal::new(ApplicantSortKey::Score);
+    let descending = RwSignal::new(true);
+
+    let sorted_students = Memo::new(move |_| {
+        let mut students = students.get();
+        let key = sort_key.get();
+        let descending = descending.get();
+
+        // The students are already sorted by name, so a stable sort keeps ties in that order. The
+        // operands are swapped for descending order instead of reversing, which would flip ties.
+        students.sort_by(|a, b| {
+            let (a, b) = if descending { (b, a) } else { (a, b) };
+            match key {
+                ApplicantSortKey::LastName => (&a.last_name, &a.first_name)
+                    .cmp(&(&b.last_name, &b.first_name)),
+                ApplicantSortKey::FirstName => (&a.first_name, &a.last_name)
+                    .cmp(&(&b.first_name, &b.last_name)),
+                ApplicantSortKey::Score => a.score.total.total_cmp(&b.score.total),
+            }
+        });
+
+        students
+    });
+
+    let header = move |key: ApplicantSortKey, label: &'static str, class: &'static str| {
+        let on_click = move |_| {
+            if sort_key.get() == key {
+                descending.update(|descending| *descending = !*descending);
+            } else {
+                sort_key.set(key);
+                // Scores are most useful from highest to lowest, and names from A to Z.
+                descending.set(key == ApplicantSortKey::Score);
+            }
+        };
+        let indicator = move || match (sort_key.get() == key, descending.get()) {
+            (false, _) => "",
+            (true, false) => " ▲",
+            (true, true) => " ▼",
+        };
+
+        view! {
+            <div
+                class=format!("{class} text-lg font-bold text-left cursor-pointer select-none")
+                on:click=on_click
+            >
+                {label}
+                {indicator}
+            </div>
+        }
+    };
+
+    view! {
         <div class="m-3 flex flex-col">
             <div class="flex flex-row p-1 border-b-1 border-black">
-                <div class="pl-5 flex-1 text-lg font-bold text-left">"Last Name"</div>
-                <div class="pr-5 flex-1 text-lg font-bold text-left">"First Name"</div>
+                {header(ApplicantSortKey::LastName, "Last Name", "pl-5 flex-1")}
+                {header(ApplicantSortKey::FirstName, "First Name", "flex-1")}
+                {header(ApplicantSortKey::Score, "Score", "pr-5 w-24")}
             </div>
             <For
-                each=move || eligible_students.get()
-                key=|(id, _)| id.clone()
-                children=move |(student_id, student)| {
-                    let student = StoredValue::new(student);
-                    let first_name = Memo::new(move |_| {
-                        student
-                            .get_value()
-                            .get("first_name")
-                            .map(|n| n.as_string().ok().flatten())
-                            .unwrap_or_default()
-                    });
-                    let last_name = Memo::new(move |_| {
-                        student
-                            .get_value()
-                            .get("last_name")
-                            .map(|n| n.as_string().ok().flatten())
-                            .unwrap_or_default()
-                    });
+                each=move || sorted_students.get()
+                key=|student| student.student_id.clone()
+                children=move |student| {
+                    let student_id = student.student_id.clone();
                     let on_click = move |_| {
-                        navigate_student.run(student_id.clone());
+                        on_select.run(student_id.clone());
+                    };
+                    let score_title = if student.score.components.is_empty() {
+                        "This scholarship has no scoring criteria.".to_string()
+                    } else {
+                        student.score.describe()
                     };
-                    // For now, we'll just display each student's first and last name.
-                    // Now I'll add a container with some links. These links will just be appended
-                    // to the end of the current path.
 
                     view! {
                         <div
                             class="flex flex-row p-1 border-y-1 border-gray-200 cursor-pointer hover:bg-yellow-100 transition-all duration-200"
                             on:click=on_click
                         >
-                            <div class="pl-5 flex-1 text-lg text-left">{last_name}</div>
-                            <div class="pr-5 flex-1 text-lg text-left">{first_name}</div>
+                            <div class="pl-5 flex-1 text-lg text-left">{student.last_name}</div>
+                            <div class="flex-1 text-lg text-left">{student.first_name}</div>
+                            <div class="pr-5 w-24 text-lg text-left" title=score_title>
+                                {format!("{:.1}", student.score.total)}
+                            </div>
                         </div>
                     }
                 }
@@ -615,6 +475,14 @@ pub fn StudentInformationDialog(
     let auth = expect_context::<AuthSignal>();
     let access_token =
         Memo::new(move |_| auth.with(|auth| auth.authenticated().map(|a| a.access_token())));
+    let get_form = move |data_type: &str| {
+        get_applicant_data(
+            access_token.get().unwrap_or_default(),
+            scholarship_id.get(),
+            student_id.get().unwrap_or_default(),
+            data_type.to_string(),
+        )
+    };
 
     //#region Schema Definitions
 
@@ -1225,10 +1093,7 @@ pub fn StudentInformationDialog(
                         </ActionButton>
                     </div>
                     {move || Suspend::new(async move {
-                        get_student_data(
-                                student_id.get().unwrap_or_default(),
-                                "demographics".to_string(),
-                            )
+                        get_form("demographics")
                             .await
                             .map(|student_info| {
                                 view! {
@@ -1240,10 +1105,7 @@ pub fn StudentInformationDialog(
                             })
                     })}
                     {move || Suspend::new(async move {
-                        get_student_data(
-                                student_id.get().unwrap_or_default(),
-                                "academics".to_string(),
-                            )
+                        get_form("academics")
                             .await
                             .map(|student_info| {
                                 view! {
@@ -1255,10 +1117,7 @@ pub fn StudentInformationDialog(
                             })
                     })}
                     {move || Suspend::new(async move {
-                        get_student_data(
-                                student_id.get().unwrap_or_default(),
-                                "athletics".to_string(),
-                            )
+                        get_form("athletics")
                             .await
                             .map(|student_info| {
                                 view! {
@@ -1271,10 +1130,7 @@ pub fn StudentInformationDialog(
                             })
                     })}
                     {move || Suspend::new(async move {
-                        get_student_data(
-                                student_id.get().unwrap_or_default(),
-                                "demographics".to_string(),
-                            )
+                        get_form("demographics")
                             .await
                             .map(|student_info| {
                                 view! {
@@ -1286,10 +1142,7 @@ pub fn StudentInformationDialog(
                             })
                     })}
                     {move || Suspend::new(async move {
-                        get_student_data(
-                                student_id.get().unwrap_or_default(),
-                                "workexp".to_string(),
-                            )
+                        get_form("workexp")
                             .await
                             .map(|student_info| {
                                 view! {
@@ -1302,10 +1155,7 @@ pub fn StudentInformationDialog(
                             })
                     })}
                     {move || Suspend::new(async move {
-                        get_student_data(
-                                student_id.get().unwrap_or_default(),
-                                "university".to_string(),
-                            )
+                        get_form("university")
                             .await
                             .map(|student_info| {
                                 view! {
@@ -1317,7 +1167,7 @@ pub fn StudentInformationDialog(
                             })
                     })}
                     {move || Suspend::new(async move {
-                        get_student_data(student_id.get().unwrap_or_default(), "family".to_string())
+                        get_form("family")
                             .await
                             .map(|student_info| {
                                 view! {
@@ -1329,10 +1179,7 @@ pub fn StudentInformationDialog(
                             })
                     })}
                     {move || Suspend::new(async move {
-                        get_student_data(
-                                student_id.get().unwrap_or_default(),
-                                "specifics".to_string(),
-                            )
+                        get_form("specifics")
                             .await
                             .map(|student_info| {
                                 view! {
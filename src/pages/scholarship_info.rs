@@ -3,13 +3,14 @@ use leptos::either::Either;
 use leptos::html::Dialog;
 use leptos::logging::debug_log;
 use leptos::prelude::*;
-use leptos_oidc::{AuthLoaded, Authenticated};
+use leptos_oidc::{AuthLoaded, AuthSignal, Authenticated};
 use leptos_router::hooks::{use_navigate, use_params};
-use crate::common::{ComparisonData, ExpandableInfo, ScholarshipFormParams, SubmitStatus, ValueType};
-use crate::components::{ActionButton, Banner, ChipsList, Header, Loading, OutlinedTextField, Panel, RadioList, Row, TextFieldType, Toast, ToastContext, ToastList, ValidatedForm};
+use crate::input;
+use crate::common::{get_requirements, BUCKETS_EXAMPLE, CombinationMode, ComparisonData, ExpandableInfo, RequirementCategory, RULE_EXAMPLE, ScholarshipFormParams, SubmitStatus, ValuePatch, ValueType};
+use crate::components::{ActionButton, Banner, ChipsList, Header, Loading, MultiEntry, OutlinedTextField, Panel, RadioList, Row, Select, TextFieldType, Toast, ToastContext, ToastList, ValidatedForm};
 use super::UnauthenticatedPage;
 use crate::utils::get_user_claims;
-use super::api::{get_provider_scholarships, get_scholarship_info, CreateScholarshipInfo, RegisterScholarship, DeleteProviderScholarship, get_comparisons_categorized};
+use super::api::{preview_applicant_counts, get_provider_scholarships, get_scholarship_info, PatchScholarshipInfo, RegisterScholarship, DeleteProviderScholarship, get_comparisons_categorized};
 
 
 /// # Scholarship Info Page
@@ -341,7 +342,8 @@ fn ScholarshipListEntry(
 /// # Scholarship Edit Form Component
 ///
 /// Displays all information about a scholarship and allows the provider to edit that information.
-/// All information is only saved when the submit button is clicked.
+/// All information is only saved when the submit button is clicked, and only the fields that
+/// changed are sent, so that other edits to the same scholarship aren't overwritten.
 ///
 /// The component will call the `on_submit` callback when the API returns an `Ok` result. In the
 /// event that the API call fails, it will not call anything. This is mainly used to allow updates
@@ -379,24 +381,45 @@ fn ScholarshipForm(
     );
 
     let form_data = RwSignal::new(HashMap::new());
+    // The data that's known to be stored, which submissions are compared against.
+    let saved_data = StoredValue::new(HashMap::new());
     let chips_data = RwSignal::new(HashMap::new());
+    // The combination mode of each category, and the count used by the "At least" mode.
+    let mode_data = RwSignal::new(HashMap::new());
     
     Effect::new(move || {
         if let Some(Ok(scholarship)) = scholarship_info.get() {
-            // Get the default chips data.
-            let chips_default = if let Some(ValueType::Map(Some(map))) = scholarship.data.get("requirements") {
-                debug_log!("Found requirements map: {:?}", map);
-                map.clone()
-            } else {
-                debug_log!("Couldn't find requirements map in scholarship info. Using empty map.");
-                HashMap::new()
-            };
+            // Split the requirements into the chips data and the combination modes.
+            let requirements = get_requirements(&scholarship);
+            debug_log!("Found requirements: {:?}", requirements);
+
+            let mut chips_default = HashMap::new();
+            let mut modes_default = HashMap::new();
+            for (category, requirement) in requirements {
+                let ids = requirement.ids
+                    .into_iter()
+                    .map(|id| ValueType::String(Some(id)))
+                    .collect();
+                let (mode, count) = match requirement.mode {
+                    CombinationMode::All => (MODE_ALL, 1),
+                    CombinationMode::Any => (MODE_ANY, 1),
+                    CombinationMode::AtLeast(count) => (MODE_AT_LEAST, count),
+                };
+
+                modes_default.insert(category.clone(), ValueType::String(Some(mode.to_string())));
+                modes_default.insert(format!("{category}_count"), ValueType::Number(Some(count.to_string())));
+                chips_default.insert(category, ValueType::List(Some(ids)));
+            }
 
             chips_data.set(chips_default);
+            mode_data.set(modes_default);
+            saved_data.set_value(scholarship.data.clone());
             form_data.set(scholarship.data);
         }
     });
 
+    let requirements = Memo::new(move |_| combine_requirements(&chips_data.get(), &mode_data.get()));
+
     //#endregion
     //#region Comparison Logic
 
@@ -465,7 +488,15 @@ fn ScholarshipForm(
     //#endregion
     //#region Submission Logic
 
-    let submit_action = ServerAction::<CreateScholarshipInfo>::new();
+    let user_claims = get_user_claims();
+    let provider_id = Memo::new(move |_| {
+        user_claims.get()
+            .as_ref()
+            .map(|info| info.claims.subject.clone())
+    });
+
+    let submit_action = ServerAction::<PatchScholarshipInfo>::new();
+    let submitted_data = StoredValue::new(None::<HashMap<String, ValueType>>);
 
     let submit_status = RwSignal::new(SubmitStatus::Idle);
     let elements_disabled = Signal::derive(move || {
@@ -481,6 +512,9 @@ fn ScholarshipForm(
         if let Some(result) = submit_action.value().get() {
             match result {
                 Ok(()) => {
+                    if let Some(submitted) = submitted_data.get_value() {
+                        saved_data.set_value(submitted);
+                    }
                     submit_status.set(SubmitStatus::Success);
                     on_submit.run(());
                 },
@@ -492,13 +526,17 @@ fn ScholarshipForm(
     let mut toasts = expect_context::<ToastContext>();
     
     let on_submit = move |_| {
-        let mut info = ExpandableInfo::new(scholarship_id.get().unwrap_or_default());
-        info.data = form_data.get();
-        info.data.insert("requirements".to_string(), ValueType::Map(Some(chips_data.get())));
-
-        debug_log!("Map values: {:?}", info.data);
-        submit_action.dispatch(CreateScholarshipInfo {
-            info
+        let mut data = form_data.get();
+        data.insert("requirements".to_string(), ValueType::Map(Some(requirements.get())));
+
+        let patch = saved_data.with_value(|saved| ValuePatch::diff(saved, &data));
+        debug_log!("Changed values: {:?}", patch);
+        submitted_data.set_value(Some(data));
+        submit_action.dispatch(PatchScholarshipInfo {
+            provider_id: provider_id.get().unwrap_or_default(),
+            scholarship_id: scholarship_id.get().unwrap_or_default(),
+            set: patch.set,
+            remove: patch.remove,
         });
     };
 
@@ -639,8 +677,10 @@ fn ScholarshipForm(
                                                     <Header
                                                         title="Eligibility Requirements"
                                                         description="Choose any requirements that this scholarship may have. If there are no extra requirements,
-                                                        leave all options blank, however please note that this will apply all students to your scholarship."
+                                                        leave all options blank, however please note that this will apply all students to your scholarship. Students must meet every
+                                                        category, and by default only need to meet one of the options within a category."
                                                     />
+                                                    <RequirementsPreview requirements=requirements />
                                                     <Row>
                                                         <ChipsList
                                                             label="GPA Requirements"
@@ -659,6 +699,7 @@ fn ScholarshipForm(
                                                             values=sports_ids
                                                             displayed_text=sports_text
                                                         />
+                                                        <CategoryModeSelect category="sports_participation" data_map=mode_data disabled=elements_disabled />
                                                     </Row>
                                                     <Row>
                                                         <ChipsList
@@ -688,6 +729,7 @@ fn ScholarshipForm(
                                                             values=major_ids
                                                             displayed_text=major_text
                                                         />
+                                                        <CategoryModeSelect category="major" data_map=mode_data disabled=elements_disabled />
                                                     </Row>
                                                     <Row>
                                                         <ChipsList
@@ -697,6 +739,33 @@ fn ScholarshipForm(
                                                             values=misc_ids
                                                             displayed_text=misc_text
                                                         />
+                                                        <CategoryModeSelect category="misc" data_map=mode_data disabled=elements_disabled />
+                                                    </Row>
+                                                    <Header
+                                                        title="Scoring Criteria"
+                                                        description=format!("Optionally, add criteria to rank your eligible applicants. Scaled criteria award up to 10 points
+                                                        between the minimum and maximum of a field. Bucket criteria award the points of the first matching bucket, like
+                                                        {BUCKETS_EXAMPLE}. Rule criteria award their points when a student passes a rule, like
+                                                        {RULE_EXAMPLE}. Each criterion's points are multiplied by its weight.")
+                                                    />
+                                                    <Row>
+                                                        <MultiEntry
+                                                            label="Criteria:"
+                                                            description="Leave blank to list applicants without scores."
+                                                            data_map=form_data
+                                                            data_member="scoring"
+                                                            schema=vec![
+                                                                input!(Text, "label", "Label:", true, "Community Service Hours"),
+                                                                input!(Select, "method", "Method:", true, ["Scaled", "Buckets", "Rule"]),
+                                                                input!(Text, "field", "Student Field:", false, "community_service.hours"),
+                                                                input!(Number, "weight", "Weight:", false, "1"),
+                                                                input!(Number, "min", "Minimum (Scaled):", false, "0"),
+                                                                input!(Number, "max", "Maximum (Scaled):", false, "100"),
+                                                                input!(Text, "buckets", "Buckets:", false, ">= 40: 10, >= 20: 5"),
+                                                                input!(Text, "rule", "Rule:", false, "gpa >= 3.5"),
+                                                                input!(Number, "points", "Points (Rule):", false, "10"),
+                                                            ]
+                                                        />
                                                     </Row>
                                                 </ValidatedForm>
                                             },
@@ -718,3 +787,149 @@ fn ScholarshipForm(
         </Panel>
     }.into_any()
 }
+
+// The combination modes, as displayed in the CategoryModeSelect component.
+static MODE_ANY: &str = "Any of these";
+static MODE_ALL: &str = "All of these";
+static MODE_AT_LEAST: &str = "At least...";
+
+/// Combines the selected chips with their combination modes into a `requirements` map. Categories
+/// without a mode use [`CombinationMode::Any`].
+fn combine_requirements(
+    chips: &HashMap<String, ValueType>,
+    modes: &HashMap<String, ValueType>,
+) -> HashMap<String, ValueType> {
+    chips
+        .iter()
+        .map(|(category, ids)| {
+            let get_text = |key: &str| modes.get(key).map(|v| v.to_string()).unwrap_or_default();
+            let mode_text = get_text(category);
+            let mode = if mode_text == MODE_ALL {
+                CombinationMode::All
+            } else if mode_text == MODE_AT_LEAST {
+                let count = get_text(&format!("{category}_count")).trim().parse().unwrap_or(1);
+                CombinationMode::AtLeast(count)
+            } else {
+                CombinationMode::Any
+            };
+
+            let requirement = RequirementCategory {
+                ids: RequirementCategory::from_value(ids).ids,
+                mode,
+            };
+            (category.clone(), requirement.to_value())
+        })
+        .collect()
+}
+
+/// # Category Mode Select Component
+///
+/// Lets the provider choose how the requirements in a category are combined. The mode is stored
+/// in the `data_map` under the category's key, and the count for the "At least" mode is stored
+/// under `<category>_count`.
+#[component]
+fn CategoryModeSelect(
+    #[prop(into)] category: String,
+    #[prop()] data_map: RwSignal<HashMap<String, ValueType>>,
+    #[prop(optional, into)] disabled: Signal<bool>,
+) -> impl IntoView {
+    let count_member = format!("{category}_count");
+    let mode_member = category.clone();
+    let is_at_least = Signal::derive(move || {
+        data_map.with(|map| map.get(&mode_member).map(|v| v.to_string()).unwrap_or_default() == MODE_AT_LEAST)
+    });
+
+    view! {
+        <div class="flex flex-col">
+            <Select
+                label="Students must meet:"
+                value_list=vec![MODE_ANY.to_string(), MODE_ALL.to_string(), MODE_AT_LEAST.to_string()]
+                data_member=category
+                data_map=data_map
+                disabled=disabled
+            />
+            <Show when=move || is_at_least.get()>
+                <OutlinedTextField
+                    label="Number of requirements:"
+                    placeholder="2"
+                    data_member=count_member.clone()
+                    data_map=data_map
+                    input_type=TextFieldType::Number
+                    disabled=disabled
+                />
+            </Show>
+        </div>
+    }
+}
+
+/// The labels of the requirement categories, as displayed in the [`ScholarshipForm`].
+static REQUIREMENT_LABELS: [(&str, &str); 6] = [
+    ("gpa", "GPA Requirements"),
+    ("sports_participation", "Sports and Activities"),
+    ("community_service", "Community Service"),
+    ("residency", "Residency"),
+    ("major", "Majors"),
+    ("misc", "Additional Eligibility Factors"),
+];
+
+/// # Requirements Preview Component
+///
+/// Displays how many current students match the given requirements, both overall and for each
+/// category. The counts are refreshed whenever the requirements change, so providers can see the
+/// effect of each chip before saving. No student information other than the counts is shown.
+#[component]
+fn RequirementsPreview(
+    /// The in-progress requirements map, in the same format as the scholarship's `requirements`.
+    #[prop(into)] requirements: Signal<HashMap<String, ValueType>>,
+) -> impl IntoView {
+    let auth = expect_context::<AuthSignal>();
+    let access_token =
+        Memo::new(move |_| auth.with(|auth| auth.authenticated().map(|a| a.access_token())));
+
+    let preview = Resource::new(
+        move || (access_token.get(), requirements.get()),
+        async move |(access_token, requirements)| {
+            let Some(access_token) = access_token else {
+                return Err(ServerFnError::new("Not authenticated."));
+            };
+
+            preview_applicant_counts(access_token, requirements).await
+        }
+    );
+
+    view! {
+        <div class="m-2 p-3 flex flex-col gap-1 rounded-md bg-gray-100">
+            <Transition fallback=move || view! { <p>"Counting matching students..."</p> }>
+                {move || preview.get().map(|result| match result {
+                    Ok(preview) => Either::Left(view! {
+                        <p class="font-bold">
+                            {format!(
+                                "{} of {} students currently meet these requirements, and {} of them have uploaded their FAFSA.",
+                                preview.requirements_met,
+                                preview.total_students,
+                                preview.fafsa_uploaded,
+                            )}
+                        </p>
+                        <ul class="pl-4 list-disc">
+                            {preview.categories
+                                .into_iter()
+                                .map(|count| {
+                                    let label = REQUIREMENT_LABELS
+                                        .iter()
+                                        .find(|(key, _)| *key == count.category)
+                                        .map(|(_, label)| label.to_string())
+                                        .unwrap_or(count.category);
+
+                                    view! { <li>{format!("{label}: {} students", count.passing)}</li> }
+                                })
+                                .collect_view()}
+                        </ul>
+                    }),
+                    Err(err) => Either::Right(view! {
+                        <p>"Couldn't count matching students: "{err.to_string()}</p>
+                    }),
+                })}
+            </Transition>
+        </div>
+    }
+}
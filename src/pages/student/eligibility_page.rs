@@ -1,45 +1,36 @@
-use crate::common::{ComparisonData, ExpandableInfo, ValueType};
+use crate::common::{ESSAY_FORM, ExpandableInfo};
 use crate::components::{FileDrop, Header, Loading};
 use crate::pages::api::files::list_files;
-use crate::pages::api::students::get_all_student_data;
-use crate::pages::api::{get_all_scholarship_info, get_comparison_info};
-use crate::utils::get_user_claims;
-use leptos::logging::{debug_error, debug_log};
+use crate::pages::api::get_student_eligibility;
+use leptos::logging::debug_log;
 use leptos::prelude::*;
 use leptos_oidc::AuthSignal;
 
 #[component]
 pub fn StudentEligibilityPage() -> impl IntoView {
     // This page will show all scholarships that the student is eligible for.
-    // The scholarships are obviously pulled using a readonly API, and we'll also need to resolve
-    // all of the relation IDs that are stored inside of it.
-    // Then, for each scholarship found, we'll check every relation and filter out scholarships that
-    // do not pass the check. We'll show some sort of simple list to indicate what scholarships passed
-    // the check, and another to indicate which ones didn't.
+    // Eligibility is checked on the server using the shared eligibility module, which returns the
+    // result for every scholarship. We only show the scholarships whose requirements passed, since
+    // the student still needs to see scholarships that are waiting on an essay.
 
     // My only reservation with showing the scholarships that aren't valid is that people will want
     // to know why. That's hard information to keep track of right now.
 
     let auth = expect_context::<AuthSignal>();
-    let user_claims = get_user_claims();
-    let user_id = Memo::new(move |_| user_claims.get().map(|info| info.claims.subject.clone()));
     let access_token = Memo::new(move |_| auth.get().authenticated().map(|a| a.access_token()));
 
-    let form_id = StoredValue::new("scholarship_essays".to_string());
+    let form_id = StoredValue::new(ESSAY_FORM.to_string());
 
     let refresh_trigger = Trigger::new();
 
-    let scholarships_resource = Resource::new(
-        move || refresh_trigger.track(),
-        async move |_| get_all_scholarship_info().await,
-    );
-    let relations_resource = Resource::new(
-        move || refresh_trigger.track(),
-        async move |_| get_comparison_info().await,
-    );
-    let student_resource = Resource::new(
-        move || (user_id.get().unwrap_or_default(), refresh_trigger.track()),
-        async move |(user_id, _)| get_all_student_data(user_id).await,
+    let eligibility_resource = Resource::new(
+        move || (access_token.get(), refresh_trigger.track()),
+        async move |(access_token, _)| {
+            let Some(access_token) = access_token else {
+                return Err(ServerFnError::new("Couldn't find access token"));
+            };
+            get_student_eligibility(access_token).await
+        },
     );
 
     view! {
@@ -47,90 +38,19 @@ pub fn StudentEligibilityPage() -> impl IntoView {
         <div class="flex-2 mb-4 flex flex-col">
             <Suspense fallback=Loading>
                 {move || {
-                    let Some(Ok(scholarships_list)) = scholarships_resource.get() else {
-                        return view! {}.into_any();
-                    };
-                    let Some(Ok(relations_list)) = relations_resource.get() else {
-                        return view! {}.into_any();
-                    };
-                    let Some(Ok(student_info)) = student_resource.get() else {
+                    let Some(Ok(results)) = eligibility_resource.get() else {
                         return view! {}.into_any();
                     };
-                    let valid_list = scholarships_list
-                        .iter()
-                        .filter_map(|scholarship| {
+                    let valid_list = results
+                        .into_iter()
+                        .filter_map(|(scholarship, result)| {
                             let scholarship_name = scholarship
                                 .data
                                 .get("name")
-                                .unwrap_or(&ValueType::String(None))
-                                .as_string()
-                                .ok()?
+                                .and_then(|v| v.as_string().ok().flatten())
                                 .unwrap_or_default();
-                            debug_log!("Checking scholarship: {}", scholarship_name);
-                            let requirements_map = scholarship
-                                .data
-                                .get("requirements")
-                                .unwrap_or(&ValueType::Map(None))
-                                .as_map()
-                                .ok()?
-                                .unwrap_or_default()
-                                .values()
-                                .cloned()
-                                .collect::<Vec<ValueType>>();
-                            let resolved_requirements = requirements_map
-                                .iter()
-                                .map(|list_val| {
-                                    let list = list_val
-                                        .as_list()
-                                        .ok()
-                                        .flatten()
-                                        .unwrap_or_default();
-                                    list.iter()
-                                        .filter_map(|v| {
-                                            let id_string = v
-                                                .as_string()
-                                                .ok()
-                                                .flatten()
-                                                .unwrap_or_default();
-                                            relations_list
-                                                .iter()
-                                                .find(|relation| relation.id == id_string)
-                                        })
-                                        .cloned()
-                                        .collect::<Vec<ComparisonData>>()
-                                })
-                                .collect::<Vec<Vec<ComparisonData>>>();
-                            debug_log!(
-                                "Resolved requirements: {:?}", resolved_requirements.iter().flatten().map(|rel| rel.display_text.clone()).collect::<Vec<String>>()
-                            );
-                            let valid = !scholarship_name.is_empty()
-                                && resolved_requirements
-                                    .iter()
-                                    .all(|list| {
-                                        debug_log!("Checking list: {:?}", list);
-                                        if list.is_empty() {
-                                            true
-                                        } else {
-                                            list.iter()
-                                                .any(|requirement| {
-                                                    let result = requirement.compare(&student_info);
-                                                    match result.as_ref() {
-                                                        Ok(status) => {
-                                                            debug_log!(
-                                                                "Requirement with id {} is Ok({})", requirement.id, status
-                                                            );
-                                                            status.clone()
-                                                        }
-                                                        Err(e) => {
-                                                            debug_error!(
-                                                                "Requirement with id {} failed: {}", requirement.id, e
-                                                            );
-                                                            false
-                                                        }
-                                                    }
-                                                })
-                                        }
-                                    });
+                            debug_log!("Scholarship {} result: {:?}", scholarship_name, result);
+                            let valid = !scholarship_name.is_empty() && result.requirements_met;
                             if valid {
                                 debug_log!("Scholarship is valid.");
                                 let scholarship_id = StoredValue::new(scholarship.subject.clone());
@@ -149,7 +69,7 @@ pub fn StudentEligibilityPage() -> impl IntoView {
                                         list_files(access_token, form_id, scholarship_id).await
                                     },
                                 );
-                                Some((scholarship.clone(), resource))
+                                Some((scholarship, resource))
                             } else {
                                 None
                             }
@@ -1,4 +1,5 @@
-use crate::components::{FileDrop, Header, Loading};
+use crate::common::DatePurpose;
+use crate::components::{FileDrop, FormWindowGate, Header, Loading};
 use crate::pages::api::files::list_files;
 use leptos::prelude::*;
 use leptos_oidc::AuthSignal;
@@ -25,7 +26,11 @@ pub fn StudentFinancialPage() -> impl IntoView {
         },
     );
 
+    let access_token =
+        Memo::new(move |_| auth.with(|auth| auth.authenticated().map(|a| a.access_token())));
+
     view! {
+        <FormWindowGate purpose=DatePurpose::StudentForms access_token=access_token>
         <div class="flex flex-1" />
         <div class="flex flex-col flex-2 mt-6">
             <Header
@@ -43,5 +48,6 @@ pub fn StudentFinancialPage() -> impl IntoView {
             </Suspense>
         </div>
         <div class="flex flex-1" />
+        </FormWindowGate>
     }
 }
@@ -0,0 +1,170 @@
+use crate::common::DatePurpose;
+use crate::components::{AsyncValidator, DynamicForm, FormWindowGate, Loading};
+use crate::pages::api::{get_student_form, get_student_form_by_path};
+use crate::pages::api::students::check_student_id;
+use crate::pages::student::form_setup::{format_draft_time, use_student_form};
+use leptos::logging::error;
+use leptos::prelude::*;
+use leptos_oidc::AuthSignal;
+use leptos_router::hooks::use_location;
+
+/// # Student Form Page
+///
+/// Shows one of the student forms, rendered from its definition on the server. The form's data
+/// is loaded and submitted using the form's ID as its data type. Unsaved changes are autosaved as
+/// drafts, and a draft left over from an earlier visit can be restored. The form is only shown
+/// while the student forms are open, or while the student has an extension.
+///
+/// Example usage:
+/// ```ignore
+/// view! { <StudentFormPage form_id="academics" /> }
+/// ```
+#[component]
+pub fn StudentFormPage(
+    /// The ID of the form to show, such as `demographics`.
+    #[prop(into)]
+    form_id: String,
+) -> impl IntoView {
+    let controller = use_student_form(form_id.clone(), true, true);
+
+    let auth = expect_context::<AuthSignal>();
+    let access_token =
+        Memo::new(move |_| auth.with(|auth| auth.authenticated().map(|a| a.access_token())));
+
+    // Checks that need the server. The student's ID number can't already be used by another
+    // student.
+    let async_validators = StoredValue::new(match form_id.as_str() {
+        "demographics" => vec![AsyncValidator::new("id_number", move |data| async move {
+            let Some(access_token) = access_token.get_untracked() else {
+                return Ok(());
+            };
+            let id_number = data
+                .get("id_number")
+                .map(|value| value.to_string())
+                .unwrap_or_default();
+
+            match check_student_id(access_token, id_number).await {
+                Ok(None) => Ok(()),
+                Ok(Some(problem)) => Err(problem),
+                // The server checks the rest of the form when it's submitted, so a check that
+                // couldn't run shouldn't stop the student from submitting.
+                Err(err) => {
+                    error!("Couldn't check student ID number: {}", err);
+                    Ok(())
+                }
+            }
+        })],
+        _ => Vec::new(),
+    });
+
+    let definition = Resource::new(
+        move || form_id.clone(),
+        async move |form_id| get_student_form(form_id).await,
+    );
+
+    view! {
+        <FormWindowGate purpose=DatePurpose::StudentForms access_token=access_token>
+        <Show
+            when=move || controller.data_resource.get().is_some() && definition.get().is_some()
+            fallback=Loading
+        >
+            <div class="flex flex-1" />
+            <div class="flex flex-col flex-2 mt-6">
+                {move || {
+                    controller
+                        .restorable_draft
+                        .get()
+                        .map(|draft| {
+                            view! {
+                                <div class="flex flex-row flex-wrap items-center gap-4 p-4 mb-4 rounded-md border border-gray-300 bg-gray-50">
+                                    <span class="flex-1">
+                                        "You have unsaved changes from "
+                                        {format_draft_time(draft.saved_at)}
+                                        ". Would you like to restore them?"
+                                    </span>
+                                    <button
+                                        type="button"
+                                        class="underline cursor-pointer"
+                                        on:click=move |_| controller.restore_draft.run(())
+                                    >
+                                        "Restore"
+                                    </button>
+                                    <button
+                                        type="button"
+                                        class="underline cursor-pointer"
+                                        on:click=move |_| controller.dismiss_draft.run(())
+                                    >
+                                        "Discard"
+                                    </button>
+                                </div>
+                            }
+                        })
+                }}
+                {move || match definition.get() {
+                    Some(Ok(definition)) => {
+                        view! {
+                            <DynamicForm
+                                definition=definition
+                                data_map=controller.data_map
+                                on_submit=controller.submit_action
+                                unsaved_changes=controller.unsaved_changes
+                                on_discard=controller.discard_changes
+                                draft_saved_at=controller.draft_saved_at
+                                field_errors=controller.field_errors
+                                disabled=controller.submit_pending
+                                async_validators=async_validators.get_value()
+                            />
+                        }
+                            .into_any()
+                    }
+                    Some(Err(err)) => {
+                        view! { <p class="mx-auto mt-10">{err.to_string()}</p> }.into_any()
+                    }
+                    None => ().into_any(),
+                }}
+            </div>
+            <div class="flex flex-1" />
+        </Show>
+        </FormWindowGate>
+    }
+}
+
+/// # Student Form Route
+///
+/// Shows the student form whose path matches the current page, such as `additional/athletics`.
+/// The paths come from the stored form definitions, so that every form in the sidebar has a page,
+/// including forms that were created or moved in the form builder.
+#[component]
+pub fn StudentFormRoute() -> impl IntoView {
+    let location = use_location();
+    let path = Memo::new(move |_| {
+        location.pathname.with(|pathname| {
+            pathname
+                .trim_start_matches("/students/")
+                .trim_end_matches('/')
+                .to_string()
+        })
+    });
+
+    let definition = Resource::new(
+        move || path.get(),
+        async move |path| get_student_form_by_path(path).await,
+    );
+
+    view! {
+        <Transition fallback=Loading>
+            {move || {
+                definition
+                    .get()
+                    .map(|result| match result {
+                        Ok(definition) => {
+                            view! { <StudentFormPage form_id=definition.id /> }.into_any()
+                        }
+                        Err(err) => {
+                            view! { <p class="mx-auto mt-10">{err.to_string()}</p> }.into_any()
+                        }
+                    })
+            }}
+        </Transition>
+    }
+}
@@ -1,35 +1,121 @@
-use crate::common::ValueType;
+use crate::common::{
+    ExpandableInfo, ExpandableInfoReactive, FieldErrors, FormDraft, ValuePatch, ValueType,
+};
 use crate::components::{Toast, ToastContext};
-use crate::pages::api::students::{PutStudentData, get_student_data};
+use crate::pages::api::students::{
+    PatchStudentData, SaveStudentDraft, delete_student_draft, get_student_data, get_student_draft,
+};
+use crate::pages::student::ApplicationCompletionContext;
 use crate::utils::get_user_claims;
+use chrono::{DateTime, FixedOffset};
+use leptos::logging::error;
 use leptos::prelude::*;
+use leptos::serde_json;
+use leptos::task::spawn_local;
+use leptos::web_sys::{Storage, js_sys};
+use leptos_oidc::AuthSignal;
 use std::collections::HashMap;
+use std::time::Duration;
+use traits::{AsReactive, DirtyTracking};
 use uuid::Uuid;
 
 pub struct StudentFormInfo {
     pub data_map: RwSignal<HashMap<String, ValueType>>,
-    pub user_id: Memo<Option<String>>,
     pub submit_action: Callback<()>,
-    pub refresh_trigger: Trigger,
     pub data_resource: Resource<Result<HashMap<String, ValueType>, ServerFnError>>,
     pub submit_pending: Memo<bool>,
+    /// Whether the form has changed since it was loaded or last submitted.
+    pub unsaved_changes: Memo<bool>,
+    /// Discards any unsaved changes, going back to the loaded or last submitted data.
+    pub discard_changes: Callback<()>,
+    /// The errors that the server found in the last submission. Errors are dropped once the field
+    /// that they belong to is changed.
+    pub field_errors: Memo<FieldErrors>,
+    /// When the unsaved changes were last autosaved as a draft, such as `3:05 PM`.
+    pub draft_saved_at: Signal<Option<String>>,
+    /// A draft from an earlier visit that can be restored over the loaded data.
+    pub restorable_draft: Signal<Option<FormDraft>>,
+    /// Replaces the form's data with the restorable draft.
+    pub restore_draft: Callback<()>,
+    /// Deletes the restorable draft without using it.
+    pub dismiss_draft: Callback<()>,
 }
 
+/// How long to wait after the last change before autosaving a draft.
+const DRAFT_DELAY: Duration = Duration::from_secs(1);
+
+/// Gets the browser's local storage, if it can be used.
+fn local_storage() -> Option<Storage> {
+    window().local_storage().ok().flatten()
+}
+
+/// Reads a draft from local storage. Drafts that can't be read are treated as missing.
+fn read_local_draft(key: &str) -> Option<FormDraft> {
+    let draft = local_storage()?.get_item(key).ok()??;
+    serde_json::from_str(&draft).ok()
+}
+
+/// Writes a draft to local storage. Storage may be full or disabled, in which case the draft is
+/// only logged as lost.
+fn write_local_draft(key: &str, draft: &FormDraft) {
+    let result = serde_json::to_string(draft)
+        .map_err(|err| err.to_string())
+        .and_then(|draft| {
+            local_storage()
+                .ok_or_else(|| "Local storage isn't available".to_string())?
+                .set_item(key, &draft)
+                .map_err(|err| format!("{err:?}"))
+        });
+    if let Err(err) = result {
+        error!("Couldn't save draft to local storage: {}", err);
+    }
+}
+
+/// Removes a draft from local storage.
+fn remove_local_draft(key: &str) {
+    if let Some(storage) = local_storage() {
+        let _ = storage.remove_item(key);
+    }
+}
+
+/// Formats when a draft was saved as a time in the browser's time zone, such as `3:05 PM`.
+pub fn format_draft_time(saved_at: i64) -> String {
+    // The offset is in minutes behind UTC.
+    let offset = js_sys::Date::new(&(saved_at as f64).into()).get_timezone_offset() as i32 * 60;
+    DateTime::from_timestamp_millis(saved_at)
+        .zip(FixedOffset::west_opt(offset))
+        .map(|(time, offset)| time.with_timezone(&offset).format("%-I:%M %p").to_string())
+        .unwrap_or_default()
+}
+
+/// Sets up one of the student forms, which loads the student's data and submits only the fields
+/// that changed.
+///
+/// Unsaved changes are autosaved as a [`FormDraft`] in local storage once the student stops
+/// typing, and also on the server if `server_drafts` is set, so that they survive an expired
+/// session or a closed tab. When the form is loaded again, the newest draft that was started from
+/// the saved data can be restored. Drafts are deleted once the form is submitted or its changes
+/// are discarded.
 pub fn use_student_form(
     form_type: impl Into<Signal<String>>,
     enable_toasts: bool,
+    server_drafts: bool,
 ) -> StudentFormInfo {
     let form_type = form_type.into();
 
     let user_claims = get_user_claims();
     let user_id = Memo::new(move |_| user_claims.get().map(|info| info.claims.subject.clone()));
+    let auth = expect_context::<AuthSignal>();
+    let access_token =
+        Memo::new(move |_| auth.with(|auth| auth.authenticated().map(|a| a.access_token())));
 
-    let data_map = RwSignal::new(HashMap::new());
+    // The form's data is kept in a reactive struct so that unsaved changes can be tracked.
+    let form: ExpandableInfoReactive = ExpandableInfo::new(String::new()).as_reactive();
+    let data_map = form.data;
 
-    let refresh_trigger = Trigger::new();
     let data_resource = Resource::new(
-        move || (user_id.get(), form_type.get(), refresh_trigger.track()),
-        async move |(id, form_type, _)| {
+        move || (user_id.get(), form_type.get()),
+        async move |(id, form_type)| {
             let Some(id) = id else {
                 return Ok(HashMap::new());
             };
@@ -37,41 +123,192 @@ pub fn use_student_form(
         },
     );
 
+    // The data that's known to be stored, which submissions are compared against so that only
+    // the fields that changed are sent.
+    let saved_data = StoredValue::new(HashMap::<String, ValueType>::new());
+
+    let draft_key =
+        Memo::new(move |_| Some(format!("draft:{}:{}", user_id.get()?, form_type.get())));
+    let restorable_draft = RwSignal::new(None::<FormDraft>);
+    let draft_saved_at = RwSignal::new(None::<String>);
+
     Effect::new(move || {
-        data_resource.with(|map_opt| {
-            let Some(Ok(map)) = map_opt else {
-                return;
-            };
+        let Some(Ok(map)) = data_resource.get() else {
+            return;
+        };
+
+        saved_data.set_value(map.clone());
+        data_map.set(map);
+        form.mark_saved();
+
+        // Look for a draft that was left behind, preferring whichever of the local and server
+        // drafts is newer.
+        restorable_draft.set(None);
+        draft_saved_at.set(None);
+        let (Some(token), Some(key)) = (access_token.get_untracked(), draft_key.get_untracked())
+        else {
+            return;
+        };
+        let local_draft = read_local_draft(&key);
+        let saved = saved_data.get_value();
+        if !server_drafts {
+            restorable_draft.set(FormDraft::newest_restorable([local_draft], &saved));
+            return;
+        }
 
-            data_map.set(map.to_owned());
+        let form_type = form_type.get_untracked();
+        spawn_local(async move {
+            let server_draft = get_student_draft(token, form_type)
+                .await
+                .unwrap_or_else(|err| {
+                    error!("Couldn't get draft from the server: {}", err);
+                    None
+                });
+            restorable_draft.set(FormDraft::newest_restorable(
+                [local_draft, server_draft],
+                &saved,
+            ));
         });
     });
 
-    let submit_action = ServerAction::<PutStudentData>::new();
+    // Autosave unsaved changes once the student stops typing. Nothing is saved while a draft is
+    // waiting to be restored, so that the draft isn't overwritten before the student decides.
+    let save_draft_action = ServerAction::<SaveStudentDraft>::new();
+    let generation = StoredValue::new(0usize);
+    Effect::new(move || {
+        data_map.track();
+        generation.update_value(|generation| *generation += 1);
+        let current = generation.get_value();
+        if !form.is_dirty() || restorable_draft.with(Option::is_some) {
+            return;
+        }
+
+        set_timeout(
+            move || {
+                if generation.try_get_value() != Some(current) {
+                    return;
+                }
+                let (Some(token), Some(key)) =
+                    (access_token.get_untracked(), draft_key.get_untracked())
+                else {
+                    return;
+                };
+
+                let draft = FormDraft {
+                    data: data_map.get_untracked(),
+                    base: saved_data.get_value(),
+                    saved_at: js_sys::Date::now() as i64,
+                };
+                write_local_draft(&key, &draft);
+                draft_saved_at.set(Some(format_draft_time(draft.saved_at)));
+                if server_drafts {
+                    save_draft_action.dispatch(SaveStudentDraft {
+                        access_token: token,
+                        data_type: form_type.get_untracked(),
+                        draft,
+                    });
+                }
+            },
+            DRAFT_DELAY,
+        );
+    });
+
+    let clear_drafts = move || {
+        generation.update_value(|generation| *generation += 1);
+        restorable_draft.set(None);
+        draft_saved_at.set(None);
+        let (Some(token), Some(key)) = (access_token.get_untracked(), draft_key.get_untracked())
+        else {
+            return;
+        };
+
+        remove_local_draft(&key);
+        if server_drafts {
+            let form_type = form_type.get_untracked();
+            spawn_local(async move {
+                if let Err(err) = delete_student_draft(token, form_type).await {
+                    error!("Couldn't delete draft from the server: {}", err);
+                }
+            });
+        }
+    };
+
+    // The submitted data is kept so that it can be marked as saved once the submission succeeds.
+    let submitted_data = StoredValue::new(None::<HashMap<String, ValueType>>);
+    let submit_action = ServerAction::<PatchStudentData>::new();
     let on_submit = move || {
-        if let Some(id) = user_id.get() {
-            submit_action.dispatch(PutStudentData {
-                subject: id,
+        if let Some(token) = access_token.get() {
+            let data = data_map.get();
+            let patch = saved_data.with_value(|saved| ValuePatch::diff(saved, &data));
+            submitted_data.set_value(Some(data));
+            submit_action.dispatch(PatchStudentData {
+                access_token: token,
                 data_type: form_type.get(),
-                data_map: data_map.get(),
+                set: patch.set,
+                remove: patch.remove,
             });
         }
     };
 
-    if enable_toasts {
-        let mut toasts = expect_context::<ToastContext>();
-        Effect::watch(
-            move || submit_action.value().get(),
-            move |value, _, _| {
-                let Some(result) = value else {
-                    return;
-                };
+    // The errors from a rejected submission, along with the data that was rejected.
+    let rejected = RwSignal::new(None::<(FieldErrors, HashMap<String, ValueType>)>);
+    let field_errors = Memo::new(move |_| {
+        rejected.with(|rejected| {
+            let Some((errors, submitted)) = rejected else {
+                return FieldErrors::new();
+            };
 
+            data_map.with(|data| {
+                errors
+                    .iter()
+                    .filter(|(path, _)| {
+                        let key = path.split(['.', '[']).next().unwrap_or_default();
+                        data.get(key) == submitted.get(key)
+                    })
+                    .map(|(path, msg)| (path.clone(), msg.clone()))
+                    .collect()
+            })
+        })
+    });
+
+    let toasts = enable_toasts.then(expect_context::<ToastContext>);
+    let completion = use_context::<ApplicationCompletionContext>();
+    Effect::watch(
+        move || submit_action.value().get(),
+        move |value, _, _| {
+            let Some(result) = value else {
+                return;
+            };
+
+            if let Some(submitted) = submitted_data.get_value() {
+                match result {
+                    // Anything that changed while the submission was pending is still unsaved.
+                    Ok(Ok(())) => {
+                        if data_map.with_untracked(|data| *data == submitted) {
+                            form.mark_saved();
+                        }
+                        saved_data.set_value(submitted);
+                        rejected.set(None);
+                        clear_drafts();
+                        if let Some(ApplicationCompletionContext(completion)) = completion {
+                            completion.refetch();
+                        }
+                    }
+                    Ok(Err(errors)) => rejected.set(Some((errors.clone(), submitted))),
+                    Err(_) => {}
+                }
+            }
+
+            if let Some(mut toasts) = toasts {
                 let toast = match result {
-                    Ok(_) => Toast::new()
+                    Ok(Ok(())) => Toast::new()
                         .id(Uuid::new_v4())
                         .header("Submission Successful")
                         .msg("You may continue editing or fill out another form."),
+                    Ok(Err(_)) => Toast::new()
+                        .id(Uuid::new_v4())
+                        .header("Submission Failed")
+                        .msg("Some of your answers need to be fixed before they can be saved."),
                     Err(e) => Toast::new()
                         .id(Uuid::new_v4())
                         .header("Submission Failed")
@@ -80,17 +317,30 @@ pub fn use_student_form(
 
                 toasts.toast(toast);
                 untrack(move || submit_action.clear());
-            },
-            false,
-        );
-    }
+            }
+        },
+        false,
+    );
 
     StudentFormInfo {
         data_map,
-        refresh_trigger,
         data_resource,
-        user_id,
         submit_action: on_submit.into(),
         submit_pending: submit_action.pending(),
+        unsaved_changes: Memo::new(move |_| form.is_dirty()),
+        discard_changes: Callback::new(move |_| {
+            form.reset();
+            clear_drafts();
+        }),
+        field_errors,
+        draft_saved_at: draft_saved_at.into(),
+        restorable_draft: restorable_draft.into(),
+        restore_draft: Callback::new(move |_| {
+            if let Some(draft) = restorable_draft.get_untracked() {
+                data_map.set(draft.data);
+            }
+            restorable_draft.set(None);
+        }),
+        dismiss_draft: Callback::new(move |_| clear_drafts()),
     }
 }
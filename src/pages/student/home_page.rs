@@ -1,6 +1,97 @@
-use crate::components::DashboardButton;
+use crate::common::{ApplicationCompletion, CompletionStatus, FormCompletion};
+use crate::components::{DashboardButton, Loading};
+use crate::pages::student::ApplicationCompletionContext;
 use leptos::prelude::*;
 
+/// The number of missing fields that are named for each form in the checklist.
+const MISSING_SHOWN: usize = 3;
+
+/// # Application Checklist
+///
+/// Lists every form and upload of the application with the student's progress on each, so that
+/// they know what's left before the deadline.
+#[component]
+fn ApplicationChecklist() -> impl IntoView {
+    let ApplicationCompletionContext(completion) = expect_context();
+
+    view! {
+        <div class="flex flex-col gap-2 w-full max-w-xl p-4 rounded-md border border-gray-300">
+            <Transition fallback=Loading>
+                {move || {
+                    completion
+                        .get()
+                        .map(|result| match result {
+                            Ok(completion) => {
+                                view! { <ChecklistItems completion=completion /> }.into_any()
+                            }
+                            Err(err) => {
+                                view! { <p>"Couldn't load your progress: "{err.to_string()}</p> }
+                                    .into_any()
+                            }
+                        })
+                }}
+            </Transition>
+        </div>
+    }
+}
+
+#[component]
+fn ChecklistItems(completion: ApplicationCompletion) -> impl IntoView {
+    let summary = if completion.is_complete() {
+        "Your application is complete! You can still make changes until the due date.".to_string()
+    } else {
+        format!("Your application is {}% complete.", completion.percent())
+    };
+
+    view! {
+        <div class="font-bold text-xl">"Application Checklist"</div>
+        <div>{summary}</div>
+        <ul class="flex flex-col gap-2">
+            {completion
+                .forms
+                .into_iter()
+                .map(|form| view! { <ChecklistItem form=form /> })
+                .collect_view()}
+        </ul>
+    }
+}
+
+#[component]
+fn ChecklistItem(form: FormCompletion) -> impl IntoView {
+    let (icon, status) = match form.status() {
+        CompletionStatus::Complete => ("✓", "Complete".to_string()),
+        CompletionStatus::InProgress => {
+            ("○", format!("{} of {} answered", form.answered, form.required))
+        }
+        CompletionStatus::NotStarted => ("○", "Not started".to_string()),
+        CompletionStatus::Optional => ("–", "Optional".to_string()),
+    };
+    let missing = match form.missing.len() {
+        0 => None,
+        count if count > MISSING_SHOWN => Some(format!(
+            "Still needed: {}, and {} more.",
+            form.missing[..MISSING_SHOWN].join(", "),
+            count - MISSING_SHOWN
+        )),
+        _ => Some(format!("Still needed: {}.", form.missing.join(", "))),
+    };
+
+    view! {
+        <li class="flex flex-row gap-2">
+            <span class="w-4 font-bold">{icon}</span>
+            <div class="flex flex-col flex-1">
+                <div class="flex flex-row gap-2">
+                    <a class="text-blue-500 underline" href=format!("/students/{}", form.path)>
+                        {form.title}
+                    </a>
+                    <span class="ml-auto text-sm text-gray-600">{status}</span>
+                </div>
+                {missing.map(|missing| view! { <span class="text-sm text-gray-600">{missing}</span> })}
+            </div>
+        </li>
+    }
+}
+
 #[component]
 pub fn StudentHomePage() -> impl IntoView {
     view! {
@@ -14,6 +105,7 @@ pub fn StudentHomePage() -> impl IntoView {
                 </a>"."
             </div>
             <div class="text-lg">"The due date for this form is on April 2nd, 2026 at 2:05pm."</div>
+            <ApplicationChecklist />
             <div class="text-lg">"Here are some links to get you started:"</div>
             <DashboardButton
                 title="Scholarship Book"
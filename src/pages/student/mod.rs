@@ -1,30 +1,14 @@
-mod academics_page;
 mod additional_page;
-mod athletics_page;
-mod closed_form;
-mod demographics_page;
 mod eligibility_page;
-mod extracurriculars_page;
-mod family_info_page;
 mod financial_page;
+mod form_page;
 mod form_setup;
 mod home_page;
 mod shell;
-mod specific_requirements_page;
-mod university_page;
-mod work_experience_page;
 
-pub use academics_page::*;
 pub use additional_page::*;
-pub use athletics_page::*;
-pub use closed_form::*;
-pub use demographics_page::*;
 pub use eligibility_page::*;
-pub use extracurriculars_page::*;
-pub use family_info_page::*;
 pub use financial_page::*;
+pub use form_page::*;
 pub use home_page::*;
 pub use shell::*;
-pub use specific_requirements_page::*;
-pub use university_page::*;
-pub use work_experience_page::*;
@@ -1,12 +1,104 @@
-use crate::common::{SubTabInfo, TabInfo};
+use crate::common::{ApplicationCompletion, FormDefinition, SubTabInfo, TabInfo};
 use crate::components::login::StudentLoginContext;
 use crate::components::{Banner, Loading, TabSidebarList};
 use crate::pages::UnauthenticatedPage;
+use crate::pages::api::{get_application_completion, get_student_forms};
 use leptos::prelude::*;
-use leptos_oidc::{AuthLoaded, Authenticated};
+use leptos_oidc::{AuthLoaded, AuthSignal, Authenticated};
+
+/// The tab that contains every form with a nested path, such as `additional/academics`.
+const ADDITIONAL_PATH: &str = "additional";
+
+/// The student's progress through the application, which is shared by the sidebar and the
+/// student pages so that it's only loaded once. Pages refetch it after saving a form.
+#[derive(Clone, Copy)]
+pub struct ApplicationCompletionContext(
+    pub Resource<Result<ApplicationCompletion, ServerFnError>>,
+);
+
+/// Creates the sidebar tabs from the student form definitions. Forms with a top-level path get
+/// their own tab, and forms under `additional/` are shown as sub-tabs of the "Additional Info"
+/// tab. Each form gets a badge with its progress once the student's completion has loaded.
+fn create_tabs(
+    forms: &[FormDefinition],
+    completion: Option<&ApplicationCompletion>,
+) -> Vec<TabInfo> {
+    let badge = |path: &str| Some(completion?.form(path)?.badge());
+
+    let mut tabs = vec![TabInfo::new("Home", "home", None)];
+    let mut additional = Vec::new();
+
+    for form in forms {
+        match form.path.split_once('/') {
+            Some((ADDITIONAL_PATH, path)) => {
+                additional
+                    .push(SubTabInfo::new(form.tab_text.clone(), path).badge(badge(&form.path)));
+            }
+            Some(_) => {}
+            None => tabs.push(
+                TabInfo::new(form.tab_text.clone(), form.path.clone(), None)
+                    .badge(badge(&form.path)),
+            ),
+        }
+    }
+
+    // The financial forms are file uploads, so they aren't part of the form definitions.
+    let financials_path = format!("{ADDITIONAL_PATH}/financials");
+    additional.push(SubTabInfo::new("Financials", "financials").badge(badge(&financials_path)));
+
+    // The "Additional Info" tab counts the forms within it that still need answers.
+    let additional_badge = completion.map(|completion| {
+        let prefix = format!("{ADDITIONAL_PATH}/");
+        match completion
+            .incomplete()
+            .filter(|form| form.path.starts_with(&prefix))
+            .count()
+        {
+            0 => "✓".to_string(),
+            left => format!("{left} left"),
+        }
+    });
+    tabs.push(
+        TabInfo::new("Additional Info", ADDITIONAL_PATH, Some(additional)).badge(additional_badge),
+    );
+    tabs.push(TabInfo::new("Eligible Scholarships", "eligibility", None));
+
+    tabs
+}
+
+/// The sidebar of the student pages, which shows the progress of each form.
+#[component]
+fn StudentTabs(forms: Resource<Result<Vec<FormDefinition>, ServerFnError>>) -> impl IntoView {
+    let auth = expect_context::<AuthSignal>();
+    let access_token =
+        Memo::new(move |_| auth.with(|auth| auth.authenticated().map(|a| a.access_token())));
+    let completion = Resource::new(
+        move || access_token.get(),
+        async move |access_token| match access_token {
+            Some(access_token) => get_application_completion(access_token).await,
+            None => Ok(ApplicationCompletion::default()),
+        },
+    );
+    provide_context(ApplicationCompletionContext(completion));
+
+    let tabs = Signal::derive(move || {
+        let completion = completion.get().and_then(Result::ok);
+        forms.with(|forms| {
+            let forms = forms.as_ref().and_then(|forms| forms.as_ref().ok());
+            create_tabs(
+                forms.map(Vec::as_slice).unwrap_or_default(),
+                completion.as_ref(),
+            )
+        })
+    });
+
+    view! { <TabSidebarList base_path="students" tabs=tabs /> }
+}
 
 #[component]
 pub fn StudentShell() -> impl IntoView {
+    let forms = Resource::new(|| (), async move |_| get_student_forms().await);
+
     // Create login context
     view! {
         <StudentLoginContext>
@@ -19,30 +111,7 @@ pub fn StudentShell() -> impl IntoView {
                 <div class="flex flex-1 min-h-0">
                     <AuthLoaded fallback=Loading>
                         <Authenticated unauthenticated=UnauthenticatedPage>
-                            <TabSidebarList
-                                base_path="students"
-                                tabs=vec![
-                                    TabInfo::new("Home", "home", None),
-                                    TabInfo::new("Demographics", "demographics", None),
-                                    TabInfo::new(
-                                        "Additional Info",
-                                        "additional",
-                                        Some(
-                                            vec![
-                                                SubTabInfo::new("Academics", "academics"),
-                                                SubTabInfo::new("Athletics", "athletics"),
-                                                SubTabInfo::new("Work Experience", "work-experience"),
-                                                SubTabInfo::new("Extracurriculars", "extracurriculars"),
-                                                SubTabInfo::new("University Info", "university"),
-                                                SubTabInfo::new("Family Info", "family-info"),
-                                                SubTabInfo::new("Specifics", "specifics"),
-                                                SubTabInfo::new("Financials", "financials"),
-                                            ],
-                                        ),
-                                    ),
-                                    TabInfo::new("Eligible Scholarships", "eligibility", None),
-                                ]
-                            />
+                            <StudentTabs forms=forms />
                         </Authenticated>
                     </AuthLoaded>
                 </div>
@@ -1,6 +1,7 @@
 use leptos::prelude::*;
 use leptos_oidc::{Algorithm, AuthSignal, TokenData};
-use crate::common::UserClaims;
+use crate::common::{UserClaims, to_local_input};
+use chrono::{DateTime, FixedOffset};
 
 pub fn use_origin() -> String {
     #[cfg(target_arch = "wasm32")] {
@@ -31,3 +32,28 @@ pub fn get_user_claims() -> Signal<Option<TokenData<UserClaims>>> {
         })
     })
 }
+
+/// Converts a time into the browser's time zone, for use as the value of a `datetime-local`
+/// input. This function only works in the browser.
+pub fn to_browser_input(date: &DateTime<FixedOffset>) -> String {
+    use leptos::web_sys::js_sys;
+    // The offset is in minutes behind UTC.
+    let millis = date.timestamp_millis() as f64;
+    let offset = js_sys::Date::new(&millis.into()).get_timezone_offset() as i32 * 60;
+    FixedOffset::west_opt(offset)
+        .map(|offset| to_local_input(&date.with_timezone(&offset)))
+        .unwrap_or_else(|| to_local_input(date))
+}
+
+/// Gets the browser's offset from UTC at the time in a `datetime-local` input's value, which
+/// accounts for daylight saving time. This function only works in the browser.
+pub fn browser_offset(local: &str) -> Option<FixedOffset> {
+    use leptos::web_sys::js_sys;
+    // Times without a zone are parsed as local time, and the offset is in minutes behind UTC.
+    let date = js_sys::Date::new(&local.into());
+    let offset = date.get_timezone_offset();
+    if offset.is_nan() {
+        return None;
+    }
+    FixedOffset::west_opt(offset as i32 * 60)
+}
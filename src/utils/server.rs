@@ -1,7 +1,17 @@
 use std::collections::HashMap;
+use std::time::Duration;
 use aws_config::SdkConfig;
+use aws_sdk_dynamodb::error::ProvideErrorMetadata;
+use aws_sdk_dynamodb::types::WriteRequest;
+use leptos::logging::error;
+use leptos::prelude::ServerFnError;
 use crate::common::ValueType;
 
+/// The maximum number of requests that DynamoDB accepts in a single `batch_write_item` call.
+const BATCH_WRITE_LIMIT: usize = 25;
+/// The number of times unprocessed batch requests are retried before giving up.
+const BATCH_WRITE_RETRIES: u32 = 5;
+
 /// Creates an [`SdkConfig`] struct for use with AWS SDK structs.
 pub async fn create_aws_config() -> SdkConfig {
     aws_config::load_defaults(aws_config::BehaviorVersion::latest()).await
@@ -14,4 +24,56 @@ pub async fn create_dynamo_client() -> aws_sdk_dynamodb::Client {
 
 pub fn into_attr_map(map: HashMap<String, ValueType>) -> HashMap<String, aws_sdk_dynamodb::types::AttributeValue> {
     map.into_iter().map(|(k, v)| (k, v.into())).collect()
-}
\ No newline at end of file
+}
+
+/// Sends a list of write requests to a single table using `batch_write_item`.
+///
+/// DynamoDB only accepts 25 requests per batch, so the requests are sent in chunks. Any requests
+/// that DynamoDB returns as unprocessed are retried with an increasing delay. Returns an error if
+/// a batch fails, or if requests are still unprocessed after all retries.
+pub async fn batch_write_requests(
+    client: &aws_sdk_dynamodb::Client,
+    table_name: &str,
+    requests: Vec<WriteRequest>,
+) -> Result<(), ServerFnError> {
+    for chunk in requests.chunks(BATCH_WRITE_LIMIT) {
+        let mut pending = chunk.to_vec();
+        let mut attempt = 0;
+
+        while !pending.is_empty() {
+            if attempt > 0 {
+                tokio::time::sleep(Duration::from_millis(50 * 2u64.pow(attempt))).await;
+            }
+
+            let output = client
+                .batch_write_item()
+                .request_items(table_name, pending)
+                .send()
+                .await
+                .map_err(|err| {
+                    let msg = err.message().unwrap_or("Unknown error occurred").to_string();
+                    error!("{}", msg);
+                    ServerFnError::new(msg)
+                })?;
+
+            pending = output
+                .unprocessed_items
+                .and_then(|mut items| items.remove(table_name))
+                .unwrap_or_default();
+
+            attempt += 1;
+            if !pending.is_empty() && attempt > BATCH_WRITE_RETRIES {
+                let msg = format!(
+                    "Couldn't write {} items to {} after {} retries.",
+                    pending.len(),
+                    table_name,
+                    BATCH_WRITE_RETRIES
+                );
+                error!("{}", msg);
+                return Err(ServerFnError::new(msg));
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -80,3 +80,52 @@ pub trait ReactiveCapture {
     /// Captures the current state of the reactive struct into a non-reactive struct.
     fn capture(&self) -> Self::CaptureType;
 }
+
+/// Trait that tracks which fields of a reactive struct have changed since it was last saved.
+///
+/// The reactive struct keeps a snapshot of the non-reactive struct. The snapshot is taken when the
+/// reactive struct is created, and whenever [`mark_saved`](DirtyTracking::mark_saved) is called.
+/// Every field is compared against the snapshot, so a field that is changed and then changed back
+/// is no longer dirty.
+///
+/// For example:
+///
+/// ```ignore
+/// let user_reactive = User { id: 1, name: "Jane".to_string() }.as_reactive();
+/// user_reactive.name.set("John".to_string());
+///
+/// assert_eq!(user_reactive.changed_fields(), vec!["name"]);
+/// let patch: UserPatch = user_reactive.capture_patch();
+/// assert_eq!(patch.id, None);
+/// assert_eq!(patch.name, Some("John".to_string()));
+///
+/// // Go back to the snapshot...
+/// user_reactive.reset();
+/// // ...or save the changes as the new snapshot.
+/// user_reactive.mark_saved();
+/// ```
+pub trait DirtyTracking: ReactiveCapture {
+    /// A version of the captured struct where every field is an `Option`. Only fields that have
+    /// changed are `Some`.
+    type Patch;
+
+    /// Gets the names of every field that differs from the snapshot. Tuple struct fields are
+    /// named by their index.
+    ///
+    /// This is reactive, so it can be used to show that there are unsaved changes.
+    fn changed_fields(&self) -> Vec<&'static str>;
+
+    /// Returns true if any field differs from the snapshot. This is reactive.
+    fn is_dirty(&self) -> bool {
+        !self.changed_fields().is_empty()
+    }
+
+    /// Captures only the fields that differ from the snapshot.
+    fn capture_patch(&self) -> Self::Patch;
+
+    /// Sets every field back to the value in the snapshot.
+    fn reset(&self);
+
+    /// Replaces the snapshot with the current values, so that nothing is dirty.
+    fn mark_saved(&self);
+}